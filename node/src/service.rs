@@ -138,6 +138,13 @@ pub fn new_partial(
 			Some("//Alice"),
 		)
 		.expect("Creating key with account Alice should succeed.");
+		// heartbeat key of the (dev) block producer Alice, to be set via `set_heartbeat_key`
+		sp_keystore::Keystore::sr25519_generate_new(
+			&*keystore,
+			node_template_runtime::pallet_eosio_system::KEY_TYPE,
+			Some("//Alice"),
+		)
+		.expect("Creating key with account Alice should succeed.");
 	}
 
 	Ok(sc_service::PartialComponents {
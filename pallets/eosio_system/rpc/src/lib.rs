@@ -0,0 +1,272 @@
+//! RPC interface for the EOSIO System pallet.
+//!
+//! Exposes the `eosio_*` methods i.e. the equivalents of EOSIO's `get_producers` & `get_account`
+//! along with the producer schedule & the unclaimed rewards of a producer.
+
+use std::{convert::TryInto, marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use jsonrpsee::{
+	core::{Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::traits::Block as BlockT;
+
+pub use pallet_eosio_system_runtime_api::EosioSystemApi as EosioSystemRuntimeApi;
+
+/// Producer as per `eosio_getProducers`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Producer<AccountId, BlockNumber> {
+	pub owner: AccountId,
+	/// Total vote weight of the votes received.
+	pub total_votes: NumberOrHex,
+	pub is_active: bool,
+	pub url: String,
+	pub unpaid_blocks: u32,
+	pub last_claim_time: BlockNumber,
+	/// ISO 3166-1 numeric country code.
+	pub location: u16,
+	pub last_heartbeat: BlockNumber,
+}
+
+/// Unstaked tokens waiting for their unbonding period to end.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Unbonding<BlockNumber> {
+	pub amount: NumberOrHex,
+	pub unlock_at: BlockNumber,
+}
+
+/// Stake & votes of an account as per `eosio_getAccount`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Account<AccountId, BlockNumber> {
+	pub staked: NumberOrHex,
+	pub unbonding: Vec<Unbonding<BlockNumber>>,
+	pub proxy: Option<AccountId>,
+	pub producers: Vec<AccountId>,
+	pub last_vote_weight: NumberOrHex,
+	pub is_proxy: bool,
+	/// Vote weight delegated to the account as a proxy.
+	pub proxied_vote_weight: NumberOrHex,
+}
+
+/// Producer schedule as per `eosio_getProducerSchedule`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Schedule<AccountId> {
+	pub version: u32,
+	pub active: Vec<AccountId>,
+	pub standby: Vec<AccountId>,
+}
+
+/// Estimate of a producer's rewards as per `eosio_getUnclaimedRewards`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct UnclaimedRewards<BlockNumber> {
+	pub per_block_pay: NumberOrHex,
+	pub per_vote_pay: NumberOrHex,
+	pub claimable_at: BlockNumber,
+}
+
+#[rpc(client, server)]
+pub trait EosioSystemApi<BlockHash, AccountId, BlockNumber> {
+	/// Up to `limit` (default: all) producers sorted by their total votes (descending).
+	#[method(name = "eosio_getProducers")]
+	fn get_producers(
+		&self,
+		limit: Option<u32>,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<Producer<AccountId, BlockNumber>>>;
+
+	/// The stake & votes of the account (if any).
+	#[method(name = "eosio_getAccount")]
+	fn get_account(
+		&self,
+		account: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<Account<AccountId, BlockNumber>>>;
+
+	/// The current active & standby producers.
+	#[method(name = "eosio_getProducerSchedule")]
+	fn get_producer_schedule(&self, at: Option<BlockHash>) -> RpcResult<Schedule<AccountId>>;
+
+	/// Estimate of the rewards the producer would get by claiming now (if registered).
+	#[method(name = "eosio_getUnclaimedRewards")]
+	fn get_unclaimed_rewards(
+		&self,
+		producer: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<UnclaimedRewards<BlockNumber>>>;
+}
+
+/// Provides the `eosio_*` RPC methods.
+///
+/// `M` is `(Block, Balance)` of the runtime.
+pub struct EosioSystem<C, M> {
+	client: Arc<C>,
+	_marker: PhantomData<M>,
+}
+
+impl<C, M> EosioSystem<C, M> {
+	/// Create new `EosioSystem` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+	/// The balance doesn't fit in the RPC representation.
+	BalanceOverflow,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::BalanceOverflow => 2,
+		}
+	}
+}
+
+fn runtime_error(message: &'static str, e: impl ToString) -> JsonRpseeError {
+	CallError::Custom(ErrorObject::owned(Error::RuntimeError.into(), message, Some(e.to_string())))
+		.into()
+}
+
+fn to_number_or_hex<Balance: Copy + TryInto<NumberOrHex>>(
+	value: Balance,
+) -> RpcResult<NumberOrHex> {
+	value.try_into().map_err(|_| {
+		CallError::Custom(ErrorObject::owned(
+			Error::BalanceOverflow.into(),
+			"Balance doesn't fit in NumberOrHex representation",
+			None::<()>,
+		))
+		.into()
+	})
+}
+
+impl<C, Block, AccountId, Balance, BlockNumber>
+	EosioSystemApiServer<<Block as BlockT>::Hash, AccountId, BlockNumber>
+	for EosioSystem<C, (Block, Balance)>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: EosioSystemRuntimeApi<Block, AccountId, Balance, BlockNumber>,
+	AccountId: Codec + Clone + Send + Sync + 'static + Serialize + for<'de> Deserialize<'de>,
+	Balance: Codec + Copy + TryInto<NumberOrHex> + Send + Sync + 'static,
+	BlockNumber: Codec + Send + Sync + 'static + Serialize + for<'de> Deserialize<'de>,
+{
+	fn get_producers(
+		&self,
+		limit: Option<u32>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<Producer<AccountId, BlockNumber>>> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let producers = api
+			.get_producers(at_hash, limit.unwrap_or(u32::MAX))
+			.map_err(|e| runtime_error("Unable to query producers.", e))?;
+
+		Ok(producers
+			.into_iter()
+			.map(|p| Producer {
+				owner: p.owner,
+				total_votes: p.total_votes.into(),
+				is_active: p.is_active,
+				url: String::from_utf8_lossy(&p.url).into_owned(),
+				unpaid_blocks: p.unpaid_blocks,
+				last_claim_time: p.last_claim_time,
+				location: p.location,
+				last_heartbeat: p.last_heartbeat,
+			})
+			.collect())
+	}
+
+	fn get_account(
+		&self,
+		account: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<Account<AccountId, BlockNumber>>> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let voter = match api
+			.get_voter(at_hash, account)
+			.map_err(|e| runtime_error("Unable to query account.", e))?
+		{
+			Some(voter) => voter,
+			None => return Ok(None),
+		};
+
+		let unbonding = voter
+			.unbonding
+			.into_iter()
+			.map(|(amount, unlock_at)| {
+				Ok(Unbonding { amount: to_number_or_hex(amount)?, unlock_at })
+			})
+			.collect::<RpcResult<Vec<_>>>()?;
+
+		Ok(Some(Account {
+			staked: to_number_or_hex(voter.staked)?,
+			unbonding,
+			proxy: voter.proxy,
+			producers: voter.producers,
+			last_vote_weight: voter.last_vote_weight.into(),
+			is_proxy: voter.is_proxy,
+			proxied_vote_weight: voter.proxied_vote_weight.into(),
+		}))
+	}
+
+	fn get_producer_schedule(
+		&self,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Schedule<AccountId>> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let schedule = api
+			.get_producer_schedule(at_hash)
+			.map_err(|e| runtime_error("Unable to query producer schedule.", e))?;
+
+		Ok(Schedule {
+			version: schedule.version,
+			active: schedule.active,
+			standby: schedule.standby,
+		})
+	}
+
+	fn get_unclaimed_rewards(
+		&self,
+		producer: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<UnclaimedRewards<BlockNumber>>> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let estimate = match api
+			.get_unclaimed_rewards(at_hash, producer)
+			.map_err(|e| runtime_error("Unable to query unclaimed rewards.", e))?
+		{
+			Some(estimate) => estimate,
+			None => return Ok(None),
+		};
+
+		Ok(Some(UnclaimedRewards {
+			per_block_pay: to_number_or_hex(estimate.per_block_pay)?,
+			per_vote_pay: to_number_or_hex(estimate.per_vote_pay)?,
+			claimable_at: estimate.claimable_at,
+		}))
+	}
+}
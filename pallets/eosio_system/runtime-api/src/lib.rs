@@ -0,0 +1,32 @@
+//! Runtime API definition for the EOSIO System pallet.
+//!
+//! The equivalents of EOSIO's `get_producers` & `get_account` along with the producer schedule
+//! & the unclaimed rewards of a producer.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+pub use pallet_eosio_system::{ProducerDetails, ProducerSchedule, RewardsEstimate, VoterDetails};
+
+sp_api::decl_runtime_apis! {
+	pub trait EosioSystemApi<AccountId, Balance, BlockNumber>
+	where
+		AccountId: Codec,
+		Balance: Codec,
+		BlockNumber: Codec,
+	{
+		/// Up to `limit` producers sorted by their total votes (descending).
+		fn get_producers(limit: u32) -> Vec<ProducerDetails<AccountId, BlockNumber>>;
+
+		/// The stake & votes of the account (if any).
+		fn get_voter(account: AccountId) -> Option<VoterDetails<AccountId, Balance, BlockNumber>>;
+
+		/// The current active & standby producers.
+		fn get_producer_schedule() -> ProducerSchedule<AccountId>;
+
+		/// Estimate of the rewards the producer would get by claiming now (if registered).
+		fn get_unclaimed_rewards(producer: AccountId) -> Option<RewardsEstimate<Balance, BlockNumber>>;
+	}
+}
@@ -5,30 +5,297 @@ use super::*;
 #[allow(unused)]
 use crate::Pallet as EOSIOSystem;
 use frame_benchmarking::v2::*;
-use frame_system::RawOrigin;
+use frame_support::{
+	assert_ok,
+	sp_runtime::{traits::IdentifyAccount, RuntimeAppPublic, Saturating},
+	traits::{Currency, Get},
+};
+use frame_system::{offchain::AppCrypto, RawOrigin};
+
+const SEED: u32 = 0;
+const URL: &[u8] = b"https://eosnation.io";
+const LOCATION: u16 = 124; // Canada
+
+/// Account with enough balance to register as a producer & stake a few times the min. stake.
+fn funded_account<T: Config>(name: &'static str, index: u32) -> T::AccountId {
+	let account: T::AccountId = account(name, index, SEED);
+	let amount = T::ProducerBond::get()
+		.saturating_add(T::MinStakeAmount::get())
+		.saturating_mul(1_000u32.into());
+	T::MyCurrency::make_free_balance_be(&account, amount);
+	account
+}
+
+fn register_producers<T: Config>(name: &'static str, n: u32) -> Vec<T::AccountId> {
+	(0..n)
+		.map(|i| {
+			let producer = funded_account::<T>(name, i);
+			assert_ok!(EOSIOSystem::<T>::register_producer(
+				RawOrigin::Signed(producer.clone()).into(),
+				URL.to_vec(),
+				LOCATION
+			));
+			producer
+		})
+		.collect()
+}
+
+fn stake<T: Config>(voter: &T::AccountId, amount: BalanceOf<T>) {
+	assert_ok!(EOSIOSystem::<T>::stake_to_vote(RawOrigin::Signed(voter.clone()).into(), amount));
+}
+
+fn vote<T: Config>(voter: &T::AccountId, producers: Vec<T::AccountId>) {
+	assert_ok!(EOSIOSystem::<T>::vote_producer(RawOrigin::Signed(voter.clone()).into(), producers));
+}
+
+/// Proxy voting for `v` producers with a delegator.
+fn setup_proxy<T: Config>(v: u32) -> T::AccountId {
+	let proxy = funded_account::<T>("proxy", 0);
+	assert_ok!(EOSIOSystem::<T>::register_proxy(RawOrigin::Signed(proxy.clone()).into()));
+	stake::<T>(&proxy, T::MinStakeAmount::get());
+	vote::<T>(&proxy, register_producers::<T>("producer", v));
+
+	let delegator = funded_account::<T>("delegator", 0);
+	stake::<T>(&delegator, T::MinStakeAmount::get());
+	assert_ok!(EOSIOSystem::<T>::vote_proxy(RawOrigin::Signed(delegator).into(), proxy.clone()));
+	proxy
+}
 
 #[benchmarks]
 mod benchmarks {
 	use super::*;
 
+	// the vote weight of `v` producers is updated
+	#[benchmark]
+	fn stake_to_vote(v: Linear<1, { T::MaxVotesPerAccount::get() }>) {
+		let caller = funded_account::<T>("voter", 0);
+		stake::<T>(&caller, T::MinStakeAmount::get());
+		let producers = register_producers::<T>("producer", v);
+		vote::<T>(&caller, producers.clone());
+
+		#[extrinsic_call]
+		stake_to_vote(RawOrigin::Signed(caller.clone()), T::MinStakeAmount::get());
+
+		let staked = T::MinStakeAmount::get().saturating_mul(2u32.into());
+		assert_eq!(Stakes::<T>::get(&caller).unwrap().staked, staked);
+		assert_eq!(
+			ProducerTable::<T>::get(&producers[0]).unwrap().total_votes,
+			EOSIOSystem::<T>::stake_to_vote_weight(staked)
+		);
+	}
+
+	#[benchmark]
+	fn unstake_to_vote(v: Linear<1, { T::MaxVotesPerAccount::get() }>) {
+		let caller = funded_account::<T>("voter", 0);
+		stake::<T>(&caller, T::MinStakeAmount::get().saturating_mul(2u32.into()));
+		let producers = register_producers::<T>("producer", v);
+		vote::<T>(&caller, producers.clone());
+
+		#[extrinsic_call]
+		unstake_to_vote(RawOrigin::Signed(caller.clone()), T::MinStakeAmount::get());
+
+		assert_eq!(Stakes::<T>::get(&caller).unwrap().staked, T::MinStakeAmount::get());
+		assert_eq!(
+			ProducerTable::<T>::get(&producers[0]).unwrap().total_votes,
+			EOSIOSystem::<T>::stake_to_vote_weight(T::MinStakeAmount::get())
+		);
+	}
+
+	// the previous votes (for `v` producers) are replaced by votes for `v` other producers
+	#[benchmark]
+	fn vote_producer(v: Linear<1, { T::MaxVotesPerAccount::get() }>) {
+		let caller = funded_account::<T>("voter", 0);
+		stake::<T>(&caller, T::MinStakeAmount::get());
+		vote::<T>(&caller, register_producers::<T>("old", v));
+		let producers = register_producers::<T>("producer", v);
+
+		#[extrinsic_call]
+		vote_producer(RawOrigin::Signed(caller.clone()), producers.clone());
+
+		assert_eq!(Voting::<T>::get(&caller).unwrap().producers.into_inner(), producers);
+	}
+
 	#[benchmark]
-	fn do_something() {
-		// let value = 100u32.into();
-		// let caller: T::AccountId = whitelisted_caller();
-		// #[extrinsic_call]
-		// do_something(RawOrigin::Signed(caller), value);
+	fn register_producer() {
+		let caller = funded_account::<T>("producer", 0);
+
+		#[extrinsic_call]
+		register_producer(RawOrigin::Signed(caller.clone()), URL.to_vec(), LOCATION);
+
+		assert_eq!(EOSIOSystem::<T>::active(caller), Some(true));
+	}
+
+	#[benchmark]
+	fn update_producer() {
+		let caller = register_producers::<T>("producer", 1).remove(0);
+		let url = vec![b'a'; T::MaxProducerInfoUrlLen::get() as usize];
+
+		#[extrinsic_call]
+		update_producer(RawOrigin::Signed(caller.clone()), url.clone(), 840);
+
+		assert_eq!(ProducerTable::<T>::get(&caller).unwrap().url.into_inner(), url);
+	}
+
+	#[benchmark]
+	fn unregister_producer() {
+		let caller = register_producers::<T>("producer", 1).remove(0);
+
+		#[extrinsic_call]
+		unregister_producer(RawOrigin::Signed(caller.clone()));
 
-		// assert_eq!(Something::<T>::get(), Some(value));
+		assert_eq!(EOSIOSystem::<T>::active(caller), Some(false));
 	}
 
+	// refund all the (max.) unbonding chunks
 	#[benchmark]
-	fn cause_error() {
-		// Something::<T>::put(100u32);
-		// let caller: T::AccountId = whitelisted_caller();
-		// #[extrinsic_call]
-		// cause_error(RawOrigin::Signed(caller));
+	fn refund() {
+		let caller = funded_account::<T>("voter", 0);
+		let chunks = T::MaxUnbondingChunks::get();
+		stake::<T>(&caller, T::MinStakeAmount::get().saturating_mul(chunks.into()));
+		for i in 1..=chunks {
+			frame_system::Pallet::<T>::set_block_number(i.into());
+			assert_ok!(EOSIOSystem::<T>::unstake_to_vote(
+				RawOrigin::Signed(caller.clone()).into(),
+				T::MinStakeAmount::get()
+			));
+		}
+		frame_system::Pallet::<T>::set_block_number(
+			chunks.saturating_add(T::UnbondingDuration::get()).into(),
+		);
+
+		#[extrinsic_call]
+		refund(RawOrigin::Signed(caller.clone()));
+
+		assert!(Stakes::<T>::get(&caller).is_none());
+	}
+
+	// the delegated vote weight is added back to the votes of `v` producers
+	#[benchmark]
+	fn register_proxy(v: Linear<1, { T::MaxVotesPerAccount::get() }>) {
+		let proxy = setup_proxy::<T>(v);
+		assert_ok!(EOSIOSystem::<T>::unregister_proxy(RawOrigin::Signed(proxy.clone()).into()));
+
+		#[extrinsic_call]
+		register_proxy(RawOrigin::Signed(proxy.clone()));
+
+		assert!(Voting::<T>::get(&proxy).unwrap().is_proxy);
+	}
+
+	// the delegated vote weight is removed from the votes of `v` producers
+	#[benchmark]
+	fn unregister_proxy(v: Linear<1, { T::MaxVotesPerAccount::get() }>) {
+		let proxy = setup_proxy::<T>(v);
+
+		#[extrinsic_call]
+		unregister_proxy(RawOrigin::Signed(proxy.clone()));
+
+		assert!(!Voting::<T>::get(&proxy).unwrap().is_proxy);
+	}
+
+	// the caller's votes for `v` producers are replaced by a proxy voting for `v` producers
+	#[benchmark]
+	fn vote_proxy(v: Linear<1, { T::MaxVotesPerAccount::get() }>) {
+		let proxy = setup_proxy::<T>(v);
+		let caller = funded_account::<T>("voter", 0);
+		stake::<T>(&caller, T::MinStakeAmount::get());
+		vote::<T>(&caller, register_producers::<T>("old", v));
+
+		#[extrinsic_call]
+		vote_proxy(RawOrigin::Signed(caller.clone()), proxy.clone());
+
+		assert_eq!(Voting::<T>::get(&caller).unwrap().delegate_to, Some(proxy));
+	}
+
+	#[benchmark]
+	fn heartbeat() {
+		let key =
+			<T::AuthorityId as AppCrypto<T::Public, T::Signature>>::RuntimeAppPublic::generate_pair(
+				None,
+			);
+		let public: T::Public =
+			<T::AuthorityId as AppCrypto<T::Public, T::Signature>>::GenericPublic::from(
+				key.clone(),
+			)
+			.into();
+		let producer = register_producers::<T>("producer", 1).remove(0);
+		assert_ok!(EOSIOSystem::<T>::set_heartbeat_key(
+			RawOrigin::Signed(producer.clone()).into(),
+			public.clone().into_account()
+		));
+
+		let block_number: T::BlockNumber = 5u32.into();
+		frame_system::Pallet::<T>::set_block_number(block_number);
+		let payload = HeartbeatPayload { block_number, public };
+		let signature: T::Signature =
+			<T::AuthorityId as AppCrypto<T::Public, T::Signature>>::GenericSignature::from(
+				key.sign(&payload.encode()).expect("the key was generated above; qed"),
+			)
+			.into();
+
+		#[extrinsic_call]
+		heartbeat(RawOrigin::None, payload, signature);
+
+		assert_eq!(ProducerTable::<T>::get(&producer).unwrap().last_heartbeat, block_number);
+	}
+
+	#[benchmark]
+	fn claim_rewards() {
+		let producer = register_producers::<T>("producer", 1).remove(0);
+		let voter = funded_account::<T>("voter", 0);
+		stake::<T>(&voter, T::MinStakeAmount::get());
+		vote::<T>(&voter, vec![producer.clone()]);
+		ProducerTable::<T>::mutate(&producer, |info| {
+			if let Some(info) = info {
+				info.unpaid_blocks = 100;
+			}
+		});
+		TotalUnpaidBlocks::<T>::put(100);
+		frame_system::Pallet::<T>::set_block_number(T::ClaimInterval::get().into());
+
+		#[extrinsic_call]
+		claim_rewards(RawOrigin::Signed(producer.clone()));
+
+		assert_eq!(ProducerTable::<T>::get(&producer).unwrap().unpaid_blocks, 0);
+	}
+
+	// rank `p` producers with distinct votes
+	#[benchmark]
+	fn update_elected_producers(p: Linear<1, { T::MaxProducers::get() }>) {
+		for (i, producer) in register_producers::<T>("producer", p).iter().enumerate() {
+			ProducerTable::<T>::mutate(producer, |info| {
+				if let Some(info) = info {
+					info.total_votes = i as u128 + 1;
+				}
+			});
+		}
+
+		#[block]
+		{
+			EOSIOSystem::<T>::update_elected_producers();
+		}
+
+		assert_eq!(
+			ActiveProducers::<T>::get().len() as u32,
+			p.min(T::ActiveValidatorsCount::get())
+		);
+	}
+
+	// the previous key is replaced
+	#[benchmark]
+	fn set_heartbeat_key() {
+		let caller = register_producers::<T>("producer", 1).remove(0);
+		let previous: T::AccountId = account("key", 0, SEED);
+		assert_ok!(EOSIOSystem::<T>::set_heartbeat_key(
+			RawOrigin::Signed(caller.clone()).into(),
+			previous.clone()
+		));
+		let key: T::AccountId = account("key", 1, SEED);
+
+		#[extrinsic_call]
+		set_heartbeat_key(RawOrigin::Signed(caller.clone()), key.clone());
 
-		// assert_eq!(Something::<T>::get(), Some(101u32));
+		assert_eq!(HeartbeatKeys::<T>::get(&key), Some(caller));
+		assert!(HeartbeatKeys::<T>::get(&previous).is_none());
 	}
 
 	impl_benchmark_test_suite!(EOSIOSystem, crate::mock::new_test_ext(), crate::mock::Test);
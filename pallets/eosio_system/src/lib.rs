@@ -20,6 +20,15 @@
 //! ### Staking
 //!
 //! Anyone can stake their tokens (greater than min. staking amount set) to vote for a validator.
+//! The staked tokens are locked in the staker's account.
+//!
+//! Unstaked tokens don't count towards voting anymore, but they remain locked for an unbonding period
+//! (`UnbondingDuration`) after which the staker can `refund` them.
+//!
+//! ### Producer Registration
+//!
+//! Anyone can register as a producer by reserving a registration bond (`ProducerBond`) along with the URL (UTF-8)
+//! of their website & their location (ISO 3166-1 numeric country code). The bond is released on unregistering.
 //!
 //! ### Voting
 //!
@@ -28,7 +37,13 @@
 //! Any staked token holder can delegate their voting power to another account so that they can vote for the
 //! selected validators (30).
 //!
+//! The account voted for has to be registered as a proxy (`register_proxy`). The producer votes of a proxy carry the
+//! vote weight of its own stake plus the stake of all the accounts delegating to it (`vote_proxy`). Any change in a
+//! delegator's stake is propagated to the proxy's producer votes.
+//!
 //! There is also a factor called "vote decay" which is used to reduce the voting power of a token holder over time.
+//! Like EOSIO, the vote weight is `stake * 2^(weeks_since_epoch/52)` i.e. a vote cast now weighs twice the vote
+//! cast a year ago with the same stake. Hence, voters need to re-vote in order to keep their voting power.
 //!
 //! The voters would also get the rewards earned from the treasury pool if they vote for the elected validators.
 //!
@@ -36,74 +51,258 @@
 //!
 //! The top 21 validators with the most votes are elected as validators that can author blocks.
 //! The remaining validators (50) are on standby and can replace a validator if they have more votes.
-//! The validators (active + standby) are renominated every 14_400 blocks (~ ONE_DAY).
+//! The validators (active + standby) are renominated every 14_400 blocks (~ ONE_DAY). Only the registered producers
+//! (up to `MaxProducers`) are ranked.
+//!
+//! The active validators are fed to `pallet_session` via `SessionManager`, which in turn rotates the authorities of
+//! Aura & GRANDPA. So, the producers have to set their session keys (`Session::set_keys`) in order to author blocks;
+//! the active producers without session keys are left out of the session.
 //!
 //! The standby Block Producers (BPs) have to signal that they are alive by sending a heartbeat every few (say 10) blocks.
+//! The heartbeats are sent by the offchain worker as unsigned transactions with a payload signed by the producer's
+//! heartbeat key (`KEY_TYPE`), which has to be inserted into the node's keystore (`author_insertKey`) & set by the
+//! producer (`set_heartbeat_key`). So, the producer's account key never has to be held by the node. The producers
+//! which don't send any heartbeat within `HeartbeatDuration` are deactivated during the next ranking & have to register
+//! again.
+//!
+//! ### Rewards
 //!
-//! In a cycle, The active validators are rewarded with tokens for every block they produce and they are punished for every block they miss.
-//! For standby validators, they are rewarded based on their consistent heartbeat signals and they lose their earned rewards for the times
-//! they missed sending their heartbeat.
+//! Like EOSIO, the author (found via `FindAuthor`) of every block is credited with an unpaid block, if it is an active
+//! producer. The producers' inflation (`ProducersInflationRate` of the total issuance p.a.) is split between a per-block
+//! bucket (`PerBlockRewardShare`) & a per-vote bucket. A producer can `claim_rewards` once per `ClaimInterval` (~ ONE_DAY)
+//! & gets paid its share of the per-block bucket as per its unpaid blocks & its share of the per-vote bucket as per its
+//! total votes (if at least `MinPerVotePay`). The rewards are minted on payout.
 //!
 //! ## Interface
 //!
 //! ### Dispatchable Functions
 //!
 //! - `register_producer`
+//! - `update_producer`
 //! - `unregister_producer`
 //! - `vote_producer`
+//! - `register_proxy`
+//! - `unregister_proxy`
+//! - `vote_proxy`
 //! - `stake_to_vote`
 //! - `unstake_to_vote`
+//! - `refund`
+//! - `heartbeat`
+//! - `set_heartbeat_key`
+//! - `claim_rewards`
 //! - `update_elected_producers`
 //!
+//! ### Runtime API & RPC
+//!
+//! The `EosioSystemApi` runtime API (`pallet-eosio-system-runtime-api`) is exposed by the node via the RPC methods
+//! (`pallet-eosio-system-rpc`):
+//!
+//! - `eosio_getProducers`
+//! - `eosio_getAccount`
+//! - `eosio_getProducerSchedule`
+//! - `eosio_getUnclaimedRewards`
+//!
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
 pub use pallet::*;
 
-// #[cfg(test)]
-// mod mock;
+use codec::{Decode, Encode};
+use frame_support::{
+	inherent::Vec,
+	sp_runtime::RuntimeDebug,
+};
+use frame_system::{
+	offchain::{SignedPayload, SigningTypes},
+	pallet_prelude::BlockNumberFor,
+};
+use sp_core::crypto::KeyTypeId;
+
+/// Key type of the keys producers sign their heartbeats with.
+pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"eosh");
+
+pub mod crypto {
+	use super::KEY_TYPE;
+	use frame_support::sp_runtime::{
+		app_crypto::{app_crypto, sr25519},
+		traits::Verify,
+		MultiSignature, MultiSigner,
+	};
+	use sp_core::sr25519::Signature as Sr25519Signature;
+	app_crypto!(sr25519, KEY_TYPE);
+
+	pub struct HeartbeatAuthId;
 
-// #[cfg(test)]
-// mod tests;
+	// implemented for runtime
+	impl frame_system::offchain::AppCrypto<MultiSigner, MultiSignature> for HeartbeatAuthId {
+		type RuntimeAppPublic = Public;
+		type GenericSignature = sp_core::sr25519::Signature;
+		type GenericPublic = sp_core::sr25519::Public;
+	}
+
+	// implemented for mock runtime in test
+	impl frame_system::offchain::AppCrypto<<Sr25519Signature as Verify>::Signer, Sr25519Signature>
+		for HeartbeatAuthId
+	{
+		type RuntimeAppPublic = Public;
+		type GenericSignature = sp_core::sr25519::Signature;
+		type GenericPublic = sp_core::sr25519::Public;
+	}
+}
 
-// #[cfg(feature = "runtime-benchmarks")]
-// mod benchmarking;
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod migrations;
 pub mod weights;
 pub use weights::*;
 
+/// Vote weight i.e. the staked amount (in the smallest units) scaled by the EOSIO time factor.
+///
+/// Kept as an integer, as the integer part of a `FixedU128` saturates at ~3.4e20 units, which the
+/// time factor (doubling yearly) soon brings within reach of the total stake.
+pub type VoteWeight = u128;
+
+/// `2^(k/52)` for `k` in `[0, 52)` as `FixedU128` inner values (scaled by 1e18).
+///
+/// Used for the fractional part of the EOSIO vote weight i.e. `2^(weeks_since_epoch/52)`, as the
+/// runtime can't use floating point `pow`.
+const VOTE_WEIGHT_FRACTIONS: [u128; 52] = [
+	1_000_000_000_000_000_000,
+	1_013_418_990_698_700_315,
+	1_027_018_050_708_772_437,
+	1_040_799_596_378_630_784,
+	1_054_766_076_481_646_673,
+	1_068_919_972_651_258_516,
+	1_083_263_799_821_920_750,
+	1_097_800_106_675_969_866,
+	1_112_531_476_096_486_920,
+	1_127_460_525_626_237_011,
+	1_142_589_907_932_767_254,
+	1_157_922_311_279_745_908,
+	1_173_460_460_004_626_391,
+	1_189_207_115_002_721_066,
+	1_205_165_074_217_770_817,
+	1_221_337_173_139_097_560,
+	1_237_726_285_305_428_047,
+	1_254_335_322_815_478_480,
+	1_271_167_236_845_390_644,
+	1_288_225_018_173_111_522,
+	1_305_511_697_709_809_551,
+	1_323_030_347_038_421_944,
+	1_340_784_078_959_428_779,
+	1_358_776_048_043_950_824,
+	1_377_009_451_194_269_374,
+	1_395_487_528_211_867_701,
+	1_414_213_562_373_095_048,
+	1_433_190_881_012_555_450,
+	1_452_422_856_114_325_043,
+	1_471_912_904_911_102_918,
+	1_491_664_490_491_401_970,
+	1_511_681_122_414_887_639,
+	1_531_966_357_335_973_870,
+	1_552_523_799_635_787_108,
+	1_573_357_102_062_610_608,
+	1_594_469_966_380_922_863,
+	1_615_866_144_029_145_473,
+	1_637_549_436_786_217_321,
+	1_659_523_697_447_113_512,
+	1_681_792_830_507_429_086,
+	1_704_360_792_857_149_153,
+	1_727_231_594_483_728_734,
+	1_750_409_299_184_607_206,
+	1_773_898_025_289_283_988,
+	1_797_701_946_391_083_748,
+	1_821_825_292_088_741_154,
+	1_846_272_348_737_936_958,
+	1_871_047_460_212_918_920,
+	1_896_155_028_678_342_929,
+	1_921_599_515_371_471_443,
+	1_947_385_441_394_868_253,
+	1_973_517_388_519_730_400,
+];
+
+/// ISO 3166-1 numeric country codes (sorted), used to validate a producer's location.
+const ISO_3166_NUMERIC_CODES: [u16; 249] = [
+	4, 8, 10, 12, 16, 20, 24, 28, 31, 32, 36, 40, 44, 48, 50, 51, 52, 56, 60, 64, 68, 70, 72, 74,
+	76, 84, 86, 90, 92, 96, 100, 104, 108, 112, 116, 120, 124, 132, 136, 140, 144, 148, 152, 156,
+	158, 162, 166, 170, 174, 175, 178, 180, 184, 188, 191, 192, 196, 203, 204, 208, 212, 214, 218,
+	222, 226, 231, 232, 233, 234, 238, 239, 242, 246, 248, 250, 254, 258, 260, 262, 266, 268, 270,
+	275, 276, 288, 292, 296, 300, 304, 308, 312, 316, 320, 324, 328, 332, 334, 336, 340, 344, 348,
+	352, 356, 360, 364, 368, 372, 376, 380, 384, 388, 392, 398, 400, 404, 408, 410, 414, 417, 418,
+	422, 426, 428, 430, 434, 438, 440, 442, 446, 450, 454, 458, 462, 466, 470, 474, 478, 480, 484,
+	492, 496, 498, 499, 500, 504, 508, 512, 516, 520, 524, 528, 531, 533, 534, 535, 540, 548, 554,
+	558, 562, 566, 570, 574, 578, 580, 581, 583, 584, 585, 586, 591, 598, 600, 604, 608, 612, 616,
+	620, 624, 626, 630, 634, 638, 642, 643, 646, 652, 654, 659, 660, 662, 663, 666, 670, 674, 678,
+	682, 686, 688, 690, 694, 702, 703, 704, 705, 706, 710, 716, 724, 728, 729, 732, 740, 744, 748,
+	752, 756, 760, 762, 764, 768, 772, 776, 780, 784, 788, 792, 795, 796, 798, 800, 804, 807, 818,
+	826, 831, 832, 833, 834, 840, 850, 854, 858, 860, 862, 876, 882, 887, 894,
+];
+
 #[frame_support::pallet]
 pub mod pallet {
 
 	use super::*;
 	use frame_support::{
 		inherent::Vec,
+		log,
 		pallet_prelude::*,
-		sp_runtime::{traits::Zero, BoundedVec, FixedU128},
-		traits::{Currency, Get, LockIdentifier, LockableCurrency},
+		sp_runtime::{
+			traits::{
+				CheckedAdd, CheckedSub, IdentifyAccount, SaturatedConversion, Saturating, Zero,
+			},
+			BoundedVec, DispatchError, FixedPointNumber, FixedU128, Perbill, RuntimeAppPublic,
+		},
+		traits::{
+			Contains, Currency, FindAuthor, Get, LockIdentifier, LockableCurrency,
+			ReservableCurrency, WithdrawReasons,
+		},
 		Blake2_128Concat,
 	};
-	use frame_system::pallet_prelude::*;
+	use frame_system::{
+		offchain::{AppCrypto, CreateSignedTransaction, SendUnsignedTransaction, Signer},
+		pallet_prelude::*,
+	};
 
 	const ID1: LockIdentifier = *b"EOSIO___";
 
 	type BalanceOf<T: Config> = <<T as Config>::MyCurrency as Currency<T::AccountId>>::Balance;
 
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
 	#[pallet::config]
-	pub trait Config: frame_system::Config + TypeInfo {
+	pub trait Config:
+		frame_system::Config + CreateSignedTransaction<Call<Self>> + TypeInfo
+	{
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 
+		/// The identifier type for the producers' heartbeat keys.
+		type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
+
+		/// The priority of the heartbeat (unsigned) transactions.
+		#[pallet::constant]
+		type UnsignedPriority: Get<TransactionPriority>;
+
 		/// Type representing the weight of this pallet
 		type WeightInfo: WeightInfo;
 
 		/// MyCurrency type for this pallet. Here, we could have used `Currency` trait.
 		/// But, we need to use `set_lock` function which is not available in `Currency` trait.
 		/// That's why `LockableCurrency` trait is used which itself inherits `Currency` trait.
-		type MyCurrency: LockableCurrency<Self::AccountId>;
+		/// And `ReservableCurrency` trait is used for reserving the producer's registration bond.
+		type MyCurrency: LockableCurrency<Self::AccountId> + ReservableCurrency<Self::AccountId>;
 
 		/// The minimum amount of tokens that can be staked.
 		#[pallet::constant]
@@ -116,33 +315,161 @@ pub mod pallet {
 		type MaxVotesPerAccount: Get<u32>;
 
 		/// No. of validators that can author blocks i.e. Active Validators
+		/// NOTE: Here, `u8` was supposed to be used but it is used as the bound of `BoundedVec`
+		/// which requires `u32`.
 		#[pallet::constant]
-		type ActiveValidatorsCount: Get<u8>;
+		type ActiveValidatorsCount: Get<u32>;
 
 		/// No. of validators that are on standby i.e. Standby Validators
+		/// NOTE: Here, `u16` was supposed to be used but it is used as the bound of `BoundedVec`
+		/// which requires `u32`.
+		#[pallet::constant]
+		type StandbyValidatorsCount: Get<u32>;
+
+		/// Max. no. of registered producers at a time, which bounds the ranking.
 		#[pallet::constant]
-		type StandbyValidatorsCount: Get<u16>;
+		type MaxProducers: Get<u32>;
 
 		/// Every no. of blocks, the validators are ranked via latest ranking.
 		#[pallet::constant]
 		type RankingDuration: Get<u32>;
 
 		/// Heartbeat duration in blocks
+		///
+		/// A producer not sending any heartbeat within this duration gets deactivated during the
+		/// next ranking.
 		#[pallet::constant]
 		type HeartbeatDuration: Get<u32>;
 
+		/// Max. length of the producer's URL in bytes (UTF-8 encoded).
 		#[pallet::constant]
 		type MaxProducerInfoUrlLen: Get<u32>;
+
+		/// The amount of tokens reserved from a producer while it is registered.
+		#[pallet::constant]
+		type ProducerBond: Get<BalanceOf<Self>>;
+
+		/// No. of blocks the unstaked tokens remain locked before they can be refunded.
+		#[pallet::constant]
+		type UnbondingDuration: Get<u32>;
+
+		/// Max. no. of unbonding chunks an account can have at a time.
+		#[pallet::constant]
+		type MaxUnbondingChunks: Get<u32>;
+
+		/// No. of blocks in a week. Used to compute the time-based vote weight.
+		#[pallet::constant]
+		type BlocksPerWeek: Get<u32>;
+
+		/// Find the author of the current block, which is credited with an unpaid block.
+		type FindAuthor: FindAuthor<Self::AccountId>;
+
+		/// Whether a producer has set its session keys (`Session::set_keys`). Only such producers
+		/// are handed to `pallet_session`, as the others couldn't author any block.
+		type HasSessionKeys: Contains<Self::AccountId>;
+
+		/// No. of blocks in a year. Used to compute the inflation since the last claim.
+		#[pallet::constant]
+		type BlocksPerYear: Get<u32>;
+
+		/// Annual inflation (of the total issuance) paid to the producers.
+		///
+		/// NOTE: EOSIO inflates 5% p.a., of which 1/5 goes to the producers i.e. 1%.
+		#[pallet::constant]
+		type ProducersInflationRate: Get<Perbill>;
+
+		/// Share of the producers' inflation paid per block produced. The rest is paid per vote.
+		///
+		/// NOTE: 1/4 in EOSIO.
+		#[pallet::constant]
+		type PerBlockRewardShare: Get<Perbill>;
+
+		/// Min. no. of blocks between two claims of a producer (~ ONE_DAY).
+		#[pallet::constant]
+		type ClaimInterval: Get<u32>;
+
+		/// Min. per-vote pay of a claim. Any lower per-vote pay is forfeited.
+		#[pallet::constant]
+		type MinPerVotePay: Get<BalanceOf<Self>>;
 	}
 
-	#[derive(
-		Decode, Encode, TypeInfo, Clone, PartialEq, Eq, Default, RuntimeDebug, MaxEncodedLen,
-	)]
+	// --- Staking ---
+	/// A chunk of unstaked tokens waiting for its unbonding period to end.
+	#[derive(Decode, Encode, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug, MaxEncodedLen)]
+	pub struct UnbondingChunk<Balance, BlockNumber> {
+		/// Amount of tokens being unbonded.
+		pub amount: Balance,
+		/// Block number from which the amount can be refunded.
+		pub unlock_at: BlockNumber,
+	}
+
+	/// Stake of an account
+	#[derive(Decode, Encode, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug, MaxEncodedLen)]
+	// #[scale_info(skip_type_params(T))]
+	pub struct StakeInfo<T: Config> {
+		/// Amount of tokens staked to vote.
+		pub staked: BalanceOf<T>,
+		/// Unstaked tokens which are still locked.
+		pub unbonding:
+			BoundedVec<UnbondingChunk<BalanceOf<T>, T::BlockNumber>, T::MaxUnbondingChunks>,
+	}
+
+	impl<T: Config> Default for StakeInfo<T> {
+		fn default() -> Self {
+			Self { staked: Zero::zero(), unbonding: BoundedVec::default() }
+		}
+	}
+
+	impl<T: Config> StakeInfo<T> {
+		/// Total amount locked i.e. staked + unbonding.
+		pub fn total(&self) -> BalanceOf<T> {
+			self.unbonding
+				.iter()
+				.fold(self.staked, |acc, chunk| acc.saturating_add(chunk.amount))
+		}
+	}
+
+	/// Stake of an account
+	#[pallet::storage]
+	#[pallet::getter(fn stakes)]
+	pub type Stakes<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, StakeInfo<T>>;
+
+	#[derive(Decode, Encode, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug, MaxEncodedLen)]
 	// #[scale_info(skip_type_params(T))]
 	pub struct VoterInfo<T: Config> {
-		delegate_to: T::AccountId,
-		cycle_no: u32,
-		votes: BoundedVec<u8, T::MaxVotesPerAccount>,
+		/// The proxy the voter delegated their vote to (if any).
+		pub delegate_to: Option<T::AccountId>,
+		/// The producers voted for.
+		pub producers: BoundedVec<T::AccountId, T::MaxVotesPerAccount>,
+		/// The vote weight added to each of the voted producers (or to the proxy) during the last
+		/// vote.
+		pub last_vote_weight: VoteWeight,
+		/// Whether the account is registered as a proxy.
+		pub is_proxy: bool,
+		/// The sum of the vote weights delegated to this account as a proxy.
+		pub proxied_vote_weight: VoteWeight,
+	}
+
+	impl<T: Config> Default for VoterInfo<T> {
+		fn default() -> Self {
+			Self {
+				delegate_to: None,
+				producers: BoundedVec::default(),
+				last_vote_weight: Zero::zero(),
+				is_proxy: false,
+				proxied_vote_weight: Zero::zero(),
+			}
+		}
+	}
+
+	impl<T: Config> VoterInfo<T> {
+		/// Whether the voter info doesn't hold anything & hence, can be removed.
+		fn is_empty(&self) -> bool {
+			self.delegate_to.is_none()
+				&& self.producers.is_empty()
+				&& !self.is_proxy
+				&& self.proxied_vote_weight.is_zero()
+		}
 	}
 
 	/// Voting status of an account
@@ -157,14 +484,16 @@ pub mod pallet {
 	)]
 	// #[scale_info(skip_type_params(T))]
 	pub struct ProducerInfo<T: Config> {
-		total_votes: FixedU128,
+		pub total_votes: VoteWeight,
 		// eosio::public_key producer_key; // a packed public key object
-		is_active: bool,
-		url: BoundedVec<u16, T::MaxProducerInfoUrlLen>,
-		unpaid_blocks: u32,
-		last_claim_time: T::BlockNumber,
-		location: u16,
-		last_heartbeat: T::BlockNumber,
+		pub is_active: bool,
+		/// UTF-8 encoded URL of the producer's website.
+		pub url: BoundedVec<u8, T::MaxProducerInfoUrlLen>,
+		pub unpaid_blocks: u32,
+		pub last_claim_time: T::BlockNumber,
+		/// ISO 3166-1 numeric country code of the producer's location.
+		pub location: u16,
+		pub last_heartbeat: T::BlockNumber,
 		// eosio::binary_extension<eosio::block_signing_authority>  producer_authority; // added in version 1.9.0
 	}
 
@@ -173,13 +502,135 @@ pub mod pallet {
 	pub type ProducerTable<T: Config> =
 		StorageMap<_, Blake2_128Concat, T::AccountId, ProducerInfo<T>>;
 
+	/// Bond reserved from a registered producer, released as is on unregistering.
+	#[pallet::storage]
+	#[pallet::getter(fn producer_bond)]
+	pub type ProducerBonds<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+	/// Registered (active) producers i.e. the candidates of the ranking.
+	#[pallet::storage]
+	#[pallet::getter(fn candidates)]
+	pub type Candidates<T: Config> =
+		StorageValue<_, BoundedVec<T::AccountId, T::MaxProducers>, ValueQuery>;
+
+	/// Producers authoring blocks, ranked by their total votes.
+	#[pallet::storage]
+	#[pallet::getter(fn active_producers)]
+	pub type ActiveProducers<T: Config> =
+		StorageValue<_, BoundedVec<T::AccountId, T::ActiveValidatorsCount>, ValueQuery>;
+
+	/// Producers on standby, ranked by their total votes.
+	#[pallet::storage]
+	#[pallet::getter(fn standby_producers)]
+	pub type StandbyProducers<T: Config> =
+		StorageValue<_, BoundedVec<T::AccountId, T::StandbyValidatorsCount>, ValueQuery>;
+
+	/// Producer of a heartbeat key (as an account).
+	#[pallet::storage]
+	#[pallet::getter(fn heartbeat_keys)]
+	pub type HeartbeatKeys<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, T::AccountId>;
+
+	/// Heartbeat key (as an account) of a producer.
+	#[pallet::storage]
+	#[pallet::getter(fn heartbeat_key_of)]
+	pub type HeartbeatKeyOf<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, T::AccountId>;
+
+	/// Version of the producer schedule, incremented whenever the active producers change.
+	#[pallet::storage]
+	#[pallet::getter(fn schedule_version)]
+	pub type ScheduleVersion<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	// --- Rewards ---
+	/// Inflation yet to be paid out to the producers as per the blocks produced.
+	#[pallet::storage]
+	#[pallet::getter(fn per_block_bucket)]
+	pub type PerBlockBucket<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+	/// Inflation yet to be paid out to the producers as per the votes received.
+	#[pallet::storage]
+	#[pallet::getter(fn per_vote_bucket)]
+	pub type PerVoteBucket<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+	/// Block at which the inflation was last added to the buckets.
+	#[pallet::storage]
+	#[pallet::getter(fn last_bucket_fill)]
+	pub type LastBucketFill<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+
+	/// Sum of the unpaid blocks of all the producers.
+	#[pallet::storage]
+	#[pallet::getter(fn total_unpaid_blocks)]
+	pub type TotalUnpaidBlocks<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// Sum of the total votes of all the producers.
+	#[pallet::storage]
+	#[pallet::getter(fn total_producer_vote_weight)]
+	pub type TotalProducerVoteWeight<T: Config> = StorageValue<_, VoteWeight, ValueQuery>;
+
 	// === Events ===
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
-		StakedToVote { voter: T::AccountId, amount: BalanceOf<T> },
-		UnstakedToVote { voter: T::AccountId, amount: BalanceOf<T> },
-		Voted { voter: T::AccountId, producers: BoundedVec<T::AccountId, T::MaxVotesPerAccount> },
+		StakedToVote {
+			voter: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		UnstakedToVote {
+			voter: T::AccountId,
+			amount: BalanceOf<T>,
+			unlock_at: T::BlockNumber,
+		},
+		Refunded {
+			voter: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		Voted {
+			voter: T::AccountId,
+			producers: BoundedVec<T::AccountId, T::MaxVotesPerAccount>,
+		},
+		VotedProxy {
+			voter: T::AccountId,
+			proxy: T::AccountId,
+		},
+		ProxyRegistered {
+			proxy: T::AccountId,
+		},
+		ProxyUnregistered {
+			proxy: T::AccountId,
+		},
+		ProducerRegistered {
+			producer: T::AccountId,
+			bond: BalanceOf<T>,
+		},
+		ProducerUpdated {
+			producer: T::AccountId,
+		},
+		ProducerUnregistered {
+			producer: T::AccountId,
+		},
+		HeartbeatReceived {
+			producer: T::AccountId,
+			block_number: T::BlockNumber,
+		},
+		HeartbeatKeySet {
+			producer: T::AccountId,
+			key: T::AccountId,
+		},
+		ProducerDeactivated {
+			producer: T::AccountId,
+			last_heartbeat: T::BlockNumber,
+		},
+		NewProducerSchedule {
+			version: u32,
+			active: BoundedVec<T::AccountId, T::ActiveValidatorsCount>,
+			standby: BoundedVec<T::AccountId, T::StandbyValidatorsCount>,
+		},
+		RewardsClaimed {
+			producer: T::AccountId,
+			per_block_pay: BalanceOf<T>,
+			per_vote_pay: BalanceOf<T>,
+		},
 	}
 
 	// Errors inform users that something went wrong.
@@ -195,127 +646,1043 @@ pub mod pallet {
 		NonExistentProducer,
 		/// Producer Already Registered.
 		ProducerAlreadyRegistered,
+		/// Stake Amount Below Minimum.
+		StakeAmountBelowMinimum,
+		/// Insufficient Free Balance.
+		InsufficientFreeBalance,
+		/// Nothing Staked.
+		NothingStaked,
+		/// Insufficient Staked Amount.
+		InsufficientStakedAmount,
+		/// Too Many Unbonding Chunks.
+		TooManyUnbondingChunks,
+		/// Too Many Votes.
+		TooManyVotes,
+		/// Duplicate Producer.
+		DuplicateProducer,
+		/// Producer Not Active.
+		ProducerNotActive,
+		/// Already A Proxy.
+		AlreadyProxy,
+		/// Not A Proxy.
+		NotAProxy,
+		/// Proxy Cannot Delegate.
+		ProxyCannotDelegate,
+		/// Cannot Proxy To Self.
+		CannotProxyToSelf,
+		/// Invalid Location Code.
+		InvalidLocationCode,
+		/// No Matured Unbonding.
+		NoMaturedUnbonding,
+		/// Arithmetic Overflow.
+		ArithmeticOverflow,
+		/// Already Claimed Within Claim Interval.
+		ClaimTooEarly,
+		/// Too Many Registered Producers.
+		TooManyProducers,
+		/// Unknown Heartbeat Key.
+		UnknownHeartbeatKey,
+		/// Heartbeat Key In Use By Another Producer.
+		HeartbeatKeyInUse,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// Rank the producers every `RankingDuration` blocks.
+		fn on_initialize(n: T::BlockNumber) -> Weight {
+			let mut weight = Self::note_author();
+
+			if (n % T::RankingDuration::get().max(1).into()).is_zero() {
+				weight = weight.saturating_add(Self::update_elected_producers());
+			}
+
+			weight
+		}
+
+		/// Send a heartbeat on behalf of the local producer keys (if any).
+		fn offchain_worker(block_number: T::BlockNumber) {
+			if let Err(e) = Self::send_heartbeats(block_number) {
+				log::error!(target: "pallet_eosio_system", "Error: {}", e);
+			}
+		}
+	}
+
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+
+		/// Validate the heartbeats sent by the producers' offchain workers.
+		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			if let Call::heartbeat { heartbeat: ref payload, ref signature } = call {
+				let signature_valid =
+					SignedPayload::<T>::verify::<T::AuthorityId>(payload, signature.clone());
+				if !signature_valid {
+					return InvalidTransaction::BadProof.into();
+				}
+
+				// only the heartbeat keys of the registered (active) producers are accepted
+				let key = payload.public.clone().into_account();
+				let producer = match HeartbeatKeys::<T>::get(key) {
+					Some(producer) => producer,
+					None => return InvalidTransaction::BadSigner.into(),
+				};
+				let producer_info = match ProducerTable::<T>::get(&producer) {
+					Some(producer_info) if producer_info.is_active => producer_info,
+					_ => return InvalidTransaction::BadSigner.into(),
+				};
+
+				if payload.block_number > <frame_system::Pallet<T>>::block_number() {
+					return InvalidTransaction::Future.into();
+				}
+
+				if payload.block_number <= producer_info.last_heartbeat {
+					return InvalidTransaction::Stale.into();
+				}
+
+				ValidTransaction::with_tag_prefix("pallet-eosio-system")
+					.priority(T::UnsignedPriority::get())
+					// one heartbeat per producer & block in the pool
+					.and_provides((producer, payload.block_number))
+					.longevity(T::HeartbeatDuration::get() as u64)
+					.propagate(true)
+					.build()
+			} else {
+				InvalidTransaction::Call.into()
+			}
+		}
 	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Stake amount of tokens
+		///
+		/// The staked tokens are locked (not reserved) under the `EOSIO___` lock id & add up to
+		/// the caller's existing stake, which must be at least `MinStakeAmount` in total.
 		#[pallet::call_index(0)]
-		#[pallet::weight(T::WeightInfo::dummy())]
-		pub fn stake_to_vote(
-			origin: OriginFor<T>,
-			something: u32,
-			amount: BalanceOf<T>,
-		) -> DispatchResult {
-			/* 			// Check that the extrinsic was signed and get the signer.
-					   let voter = ensure_signed(origin)?;
+		#[pallet::weight(T::WeightInfo::stake_to_vote(T::MaxVotesPerAccount::get()))]
+		pub fn stake_to_vote(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResult {
+			// Check that the extrinsic was signed and get the signer.
+			let voter = ensure_signed(origin)?;
+
+			// ensure the amount is not zero
+			ensure!(!amount.is_zero(), Error::<T>::ZeroStakeAmount);
+
+			let mut stake_info = Stakes::<T>::get(&voter).unwrap_or_default();
 
-					   // Update storage.
-					   <Something<T>>::put(something);
+			// add the amount to the existing stake
+			stake_info.staked =
+				stake_info.staked.checked_add(&amount).ok_or(Error::<T>::ArithmeticOverflow)?;
+
+			// ensure the total stake is at least the min. stake amount
+			ensure!(
+				stake_info.staked >= T::MinStakeAmount::get(),
+				Error::<T>::StakeAmountBelowMinimum
+			);
+
+			// the lock covers the staked amount as well as the amount still unbonding.
+			let total_locked = stake_info.total();
+
+			// NOTE: `set_lock` doesn't check the balance, so ensure the caller can afford the lock.
+			ensure!(
+				T::MyCurrency::free_balance(&voter) >= total_locked,
+				Error::<T>::InsufficientFreeBalance
+			);
+
+			// lock the total amount
+			T::MyCurrency::set_lock(ID1, &voter, total_locked, WithdrawReasons::all());
+
+			// Update storage.
+			Stakes::<T>::insert(&voter, stake_info);
+
+			// the vote weight of the voter has changed.
+			Self::update_voting_power(&voter);
+
+			// Emit an event.
+			Self::deposit_event(Event::StakedToVote { voter, amount });
 
-					   // Emit an event.
-					   Self::deposit_event(Event::SomethingStored { something, who });
-					   // Return a successful DispatchResultWithPostInfo
-			*/
 			Ok(())
 		}
 
 		/// Unstake amount of tokens
+		///
+		/// The unstaked tokens stop counting as stake right away, but they remain locked for
+		/// `UnbondingDuration` blocks. After that, they can be released via `refund`.
 		#[pallet::call_index(1)]
-		#[pallet::weight(T::WeightInfo::dummy())]
+		#[pallet::weight(T::WeightInfo::unstake_to_vote(T::MaxVotesPerAccount::get()))]
 		pub fn unstake_to_vote(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResult {
-			/* 			let _who = ensure_signed(origin)?;
-
-					   // Read a value from storage.
-					   match <Something<T>>::get() {
-						   // Return an error if the value has not been set.
-						   None => return Err(Error::<T>::NoneValue.into()),
-						   Some(old) => {
-							   // Increment the value read from storage; will error in the event of overflow.
-							   let new = old.checked_add(1).ok_or(Error::<T>::StorageOverflow)?;
-							   // Update the value in storage with the incremented result.
-							   <Something<T>>::put(new);
-							   Ok(())
-						   },
-					   }
-			*/
+			let voter = ensure_signed(origin)?;
+
+			// ensure the amount is not zero
+			ensure!(!amount.is_zero(), Error::<T>::ZeroUnstakeAmount);
+
+			let mut stake_info = Stakes::<T>::get(&voter).ok_or(Error::<T>::NothingStaked)?;
+
+			// ensure the caller has staked enough
+			stake_info.staked = stake_info
+				.staked
+				.checked_sub(&amount)
+				.ok_or(Error::<T>::InsufficientStakedAmount)?;
+
+			// ensure the remaining stake is either fully withdrawn or at least the min. stake amount
+			ensure!(
+				stake_info.staked.is_zero() || stake_info.staked >= T::MinStakeAmount::get(),
+				Error::<T>::StakeAmountBelowMinimum
+			);
+
+			let unlock_at = <frame_system::Pallet<T>>::block_number()
+				.saturating_add(T::UnbondingDuration::get().into());
+
+			// merge with the chunk unlocking at the same block (if any), else add a new chunk.
+			if let Some(chunk) = stake_info.unbonding.iter_mut().find(|c| c.unlock_at == unlock_at)
+			{
+				chunk.amount = chunk.amount.saturating_add(amount);
+			} else {
+				stake_info
+					.unbonding
+					.try_push(UnbondingChunk { amount, unlock_at })
+					.map_err(|_| Error::<T>::TooManyUnbondingChunks)?;
+			}
+
+			// NOTE: the lock remains the same as the unstaked amount is now unbonding.
+			Stakes::<T>::insert(&voter, stake_info);
+
+			// the vote weight of the voter has changed.
+			Self::update_voting_power(&voter);
+
+			// Emit an event.
+			Self::deposit_event(Event::UnstakedToVote { voter, amount, unlock_at });
 
 			Ok(())
 		}
 
+		/// Vote for up to `MaxVotesPerAccount` registered producers.
+		///
+		/// Each voted producer receives the full vote weight of the caller's stake. Re-voting
+		/// replaces the previous votes & an empty list removes them.
+		///
+		/// Weighs the removal of `MaxVotesPerAccount` previous votes (directly or via the proxy
+		/// voted for) plus as many new votes, refunded as per the actual votes.
 		#[pallet::call_index(2)]
-		#[pallet::weight(T::WeightInfo::dummy())]
-		pub fn vote_producer(origin: OriginFor<T>) -> DispatchResult {
+		#[pallet::weight(T::WeightInfo::vote_producer(T::MaxVotesPerAccount::get()))]
+		pub fn vote_producer(
+			origin: OriginFor<T>,
+			producers: Vec<T::AccountId>,
+		) -> DispatchResultWithPostInfo {
 			let voter = ensure_signed(origin)?;
 
-			Ok(())
+			let producers: BoundedVec<T::AccountId, T::MaxVotesPerAccount> =
+				producers.try_into().map_err(|_| Error::<T>::TooManyVotes)?;
+
+			// ensure no producer is voted twice
+			let mut unique = producers.clone().into_inner();
+			unique.sort();
+			unique.dedup();
+			ensure!(unique.len() == producers.len(), Error::<T>::DuplicateProducer);
+
+			if !producers.is_empty() {
+				// ensure the voter has some stake
+				let stake_info = Stakes::<T>::get(&voter).ok_or(Error::<T>::NothingStaked)?;
+				ensure!(!stake_info.staked.is_zero(), Error::<T>::NothingStaked);
+			}
+
+			// ensure all the producers are registered & active
+			for producer in producers.iter() {
+				let producer_info =
+					ProducerTable::<T>::get(producer).ok_or(Error::<T>::NonExistentProducer)?;
+				ensure!(producer_info.is_active, Error::<T>::ProducerNotActive);
+			}
+
+			// the previous votes are removed from the producers directly or via the proxy
+			let previous =
+				Voting::<T>::get(&voter).map_or(0, |voter_info| match voter_info.delegate_to {
+					Some(proxy) => Voting::<T>::get(proxy).map_or(0, |p| p.producers.len()),
+					None => voter_info.producers.len(),
+				});
+			let votes = previous.max(producers.len()) as u32;
+
+			Self::update_votes(&voter, None, producers.clone());
+
+			// Emit an event.
+			Self::deposit_event(Event::Voted { voter, producers });
+
+			Ok(Some(T::WeightInfo::vote_producer(votes)).into())
 		}
 
+		/// Register the caller as a producer.
+		///
+		/// Reserves `ProducerBond` from the caller until it unregisters. A previously unregistered
+		/// producer can register again, in which case it keeps the votes it received.
 		#[pallet::call_index(3)]
-		#[pallet::weight(T::WeightInfo::dummy())]
+		#[pallet::weight(T::WeightInfo::register_producer())]
 		pub fn register_producer(
 			origin: OriginFor<T>,
-			url: Vec<u16>,
+			url: Vec<u8>,
 			location: u16,
 		) -> DispatchResult {
 			let producer = ensure_signed(origin)?;
 
-			// Ensure the URL is no longer than 512 bytes. 2*256 = 512
-			// TEST: get a benchmarking for long url
-			ensure!(
-				T::MaxProducerInfoUrlLen::get() > url.len() as u32,
-				Error::<T>::InvalidProducerInfoUrl
-			);
-
-			// Ensure the location code is valid.
-			ensure!(location > 0, "invalid location code");
+			let url = Self::validate_producer_info(url, location)?;
 
-			// check for producer existence
-			let producer_info =
-				ProducerTable::<T>::get(producer.clone()).ok_or(Error::<T>::NonExistentProducer)?;
+			// the registration counts as the first heartbeat
+			let last_heartbeat = <frame_system::Pallet<T>>::block_number();
 
-			// check for producer status
-			ensure!(!producer_info.is_active, Error::<T>::ProducerAlreadyRegistered);
-
-			// create a struct for producer info
-			let producer_info = ProducerInfo::<T> {
-				total_votes: FixedU128::from(0),
-				is_active: true,
-				url: BoundedVec::try_from(url)
-					.map_err(|_| Error::<T>::InvalidProducerInfoUrl)
-					.unwrap(),
-				unpaid_blocks: 0,
-				last_claim_time: Zero::zero(),
-				location,
-				last_heartbeat: Zero::zero(),
+			let producer_info = match ProducerTable::<T>::get(&producer) {
+				// check for producer status
+				Some(producer_info) => {
+					ensure!(!producer_info.is_active, Error::<T>::ProducerAlreadyRegistered);
+					ProducerInfo::<T> {
+						is_active: true,
+						url,
+						location,
+						last_heartbeat,
+						..producer_info
+					}
+				},
+				// create a struct for producer info
+				None => ProducerInfo::<T> {
+					total_votes: 0,
+					is_active: true,
+					url,
+					unpaid_blocks: 0,
+					last_claim_time: Zero::zero(),
+					location,
+					last_heartbeat,
+				},
 			};
 
+			Candidates::<T>::try_append(&producer).map_err(|_| Error::<T>::TooManyProducers)?;
+
+			// NOTE: inherently checked for sufficient free balance
+			let bond = T::ProducerBond::get();
+			T::MyCurrency::reserve(&producer, bond)?;
+			ProducerBonds::<T>::insert(&producer, bond);
+
 			// add the producer info
-			ProducerTable::<T>::insert(producer, producer_info);
+			ProducerTable::<T>::insert(&producer, producer_info);
+
+			// Emit an event.
+			Self::deposit_event(Event::ProducerRegistered { producer, bond });
 
 			Ok(())
 		}
 
+		/// Unregister the caller as a producer & release its bond.
+		///
+		/// The producer info (incl. votes) is kept, but the producer can't be voted for or elected
+		/// until it registers again.
 		#[pallet::call_index(4)]
-		#[pallet::weight(T::WeightInfo::dummy())]
+		#[pallet::weight(T::WeightInfo::unregister_producer())]
 		pub fn unregister_producer(origin: OriginFor<T>) -> DispatchResult {
-			/* 			// Check that the extrinsic was signed and get the signer.
-					   // This function will return an error if the extrinsic is not signed.
-					   // https://docs.substrate.io/main-docs/build/origins/
-					   let producer = ensure_signed(origin)?;
-
-					   // Update storage.
-					   <Something<T>>::put(something);
-
-					   // Emit an event.
-					   Self::deposit_event(Event::SomethingStored { something, who });
-					   // Return a successful DispatchResultWithPostInfo
-			*/
+			let producer = ensure_signed(origin)?;
+
+			let mut producer_info =
+				ProducerTable::<T>::get(&producer).ok_or(Error::<T>::NonExistentProducer)?;
+
+			// check for producer status
+			ensure!(producer_info.is_active, Error::<T>::ProducerNotActive);
+
+			producer_info.is_active = false;
+			ProducerTable::<T>::insert(&producer, producer_info);
+			Candidates::<T>::mutate(|candidates| candidates.retain(|c| c != &producer));
+
+			// release the bond
+			T::MyCurrency::unreserve(&producer, ProducerBonds::<T>::take(&producer));
+
+			// Emit an event.
+			Self::deposit_event(Event::ProducerUnregistered { producer });
+
+			Ok(())
+		}
+
+		/// Release the unstaked tokens whose unbonding period is over.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::refund())]
+		pub fn refund(origin: OriginFor<T>) -> DispatchResult {
+			let voter = ensure_signed(origin)?;
+
+			let mut stake_info = Stakes::<T>::get(&voter).ok_or(Error::<T>::NothingStaked)?;
+
+			let now = <frame_system::Pallet<T>>::block_number();
+
+			// keep only the chunks which are still unbonding & sum up the matured ones.
+			let mut amount: BalanceOf<T> = Zero::zero();
+			stake_info.unbonding.retain(|chunk| {
+				if chunk.unlock_at <= now {
+					amount = amount.saturating_add(chunk.amount);
+					false
+				} else {
+					true
+				}
+			});
+
+			// ensure there is something to refund
+			ensure!(!amount.is_zero(), Error::<T>::NoMaturedUnbonding);
+
+			let total_locked = stake_info.total();
+			if total_locked.is_zero() {
+				// nothing staked or unbonding anymore
+				T::MyCurrency::remove_lock(ID1, &voter);
+				Stakes::<T>::remove(&voter);
+			} else {
+				T::MyCurrency::set_lock(ID1, &voter, total_locked, WithdrawReasons::all());
+				Stakes::<T>::insert(&voter, stake_info);
+			}
+
+			// Emit an event.
+			Self::deposit_event(Event::Refunded { voter, amount });
+
+			Ok(())
+		}
+
+		/// Register the caller as a proxy, whose producer votes carry the vote weight of all the
+		/// accounts delegating to it.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::register_proxy(T::MaxVotesPerAccount::get()))]
+		pub fn register_proxy(origin: OriginFor<T>) -> DispatchResult {
+			let proxy = ensure_signed(origin)?;
+
+			let mut voter_info = Voting::<T>::get(&proxy).unwrap_or_default();
+
+			// ensure the caller is not a proxy already
+			ensure!(!voter_info.is_proxy, Error::<T>::AlreadyProxy);
+
+			// ensure the caller is not delegating to a proxy itself
+			ensure!(voter_info.delegate_to.is_none(), Error::<T>::ProxyCannotDelegate);
+
+			voter_info.is_proxy = true;
+			Voting::<T>::insert(&proxy, voter_info);
+
+			// the votes of the proxy now carry the delegated vote weight (if any).
+			Self::propagate_weight_change(&proxy);
+
+			// Emit an event.
+			Self::deposit_event(Event::ProxyRegistered { proxy });
+
+			Ok(())
+		}
+
+		/// Unregister the caller as a proxy.
+		///
+		/// The vote weight delegated to it stops counting until it registers again or the
+		/// delegators vote for someone else.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::unregister_proxy(T::MaxVotesPerAccount::get()))]
+		pub fn unregister_proxy(origin: OriginFor<T>) -> DispatchResult {
+			let proxy = ensure_signed(origin)?;
+
+			let mut voter_info = Voting::<T>::get(&proxy).ok_or(Error::<T>::NotAProxy)?;
+
+			// ensure the caller is a proxy
+			ensure!(voter_info.is_proxy, Error::<T>::NotAProxy);
+
+			voter_info.is_proxy = false;
+			Voting::<T>::insert(&proxy, voter_info);
+
+			// the votes of the proxy don't carry the delegated vote weight anymore.
+			Self::propagate_weight_change(&proxy);
+
+			// Emit an event.
+			Self::deposit_event(Event::ProxyUnregistered { proxy });
+
+			Ok(())
+		}
+
+		/// Delegate the caller's vote to a proxy instead of voting for producers directly.
+		///
+		/// Replaces the caller's previous producer votes (if any).
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::vote_proxy(T::MaxVotesPerAccount::get()))]
+		pub fn vote_proxy(origin: OriginFor<T>, proxy: T::AccountId) -> DispatchResult {
+			let voter = ensure_signed(origin)?;
+
+			// ensure the voter doesn't delegate to itself
+			ensure!(voter != proxy, Error::<T>::CannotProxyToSelf);
+
+			// ensure the voter has some stake
+			let stake_info = Stakes::<T>::get(&voter).ok_or(Error::<T>::NothingStaked)?;
+			ensure!(!stake_info.staked.is_zero(), Error::<T>::NothingStaked);
+
+			// ensure the voter is not a proxy
+			if let Some(voter_info) = Voting::<T>::get(&voter) {
+				ensure!(!voter_info.is_proxy, Error::<T>::ProxyCannotDelegate);
+			}
+
+			// ensure the proxy is registered
+			let proxy_info = Voting::<T>::get(&proxy).ok_or(Error::<T>::NotAProxy)?;
+			ensure!(proxy_info.is_proxy, Error::<T>::NotAProxy);
+
+			Self::update_votes(&voter, Some(proxy.clone()), BoundedVec::default());
+
+			// Emit an event.
+			Self::deposit_event(Event::VotedProxy { voter, proxy });
+
+			Ok(())
+		}
+
+		/// Update the URL & location of the caller as a registered producer.
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::update_producer())]
+		pub fn update_producer(
+			origin: OriginFor<T>,
+			url: Vec<u8>,
+			location: u16,
+		) -> DispatchResult {
+			let producer = ensure_signed(origin)?;
+
+			let url = Self::validate_producer_info(url, location)?;
+
+			let mut producer_info =
+				ProducerTable::<T>::get(&producer).ok_or(Error::<T>::NonExistentProducer)?;
+
+			// check for producer status
+			ensure!(producer_info.is_active, Error::<T>::ProducerNotActive);
+
+			producer_info.url = url;
+			producer_info.location = location;
+			ProducerTable::<T>::insert(&producer, producer_info);
+
+			// Emit an event.
+			Self::deposit_event(Event::ProducerUpdated { producer });
+
+			Ok(())
+		}
+
+		/// Signal that the producer is alive.
+		///
+		/// Sent as an unsigned transaction with a payload signed by the producer's heartbeat key
+		/// (`KEY_TYPE`, see `set_heartbeat_key`) from the offchain worker.
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::heartbeat())]
+		pub fn heartbeat(
+			origin: OriginFor<T>,
+			heartbeat: HeartbeatPayload<T::Public, T::BlockNumber>,
+			_signature: T::Signature,
+		) -> DispatchResult {
+			ensure_none(origin)?;
+
+			let producer = HeartbeatKeys::<T>::get(heartbeat.public.into_account())
+				.ok_or(Error::<T>::UnknownHeartbeatKey)?;
+
+			let mut producer_info =
+				ProducerTable::<T>::get(&producer).ok_or(Error::<T>::NonExistentProducer)?;
+
+			// check for producer status
+			ensure!(producer_info.is_active, Error::<T>::ProducerNotActive);
+
+			let block_number = <frame_system::Pallet<T>>::block_number();
+			producer_info.last_heartbeat = block_number;
+			ProducerTable::<T>::insert(&producer, producer_info);
+
+			// Emit an event.
+			Self::deposit_event(Event::HeartbeatReceived { producer, block_number });
+
+			Ok(())
+		}
+
+		/// Claim the block production & vote rewards of the caller as a registered producer.
+		///
+		/// Like EOSIO's `claimrewards`, the inflation accrued since the last claim (of any
+		/// producer) is added to the per-block & per-vote buckets, and the producer is paid its
+		/// share of the per-block bucket as per its unpaid blocks & its share of the per-vote
+		/// bucket as per its total votes. The rewards are minted on payout.
+		///
+		/// A producer can claim at most once per `ClaimInterval`.
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::claim_rewards())]
+		pub fn claim_rewards(origin: OriginFor<T>) -> DispatchResult {
+			let producer = ensure_signed(origin)?;
+
+			let mut producer_info =
+				ProducerTable::<T>::get(&producer).ok_or(Error::<T>::NonExistentProducer)?;
+
+			// check for producer status
+			ensure!(producer_info.is_active, Error::<T>::ProducerNotActive);
+
+			// ensure not claimed within the claim interval
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(
+				now.saturating_sub(producer_info.last_claim_time) >= T::ClaimInterval::get().into(),
+				Error::<T>::ClaimTooEarly
+			);
+
+			// add the inflation accrued since the last fill to the buckets
+			let (per_block_bucket, per_vote_bucket) = Self::accrued_buckets(now);
+			LastBucketFill::<T>::put(now);
+
+			let (per_block_pay, per_vote_pay) =
+				Self::producer_pay(&producer_info, per_block_bucket, per_vote_bucket);
+
+			PerBlockBucket::<T>::put(per_block_bucket.saturating_sub(per_block_pay));
+			PerVoteBucket::<T>::put(per_vote_bucket.saturating_sub(per_vote_pay));
+			TotalUnpaidBlocks::<T>::mutate(|total| {
+				*total = total.saturating_sub(producer_info.unpaid_blocks)
+			});
+
+			producer_info.unpaid_blocks = 0;
+			producer_info.last_claim_time = now;
+			ProducerTable::<T>::insert(&producer, producer_info);
+
+			// mint the rewards
+			let reward = per_block_pay.saturating_add(per_vote_pay);
+			if !reward.is_zero() {
+				let _ = T::MyCurrency::deposit_creating(&producer, reward);
+			}
+
+			// Emit an event.
+			Self::deposit_event(Event::RewardsClaimed { producer, per_block_pay, per_vote_pay });
+
+			Ok(())
+		}
+
+		/// Set the heartbeat key (as an account) of the caller as a producer, replacing the
+		/// previous one (if any).
+		///
+		/// The heartbeats signed by this key (`KEY_TYPE`) count for the caller. A key can't be
+		/// used by more than one producer.
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::set_heartbeat_key())]
+		pub fn set_heartbeat_key(origin: OriginFor<T>, key: T::AccountId) -> DispatchResult {
+			let producer = ensure_signed(origin)?;
+
+			ensure!(ProducerTable::<T>::contains_key(&producer), Error::<T>::NonExistentProducer);
+			ensure!(
+				HeartbeatKeys::<T>::get(&key).map_or(true, |owner| owner == producer),
+				Error::<T>::HeartbeatKeyInUse
+			);
+
+			// release the previous key
+			if let Some(previous) = HeartbeatKeyOf::<T>::get(&producer) {
+				HeartbeatKeys::<T>::remove(previous);
+			}
+			HeartbeatKeys::<T>::insert(&key, &producer);
+			HeartbeatKeyOf::<T>::insert(&producer, &key);
+
+			// Emit an event.
+			Self::deposit_event(Event::HeartbeatKeySet { producer, key });
+
 			Ok(())
 		}
 	}
 
 	impl<T: Config> Pallet<T> {
+		/// Get the vote weight of the staked amount at the current block.
+		///
+		/// Follows EOSIO's `stake2vote` i.e. `stake * 2^(weeks_since_epoch/52)`, so that newer
+		/// votes weigh more & older votes decay unless refreshed. Here, the epoch is the genesis
+		/// block.
+		pub fn stake_to_vote_weight(staked: BalanceOf<T>) -> VoteWeight {
+			let now = <frame_system::Pallet<T>>::block_number().saturated_into::<u32>();
+			let weeks = now / T::BlocksPerWeek::get().max(1);
+
+			// 2^(weeks/52) = 2^(weeks div 52) * 2^((weeks mod 52)/52)
+			let factor = FixedU128::from(2).saturating_pow((weeks / 52) as usize).saturating_mul(
+				FixedU128::from_inner(VOTE_WEIGHT_FRACTIONS[(weeks % 52) as usize]),
+			);
+
+			factor.saturating_mul_int(staked.saturated_into::<u128>())
+		}
+
+		/// Replace the voter's votes with either the given proxy or the given producers.
+		///
+		/// The previous vote weight is subtracted from the previous proxy or producers & the
+		/// current vote weight is added to the given proxy or producers. A proxy's current vote
+		/// weight includes the vote weight delegated to it.
+		fn update_votes(
+			voter: &T::AccountId,
+			proxy: Option<T::AccountId>,
+			producers: BoundedVec<T::AccountId, T::MaxVotesPerAccount>,
+		) {
+			let mut voter_info = Voting::<T>::get(voter).unwrap_or_default();
+
+			let staked = Stakes::<T>::get(voter).map(|s| s.staked).unwrap_or_else(Zero::zero);
+			let mut new_vote_weight = Self::stake_to_vote_weight(staked);
+			if voter_info.is_proxy {
+				new_vote_weight = new_vote_weight.saturating_add(voter_info.proxied_vote_weight);
+			}
+
+			// remove the previous votes
+			if let Some(old_proxy) = voter_info.delegate_to.take() {
+				Self::sub_proxied_vote_weight(&old_proxy, voter_info.last_vote_weight);
+			} else {
+				for producer in voter_info.producers.iter() {
+					Self::sub_producer_votes(producer, voter_info.last_vote_weight);
+				}
+			}
+
+			// add the new votes
+			if let Some(ref new_proxy) = proxy {
+				Self::add_proxied_vote_weight(new_proxy, new_vote_weight);
+			} else {
+				for producer in producers.iter() {
+					Self::add_producer_votes(producer, new_vote_weight);
+				}
+			}
+
+			voter_info.delegate_to = proxy;
+			voter_info.producers = producers;
+			voter_info.last_vote_weight = new_vote_weight;
+
+			if voter_info.is_empty() {
+				Voting::<T>::remove(voter);
+			} else {
+				Voting::<T>::insert(voter, voter_info);
+			}
+		}
+
+		/// Re-apply the voter's votes with the vote weight of their current stake.
+		fn update_voting_power(voter: &T::AccountId) {
+			if let Some(voter_info) = Voting::<T>::get(voter) {
+				if voter_info.is_proxy {
+					// NOTE: a proxy can't delegate, so it only has producer votes.
+					Self::propagate_weight_change(voter);
+				} else {
+					Self::update_votes(voter, voter_info.delegate_to, voter_info.producers);
+				}
+			}
+		}
+
+		/// Re-apply the proxy's producer votes with its current vote weight i.e. its own stake
+		/// plus the vote weight delegated to it (if still registered as a proxy).
+		fn propagate_weight_change(proxy: &T::AccountId) {
+			if let Some(mut proxy_info) = Voting::<T>::get(proxy) {
+				let staked = Stakes::<T>::get(proxy).map(|s| s.staked).unwrap_or_else(Zero::zero);
+				let mut new_vote_weight = Self::stake_to_vote_weight(staked);
+				if proxy_info.is_proxy {
+					new_vote_weight =
+						new_vote_weight.saturating_add(proxy_info.proxied_vote_weight);
+				}
+
+				if new_vote_weight != proxy_info.last_vote_weight {
+					for producer in proxy_info.producers.iter() {
+						Self::sub_producer_votes(producer, proxy_info.last_vote_weight);
+						Self::add_producer_votes(producer, new_vote_weight);
+					}
+				}
+
+				proxy_info.last_vote_weight = new_vote_weight;
+
+				if proxy_info.is_empty() {
+					Voting::<T>::remove(proxy);
+				} else {
+					Voting::<T>::insert(proxy, proxy_info);
+				}
+			}
+		}
+
+		fn add_proxied_vote_weight(proxy: &T::AccountId, vote_weight: VoteWeight) {
+			Voting::<T>::mutate(proxy, |maybe_info| {
+				if let Some(info) = maybe_info {
+					info.proxied_vote_weight = info.proxied_vote_weight.saturating_add(vote_weight);
+				}
+			});
+			Self::propagate_weight_change(proxy);
+		}
+
+		fn sub_proxied_vote_weight(proxy: &T::AccountId, vote_weight: VoteWeight) {
+			Voting::<T>::mutate(proxy, |maybe_info| {
+				if let Some(info) = maybe_info {
+					info.proxied_vote_weight = info.proxied_vote_weight.saturating_sub(vote_weight);
+				}
+			});
+			Self::propagate_weight_change(proxy);
+		}
+
+		fn add_producer_votes(producer: &T::AccountId, vote_weight: VoteWeight) {
+			ProducerTable::<T>::mutate(producer, |maybe_info| {
+				if let Some(info) = maybe_info {
+					info.total_votes = info.total_votes.saturating_add(vote_weight);
+					TotalProducerVoteWeight::<T>::mutate(|total| {
+						*total = total.saturating_add(vote_weight)
+					});
+				}
+			});
+		}
+
+		fn sub_producer_votes(producer: &T::AccountId, vote_weight: VoteWeight) {
+			ProducerTable::<T>::mutate(producer, |maybe_info| {
+				if let Some(info) = maybe_info {
+					// NOTE: subtract what is actually removed, so that the total stays in sync.
+					let removed = vote_weight.min(info.total_votes);
+					info.total_votes = info.total_votes.saturating_sub(removed);
+					TotalProducerVoteWeight::<T>::mutate(|total| {
+						*total = total.saturating_sub(removed)
+					});
+				}
+			});
+		}
+
+		/// Credit the author of the current block (if an active producer) with an unpaid block.
+		fn note_author() -> Weight {
+			let digest = <frame_system::Pallet<T>>::digest();
+			let pre_runtime_digests = digest.logs.iter().filter_map(|d| d.as_pre_runtime());
+			let author = match T::FindAuthor::find_author(pre_runtime_digests) {
+				Some(author) => author,
+				None => return T::DbWeight::get().reads(1),
+			};
+
+			let credited = ProducerTable::<T>::mutate(&author, |maybe_info| match maybe_info {
+				Some(info) if info.is_active => {
+					info.unpaid_blocks = info.unpaid_blocks.saturating_add(1);
+					true
+				},
+				_ => false,
+			});
+			if credited {
+				TotalUnpaidBlocks::<T>::mutate(|total| *total = total.saturating_add(1));
+			}
+
+			T::DbWeight::get().reads_writes(3, 2)
+		}
+
+		/// The per-block & per-vote buckets incl. the producers' inflation accrued since the last
+		/// fill until `now`.
+		fn accrued_buckets(now: T::BlockNumber) -> (BalanceOf<T>, BalanceOf<T>) {
+			let per_block_bucket = PerBlockBucket::<T>::get();
+			let per_vote_bucket = PerVoteBucket::<T>::get();
+
+			let elapsed = now.saturating_sub(LastBucketFill::<T>::get()).saturated_into::<u128>();
+			if elapsed == 0 {
+				return (per_block_bucket, per_vote_bucket);
+			}
+
+			let supply = T::MyCurrency::total_issuance().saturated_into::<u128>();
+			let to_producers =
+				FixedU128::saturating_from_rational(elapsed, T::BlocksPerYear::get().max(1))
+					.saturating_mul_int(T::ProducersInflationRate::get() * supply);
+			let to_per_block_pay = T::PerBlockRewardShare::get() * to_producers;
+			let to_per_vote_pay = to_producers.saturating_sub(to_per_block_pay);
+
+			(
+				per_block_bucket.saturating_add(to_per_block_pay.saturated_into()),
+				per_vote_bucket.saturating_add(to_per_vote_pay.saturated_into()),
+			)
+		}
+
+		/// The producer's share of the per-block bucket as per its unpaid blocks & of the per-vote
+		/// bucket as per its total votes (forfeited if below `MinPerVotePay`).
+		fn producer_pay(
+			producer_info: &ProducerInfo<T>,
+			per_block_bucket: BalanceOf<T>,
+			per_vote_bucket: BalanceOf<T>,
+		) -> (BalanceOf<T>, BalanceOf<T>) {
+			// pay as per the blocks produced
+			let total_unpaid_blocks = TotalUnpaidBlocks::<T>::get();
+			let per_block_pay: BalanceOf<T> = if total_unpaid_blocks > 0 {
+				FixedU128::saturating_from_rational(
+					producer_info.unpaid_blocks,
+					total_unpaid_blocks,
+				)
+				.saturating_mul_int(per_block_bucket.saturated_into::<u128>())
+				.saturated_into()
+			} else {
+				Zero::zero()
+			};
+
+			// pay as per the votes received
+			let per_vote_pay: BalanceOf<T> = FixedU128::checked_from_rational(
+				producer_info.total_votes,
+				TotalProducerVoteWeight::<T>::get(),
+			)
+			.unwrap_or_default()
+			.saturating_mul_int(per_vote_bucket.saturated_into::<u128>())
+			.saturated_into();
+			let per_vote_pay =
+				if per_vote_pay < T::MinPerVotePay::get() { Zero::zero() } else { per_vote_pay };
+
+			(per_block_pay, per_vote_pay)
+		}
+
+		/// Rank the registered (active) producers i.e. the `Candidates` by their total votes &
+		/// elect the top `ActiveValidatorsCount` as active & the next `StandbyValidatorsCount` as
+		/// standby.
+		///
+		/// Producers without any votes are not elected. If none of the producers can be elected,
+		/// the current schedule is kept, minus the producers which are no longer registered (i.e.
+		/// unregistered or just deactivated for missing their heartbeats). If none of the active
+		/// producers would be left, the current schedule is kept as is on purpose, since the
+		/// session can't rotate to an empty set of authorities anyway.
+		pub fn update_elected_producers() -> Weight {
+			let now = <frame_system::Pallet<T>>::block_number();
+			let heartbeat_duration: T::BlockNumber = T::HeartbeatDuration::get().into();
+
+			let candidates = Candidates::<T>::get();
+			let count = candidates.len() as u32;
+			let mut producers: Vec<(T::AccountId, VoteWeight)> = Vec::new();
+			let mut offline: Vec<(T::AccountId, T::BlockNumber)> = Vec::new();
+			for producer in candidates {
+				if let Some(info) = ProducerTable::<T>::get(&producer) {
+					if now.saturating_sub(info.last_heartbeat) > heartbeat_duration {
+						offline.push((producer, info.last_heartbeat));
+					} else if !info.total_votes.is_zero() {
+						producers.push((producer, info.total_votes));
+					}
+				}
+			}
+
+			// deactivate the producers which missed their heartbeats
+			if !offline.is_empty() {
+				Candidates::<T>::mutate(|candidates| {
+					candidates.retain(|c| !offline.iter().any(|(producer, _)| producer == c))
+				});
+			}
+			for (producer, last_heartbeat) in offline.iter() {
+				Self::deactivate_producer(producer, *last_heartbeat);
+			}
+
+			// the producer info, the bond & the reserved balance of each deactivated producer, plus
+			// the candidates
+			let deactivated = offline.len() as u64;
+			let weight = T::WeightInfo::update_elected_producers(count).saturating_add(
+				T::DbWeight::get().reads_writes(
+					deactivated,
+					3u64.saturating_mul(deactivated).saturating_add(deactivated.min(1)),
+				),
+			);
+
+			let (active, standby) = if producers.is_empty() {
+				// keep the registered producers of the current schedule
+				let candidates = Candidates::<T>::get();
+				let mut active = ActiveProducers::<T>::get();
+				active.retain(|producer| candidates.contains(producer));
+				if active.is_empty() {
+					return weight.saturating_add(T::DbWeight::get().reads(2));
+				}
+				let mut standby = StandbyProducers::<T>::get();
+				standby.retain(|producer| candidates.contains(producer));
+				(active, standby)
+			} else {
+				// sort by votes (descending) & then by account (for a deterministic order in case
+				// of tie)
+				producers.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+				let mut ranked = producers.into_iter().map(|(producer, _)| producer);
+				let active: BoundedVec<T::AccountId, T::ActiveValidatorsCount> = ranked
+					.by_ref()
+					.take(T::ActiveValidatorsCount::get() as usize)
+					.collect::<Vec<_>>()
+					.try_into()
+					.expect("taken at most `ActiveValidatorsCount` producers; qed");
+				let standby: BoundedVec<T::AccountId, T::StandbyValidatorsCount> = ranked
+					.take(T::StandbyValidatorsCount::get() as usize)
+					.collect::<Vec<_>>()
+					.try_into()
+					.expect("taken at most `StandbyValidatorsCount` producers; qed");
+				(active, standby)
+			};
+
+			let mut version = ScheduleVersion::<T>::get();
+			if active != ActiveProducers::<T>::get() {
+				version = version.saturating_add(1);
+				ScheduleVersion::<T>::put(version);
+			}
+
+			ActiveProducers::<T>::put(&active);
+			StandbyProducers::<T>::put(&standby);
+
+			// Emit an event.
+			Self::deposit_event(Event::NewProducerSchedule { version, active, standby });
+
+			// the current schedule (& the candidates, if none elected)
+			weight.saturating_add(T::DbWeight::get().reads_writes(3, 3))
+		}
+
+		/// Mark the producer as inactive (as if unregistered) & release its bond.
+		///
+		/// It has to register again in order to be voted for or elected.
+		///
+		/// NOTE: The caller has to remove the producer from `Candidates`.
+		fn deactivate_producer(producer: &T::AccountId, last_heartbeat: T::BlockNumber) {
+			ProducerTable::<T>::mutate(producer, |maybe_info| {
+				if let Some(info) = maybe_info {
+					info.is_active = false;
+				}
+			});
+
+			// release the bond
+			T::MyCurrency::unreserve(producer, ProducerBonds::<T>::take(producer));
+
+			// Emit an event.
+			Self::deposit_event(Event::ProducerDeactivated {
+				producer: producer.clone(),
+				last_heartbeat,
+			});
+		}
+
+		/// Send heartbeats signed by the local heartbeat keys of the active producers, which
+		/// haven't sent one within the last half of `HeartbeatDuration`.
+		fn send_heartbeats(block_number: T::BlockNumber) -> Result<(), &'static str> {
+			let interval: T::BlockNumber = (T::HeartbeatDuration::get() / 2).max(1).into();
+
+			let due: Vec<T::Public> =
+				<T::AuthorityId as AppCrypto<T::Public, T::Signature>>::RuntimeAppPublic::all()
+					.into_iter()
+					.map(|key| {
+						let generic_public = <T::AuthorityId as AppCrypto<
+							T::Public,
+							T::Signature,
+						>>::GenericPublic::from(key);
+						generic_public.into()
+					})
+					.filter(|public: &T::Public| {
+						HeartbeatKeys::<T>::get(public.clone().into_account())
+							.and_then(ProducerTable::<T>::get)
+							.map_or(false, |info| {
+								info.is_active
+									&& block_number.saturating_sub(info.last_heartbeat) >= interval
+							})
+					})
+					.collect();
+
+			// nothing to do on nodes without (due) producer keys
+			if due.is_empty() {
+				return Ok(());
+			}
+
+			let results = Signer::<T, T::AuthorityId>::all_accounts()
+				.with_filter(due)
+				.send_unsigned_transaction(
+					|account| HeartbeatPayload { block_number, public: account.public.clone() },
+					|payload, signature| Call::heartbeat { heartbeat: payload, signature },
+				);
+
+			for (account, result) in results.iter() {
+				match result {
+					Ok(()) => log::info!(
+						target: "pallet_eosio_system",
+						"[{:?}] Sent heartbeat at block {:?}",
+						account.id,
+						block_number
+					),
+					Err(e) => log::error!(
+						target: "pallet_eosio_system",
+						"[{:?}] Failed to send heartbeat: {:?}",
+						account.id,
+						e
+					),
+				}
+			}
+
+			Ok(())
+		}
+
+		/// Ensure the URL is valid UTF-8 within `MaxProducerInfoUrlLen` bytes & the location is a
+		/// valid ISO 3166-1 numeric country code.
+		fn validate_producer_info(
+			url: Vec<u8>,
+			location: u16,
+		) -> Result<BoundedVec<u8, T::MaxProducerInfoUrlLen>, DispatchError> {
+			ensure!(core::str::from_utf8(&url).is_ok(), Error::<T>::InvalidProducerInfoUrl);
+			let url: BoundedVec<u8, T::MaxProducerInfoUrlLen> =
+				url.try_into().map_err(|_| Error::<T>::InvalidProducerInfoUrl)?;
+
+			ensure!(
+				ISO_3166_NUMERIC_CODES.binary_search(&location).is_ok(),
+				Error::<T>::InvalidLocationCode
+			);
+
+			Ok(url)
+		}
+
 		/// Get the total staked amount of tokens
 		pub fn active(producer: T::AccountId) -> Option<bool> {
 			if let Some(producer_info) = ProducerTable::<T>::get(producer) {
@@ -324,5 +1691,184 @@ pub mod pallet {
 				None
 			}
 		}
+
+		// --- Runtime API ---
+		/// Up to `limit` producers sorted by their total votes (descending), like EOSIO's
+		/// `get_producers`.
+		pub fn get_producers(limit: u32) -> Vec<ProducerDetails<T::AccountId, T::BlockNumber>> {
+			let mut producers: Vec<ProducerDetails<T::AccountId, T::BlockNumber>> =
+				ProducerTable::<T>::iter()
+					.map(|(owner, info)| ProducerDetails {
+						owner,
+						total_votes: info.total_votes,
+						is_active: info.is_active,
+						url: info.url.into_inner(),
+						unpaid_blocks: info.unpaid_blocks,
+						last_claim_time: info.last_claim_time,
+						location: info.location,
+						last_heartbeat: info.last_heartbeat,
+					})
+					.collect();
+
+			// sort by votes (descending) & then by account (for a deterministic order in case of tie)
+			producers.sort_by(|a, b| {
+				b.total_votes.cmp(&a.total_votes).then_with(|| a.owner.cmp(&b.owner))
+			});
+			producers.truncate(limit as usize);
+
+			producers
+		}
+
+		/// The stake & votes of the account, like EOSIO's `get_account`.
+		pub fn get_voter(
+			account: T::AccountId,
+		) -> Option<VoterDetails<T::AccountId, BalanceOf<T>, T::BlockNumber>> {
+			let stake_info = Stakes::<T>::get(&account);
+			let voter_info = Voting::<T>::get(&account);
+			if stake_info.is_none() && voter_info.is_none() {
+				return None;
+			}
+
+			let stake_info = stake_info.unwrap_or_default();
+			let voter_info = voter_info.unwrap_or_default();
+			Some(VoterDetails {
+				staked: stake_info.staked,
+				unbonding: stake_info
+					.unbonding
+					.into_iter()
+					.map(|chunk| (chunk.amount, chunk.unlock_at))
+					.collect(),
+				proxy: voter_info.delegate_to,
+				producers: voter_info.producers.into_inner(),
+				last_vote_weight: voter_info.last_vote_weight,
+				is_proxy: voter_info.is_proxy,
+				proxied_vote_weight: voter_info.proxied_vote_weight,
+			})
+		}
+
+		/// The current producer schedule.
+		pub fn get_producer_schedule() -> ProducerSchedule<T::AccountId> {
+			ProducerSchedule {
+				version: ScheduleVersion::<T>::get(),
+				active: ActiveProducers::<T>::get().into_inner(),
+				standby: StandbyProducers::<T>::get().into_inner(),
+			}
+		}
+
+		/// Estimate of the rewards the producer would get by claiming at the current block
+		/// (if allowed by then), along with the block it can claim from.
+		pub fn get_unclaimed_rewards(
+			producer: T::AccountId,
+		) -> Option<RewardsEstimate<BalanceOf<T>, T::BlockNumber>> {
+			let producer_info = ProducerTable::<T>::get(&producer)?;
+
+			let now = <frame_system::Pallet<T>>::block_number();
+			let (per_block_bucket, per_vote_bucket) = Self::accrued_buckets(now);
+			let (per_block_pay, per_vote_pay) =
+				Self::producer_pay(&producer_info, per_block_bucket, per_vote_bucket);
+
+			Some(RewardsEstimate {
+				per_block_pay,
+				per_vote_pay,
+				claimable_at: producer_info
+					.last_claim_time
+					.saturating_add(T::ClaimInterval::get().into()),
+			})
+		}
+	}
+}
+
+/// Feeds the elected active producers to `pallet_session` which in turn rotates the Aura & GRANDPA
+/// authorities.
+///
+/// NOTE: The validators returned for a session are applied by `pallet_session` at the start of the
+/// following session.
+/// So, this pallet has to come before `pallet_session` in `construct_runtime!`, so that the ranking
+/// (in `on_initialize`) isn't one more rotation behind.
+impl<T: Config> pallet_session::SessionManager<T::AccountId> for Pallet<T> {
+	fn new_session(_new_index: u32) -> Option<Vec<T::AccountId>> {
+		// skip the producers without session keys, as `pallet_session` would drop them anyway.
+		let active: Vec<T::AccountId> = ActiveProducers::<T>::get()
+			.into_iter()
+			.filter(|producer| T::HasSessionKeys::contains(producer))
+			.collect();
+
+		// keep the current validators until there are elected producers.
+		if active.is_empty() {
+			None
+		} else {
+			Some(active)
+		}
+	}
+
+	fn end_session(_end_index: u32) {}
+
+	fn start_session(_start_index: u32) {}
+}
+
+/// Whether an account has set its session keys i.e. has `pallet_session::NextKeys`.
+pub struct HasSessionKeys<T>(core::marker::PhantomData<T>);
+
+impl<T: pallet_session::Config> frame_support::traits::Contains<T::ValidatorId>
+	for HasSessionKeys<T>
+{
+	fn contains(who: &T::ValidatorId) -> bool {
+		pallet_session::NextKeys::<T>::contains_key(who)
+	}
+}
+
+/// Producer as returned by the runtime API.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct ProducerDetails<AccountId, BlockNumber> {
+	pub owner: AccountId,
+	pub total_votes: VoteWeight,
+	pub is_active: bool,
+	pub url: Vec<u8>,
+	pub unpaid_blocks: u32,
+	pub last_claim_time: BlockNumber,
+	pub location: u16,
+	pub last_heartbeat: BlockNumber,
+}
+
+/// Stake & votes of an account as returned by the runtime API.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct VoterDetails<AccountId, Balance, BlockNumber> {
+	pub staked: Balance,
+	/// (amount, unlock_at) of the unstaked tokens still unbonding.
+	pub unbonding: Vec<(Balance, BlockNumber)>,
+	pub proxy: Option<AccountId>,
+	pub producers: Vec<AccountId>,
+	pub last_vote_weight: VoteWeight,
+	pub is_proxy: bool,
+	pub proxied_vote_weight: VoteWeight,
+}
+
+/// Active & standby producers as returned by the runtime API.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct ProducerSchedule<AccountId> {
+	pub version: u32,
+	pub active: Vec<AccountId>,
+	pub standby: Vec<AccountId>,
+}
+
+/// Estimate of a producer's unclaimed rewards as returned by the runtime API.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct RewardsEstimate<Balance, BlockNumber> {
+	pub per_block_pay: Balance,
+	pub per_vote_pay: Balance,
+	/// Block from which the producer can claim.
+	pub claimable_at: BlockNumber,
+}
+
+/// Payload of a producer's heartbeat, signed with its heartbeat key.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct HeartbeatPayload<Public, BlockNumber> {
+	pub block_number: BlockNumber,
+	pub public: Public,
+}
+
+impl<T: SigningTypes> SignedPayload<T> for HeartbeatPayload<T::Public, BlockNumberFor<T>> {
+	fn public(&self) -> T::Public {
+		self.public.clone()
 	}
 }
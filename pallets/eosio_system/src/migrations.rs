@@ -0,0 +1,68 @@
+//! Storage migrations of the EOSIO System pallet.
+
+use super::*;
+
+/// Storage version 1: the vote weights are `VoteWeight` integers instead of `FixedU128` & the
+/// registered producers are tracked in `Candidates`.
+pub mod v1 {
+	use super::*;
+	use core::marker::PhantomData;
+	use frame_support::{
+		inherent::Vec,
+		log,
+		sp_runtime::{BoundedVec, FixedPointNumber, FixedU128},
+		traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+		weights::Weight,
+	};
+
+	/// Convert the `FixedU128` vote weights of the voters, the producers & their total to integers
+	/// & fill `Candidates` with the registered producers.
+	///
+	/// Both weights are encoded as `u128`, so only the values are scaled down (by
+	/// `FixedU128::DIV`). The registered producers beyond `MaxProducers` are left out of the
+	/// ranking until they register again.
+	pub struct MigrateToV1<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() >= 1 {
+				log::info!(target: "pallet_eosio_system", "Skipping the v1 migration");
+				return T::DbWeight::get().reads(1);
+			}
+
+			let to_integer = |weight: VoteWeight| weight / FixedU128::DIV;
+			let mut count: u64 = 0;
+			Voting::<T>::translate_values::<VoterInfo<T>, _>(|mut voter_info| {
+				count += 1;
+				voter_info.last_vote_weight = to_integer(voter_info.last_vote_weight);
+				voter_info.proxied_vote_weight = to_integer(voter_info.proxied_vote_weight);
+				Some(voter_info)
+			});
+			let mut candidates: Vec<T::AccountId> = Vec::new();
+			ProducerTable::<T>::translate::<ProducerInfo<T>, _>(|producer, mut producer_info| {
+				count += 1;
+				producer_info.total_votes = to_integer(producer_info.total_votes);
+				if producer_info.is_active {
+					candidates.push(producer);
+				}
+				Some(producer_info)
+			});
+			TotalProducerVoteWeight::<T>::mutate(|total| *total = to_integer(*total));
+
+			if candidates.len() > T::MaxProducers::get() as usize {
+				log::warn!(
+					target: "pallet_eosio_system",
+					"Leaving {} registered producers out of the candidates",
+					candidates.len() - T::MaxProducers::get() as usize
+				);
+			}
+			Candidates::<T>::put(BoundedVec::truncate_from(candidates));
+
+			StorageVersion::new(1).put::<Pallet<T>>();
+			log::info!(target: "pallet_eosio_system", "Migrated {} vote weights to v1", count);
+
+			// the version, the entries, the total & the candidates
+			T::DbWeight::get().reads_writes(count.saturating_add(2), count.saturating_add(3))
+		}
+	}
+}
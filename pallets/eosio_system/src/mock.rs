@@ -1,12 +1,13 @@
 use crate as pallet_eosio_system;
 use frame_support::{
 	parameter_types,
-	traits::{ConstU128, ConstU16, ConstU32, ConstU64},
+	traits::{ConstU128, ConstU16, ConstU32, ConstU64, Contains, FindAuthor},
 };
 use sp_core::H256;
 use sp_runtime::{
-	testing::Header,
-	traits::{BlakeTwo256, IdentityLookup},
+	testing::{Header, TestSignature, TestXt, UintAuthorityId},
+	traits::{BlakeTwo256, Extrinsic as ExtrinsicT, IdentityLookup},
+	ConsensusEngineId, Perbill,
 };
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
@@ -17,6 +18,7 @@ pub const ALICE: u64 = 1;
 pub const BOB: u64 = 2;
 pub const CHARLIE: u64 = 3;
 pub const DAVE: u64 = 4;
+pub const EVE: u64 = 5;
 pub const TREASURY: u64 = 100;
 
 pub const ONE_YEAR: u32 = 5_184_000;
@@ -81,26 +83,124 @@ impl pallet_balances::Config for Test {
 	type MaxHolds = ();
 }
 
+type Extrinsic = TestXt<RuntimeCall, ()>;
+
+// NOTE: `UintAuthorityId` identifies as the (`u64`) account of the same value, so that the test
+// accounts can be set as heartbeat keys.
+impl frame_system::offchain::SigningTypes for Test {
+	type Public = UintAuthorityId;
+	type Signature = TestSignature;
+}
+
+impl<LocalCall> frame_system::offchain::SendTransactionTypes<LocalCall> for Test
+where
+	RuntimeCall: From<LocalCall>,
+{
+	type OverarchingCall = RuntimeCall;
+	type Extrinsic = Extrinsic;
+}
+
+impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Test
+where
+	RuntimeCall: From<LocalCall>,
+{
+	fn create_transaction<C: frame_system::offchain::AppCrypto<Self::Public, Self::Signature>>(
+		call: RuntimeCall,
+		_public: UintAuthorityId,
+		_account: u64,
+		nonce: u64,
+	) -> Option<(RuntimeCall, <Extrinsic as ExtrinsicT>::SignaturePayload)> {
+		Some((call, (nonce, ())))
+	}
+}
+
+/// Heartbeat key of the test accounts.
+pub struct TestAuthId;
+
+impl frame_system::offchain::AppCrypto<UintAuthorityId, TestSignature> for TestAuthId {
+	type RuntimeAppPublic = UintAuthorityId;
+	type GenericSignature = TestSignature;
+	type GenericPublic = UintAuthorityId;
+}
+
+parameter_types! {
+	// author of the current block, set by the tests
+	pub static Author: Option<u64> = None;
+}
+
+parameter_types! {
+	// producers without session keys, set by the tests
+	pub static WithoutSessionKeys: Vec<u64> = vec![];
+}
+
+/// Every account has session keys, unless set via `WithoutSessionKeys::set`.
+pub struct SessionKeysGiven;
+
+impl Contains<u64> for SessionKeysGiven {
+	fn contains(who: &u64) -> bool {
+		!WithoutSessionKeys::get().contains(who)
+	}
+}
+
+/// Block author as set via `Author::set`.
+pub struct AuthorGiven;
+
+impl FindAuthor<u64> for AuthorGiven {
+	fn find_author<'a, I>(_digests: I) -> Option<u64>
+	where
+		I: 'a + IntoIterator<Item = (ConsensusEngineId, &'a [u8])>,
+	{
+		Author::get()
+	}
+}
+
 parameter_types! {
+	pub const UnsignedPriority: u64 = 1 << 20;	// 2**20
 	pub const MinStakeAmount: Balance = 50 * 1e10 as Balance;	// 50 EOS
 	pub const MaxVotesPerAccount: u32 = 30;	// 30 validators
-	pub const ActiveValidatorsCount: u8 = 21;	// 21 validators
-	pub const StandbyValidatorsCount: u16 = 50;	// 50 validators
+	pub const ActiveValidatorsCount: u32 = 21;	// 21 validators
+	pub const StandbyValidatorsCount: u32 = 50;	// 50 validators
+	pub static MaxProducers: u32 = 100;	// registered producers, set by the tests
 	pub const RankingDuration: u32 = 14_400;	// in blocks
 	pub const HeartbeatDuration: u32 = 10;		// in blocks
+	pub const MaxProducerInfoUrlLen: u32 = 512;	// in bytes
+	pub static ProducerBond: Balance = 100 * 1e10 as Balance;	// 100 EOS, set by the tests
+	pub const UnbondingDuration: u32 = 43_200;	// 3 days in blocks
+	pub const MaxUnbondingChunks: u32 = 32;
+	pub const BlocksPerWeek: u32 = 100_800;	// in blocks
+	pub const BlocksPerYear: u32 = 5_256_000;	// in blocks
+	pub const ProducersInflationRate: Perbill = Perbill::from_percent(1);	// 1% p.a.
+	pub const PerBlockRewardShare: Perbill = Perbill::from_percent(25);	// 25%
+	pub const ClaimInterval: u32 = 14_400;	// 1 day in blocks
+	pub const MinPerVotePay: Balance = 100 * 1e10 as Balance;	// 100 EOS
 }
 
 /// Configure the pallet-eosio-system in pallets/eosio_system.
 impl pallet_eosio_system::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
+	type AuthorityId = TestAuthId;
+	type UnsignedPriority = UnsignedPriority;
 	type WeightInfo = ();
 	type MyCurrency = Balances;
 	type MinStakeAmount = MinStakeAmount;
 	type MaxVotesPerAccount = MaxVotesPerAccount;
 	type ActiveValidatorsCount = ActiveValidatorsCount;
 	type StandbyValidatorsCount = StandbyValidatorsCount;
+	type MaxProducers = MaxProducers;
 	type RankingDuration = RankingDuration;
 	type HeartbeatDuration = HeartbeatDuration;
+	type MaxProducerInfoUrlLen = MaxProducerInfoUrlLen;
+	type ProducerBond = ProducerBond;
+	type UnbondingDuration = UnbondingDuration;
+	type MaxUnbondingChunks = MaxUnbondingChunks;
+	type BlocksPerWeek = BlocksPerWeek;
+	type FindAuthor = AuthorGiven;
+	type HasSessionKeys = SessionKeysGiven;
+	type BlocksPerYear = BlocksPerYear;
+	type ProducersInflationRate = ProducersInflationRate;
+	type PerBlockRewardShare = PerBlockRewardShare;
+	type ClaimInterval = ClaimInterval;
+	type MinPerVotePay = MinPerVotePay;
 }
 
 // Build genesis storage according to the mock runtime.
@@ -113,6 +213,7 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 			(BOB, 20_000 * 1e10 as Balance),
 			(CHARLIE, 30_000 * 1e10 as Balance),
 			(DAVE, 40_000 * 1e10 as Balance),
+			(EVE, 50_000 * 1e10 as Balance),
 			(TREASURY, 1_000_000 * 1e10 as Balance),
 		],
 	}
@@ -1,24 +1,1458 @@
-use crate::{mock::*, Error, Event};
-use frame_support::{assert_noop, assert_ok, sp_runtime::Permill};
-
+use crate::{mock::*, Error, Event, HeartbeatPayload, UnbondingChunk, VoteWeight};
+use codec::Encode;
+use frame_support::{
+	assert_noop, assert_ok,
+	pallet_prelude::{InvalidTransaction, TransactionSource, ValidateUnsigned},
+	traits::Hooks,
+	BoundedVec,
+};
+use pallet_session::SessionManager;
 use sp_runtime::{
-	traits::{checked_pow, CheckedAdd, CheckedMul, CheckedSub},
-	DispatchError::{BadOrigin, Token},
-	FixedU128,
-	TokenError::Frozen,
+	testing::{TestSignature, UintAuthorityId},
+	DispatchError::BadOrigin,
+	FixedPointNumber, FixedU128, Perbill,
 };
 
-// suppress warnings for declared variables, but not used.
 // Block wise assumptions for corresponding time, assuming 1 BLOCK = 6 seconds
-const ONE_DAY: u32 = 14_400;
-const ONE_MONTH: u32 = 432_000;
-const ONE_QUARTER_YEAR: u32 = 1_296_000;
-const HALF_YEAR: u32 = 2_592_000;
-const THREE_QUARTER_YEAR: u32 = 3_888_000;
-const ONE_YEAR: u32 = 5_184_000;
+const ONE_DAY: u64 = 14_400;
+const ONE_WEEK: u64 = 100_800;
+
+const MIN_STAKE: Balance = 50 * 1e10 as Balance; // 50 EOS
+const BOND: Balance = 100 * 1e10 as Balance; // 100 EOS
+const URL: &[u8] = b"https://eosnation.io";
+const LOCATION: u16 = 124; // Canada
+const UNBONDING_DURATION: u64 = 43_200;
 
 // ===== helpers =====
 
+/// Register the producer with its heartbeat key.
+fn register(producer: u64) {
+	assert_ok!(EOSIOSystem::register_producer(
+		RuntimeOrigin::signed(producer),
+		URL.to_vec(),
+		LOCATION
+	));
+	assert_ok!(EOSIOSystem::set_heartbeat_key(
+		RuntimeOrigin::signed(producer),
+		heartbeat_key(producer)
+	));
+}
+
+/// Heartbeat key (as an account) of a producer, distinct from its own account.
+fn heartbeat_key(producer: u64) -> u64 {
+	producer + 10
+}
+
+fn stake(voter: u64, amount: Balance) {
+	assert_ok!(EOSIOSystem::stake_to_vote(RuntimeOrigin::signed(voter), amount));
+}
+
+fn vote(voter: u64, producers: Vec<u64>) {
+	assert_ok!(EOSIOSystem::vote_producer(RuntimeOrigin::signed(voter), producers));
+}
+
+/// Total votes of a registered producer.
+fn votes(producer: u64) -> VoteWeight {
+	EOSIOSystem::producer_table(producer).unwrap().total_votes
+}
+
+/// Vote weight of the staked amount within the first week (since genesis).
+fn weight(amount: Balance) -> VoteWeight {
+	amount
+}
+
+fn locked(account: u64) -> Balance {
+	Balances::locks(account)
+		.iter()
+		.find(|lock| lock.id == *b"EOSIO___")
+		.map(|lock| lock.amount)
+		.unwrap_or_default()
+}
+
+fn heartbeat_call(key: u64, block_number: u64, signer: u64) -> crate::Call<Test> {
+	let heartbeat = HeartbeatPayload { block_number, public: UintAuthorityId(key) };
+	let signature = TestSignature(signer, heartbeat.encode());
+	crate::Call::heartbeat { heartbeat, signature }
+}
+
+fn send_heartbeat(producer: u64) {
+	let key = heartbeat_key(producer);
+	let heartbeat =
+		HeartbeatPayload { block_number: System::block_number(), public: UintAuthorityId(key) };
+	let signature = TestSignature(key, heartbeat.encode());
+	assert_ok!(EOSIOSystem::heartbeat(RuntimeOrigin::none(), heartbeat, signature));
+}
+
+/// Producers' inflation accrued over `elapsed` blocks, split into (per-block, per-vote) pay.
+fn producers_inflation(elapsed: u64) -> (Balance, Balance) {
+	let to_producers = FixedU128::saturating_from_rational(elapsed, BlocksPerYear::get())
+		.saturating_mul_int(ProducersInflationRate::get() * Balances::total_issuance());
+	let to_per_block_pay = PerBlockRewardShare::get() * to_producers;
+	(to_per_block_pay, to_producers - to_per_block_pay)
+}
+
 // ===== getters =====
 
-// ===== setters =====
+#[test]
+fn get_default_storage() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(EOSIOSystem::stakes(ALICE), None);
+		assert_eq!(EOSIOSystem::voting(ALICE), None);
+		assert_eq!(EOSIOSystem::producer_table(ALICE), None);
+		assert!(EOSIOSystem::active_producers().is_empty());
+		assert!(EOSIOSystem::standby_producers().is_empty());
+		assert_eq!(EOSIOSystem::schedule_version(), 0);
+		assert_eq!(EOSIOSystem::total_producer_vote_weight(), 0);
+	});
+}
+
+#[test]
+fn vote_weight_doubles_every_year() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(EOSIOSystem::stake_to_vote_weight(MIN_STAKE), weight(MIN_STAKE));
+
+		System::set_block_number(52 * ONE_WEEK + 1);
+		assert_eq!(EOSIOSystem::stake_to_vote_weight(MIN_STAKE), weight(2 * MIN_STAKE));
+
+		System::set_block_number(104 * ONE_WEEK + 1);
+		assert_eq!(EOSIOSystem::stake_to_vote_weight(MIN_STAKE), weight(4 * MIN_STAKE));
+	});
+}
+
+#[test]
+fn vote_weight_of_large_stake_does_not_saturate() {
+	new_test_ext().execute_with(|| {
+		// beyond the integer part of a `FixedU128`
+		let staked = 1_000_000_000 * 1e21 as Balance;
+		System::set_block_number(520 * ONE_WEEK + 1);
+		assert_eq!(EOSIOSystem::stake_to_vote_weight(staked), weight(1_024 * staked));
+	});
+}
+
+// ===== stake_to_vote =====
+
+#[test]
+fn stake_to_vote_works() {
+	new_test_ext().execute_with(|| {
+		stake(ALICE, MIN_STAKE);
+		System::assert_last_event(Event::StakedToVote { voter: ALICE, amount: MIN_STAKE }.into());
+
+		assert_eq!(EOSIOSystem::stakes(ALICE).unwrap().staked, MIN_STAKE);
+		assert_eq!(locked(ALICE), MIN_STAKE);
+
+		// stake adds up
+		stake(ALICE, 1e10 as Balance);
+		assert_eq!(EOSIOSystem::stakes(ALICE).unwrap().staked, MIN_STAKE + 1e10 as Balance);
+		assert_eq!(locked(ALICE), MIN_STAKE + 1e10 as Balance);
+	});
+}
+
+#[test]
+fn stake_to_vote_fails_for_zero_amount() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			EOSIOSystem::stake_to_vote(RuntimeOrigin::signed(ALICE), 0),
+			Error::<Test>::ZeroStakeAmount
+		);
+	});
+}
+
+#[test]
+fn stake_to_vote_fails_below_min_stake() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			EOSIOSystem::stake_to_vote(RuntimeOrigin::signed(ALICE), MIN_STAKE - 1),
+			Error::<Test>::StakeAmountBelowMinimum
+		);
+	});
+}
+
+#[test]
+fn stake_to_vote_fails_for_insufficient_free_balance() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			EOSIOSystem::stake_to_vote(RuntimeOrigin::signed(ALICE), 10_001 * 1e10 as Balance),
+			Error::<Test>::InsufficientFreeBalance
+		);
+	});
+}
+
+#[test]
+fn stake_to_vote_fails_for_overflow() {
+	new_test_ext().execute_with(|| {
+		stake(ALICE, MIN_STAKE);
+		assert_noop!(
+			EOSIOSystem::stake_to_vote(RuntimeOrigin::signed(ALICE), Balance::MAX),
+			Error::<Test>::ArithmeticOverflow
+		);
+	});
+}
+
+#[test]
+fn stake_to_vote_updates_votes() {
+	new_test_ext().execute_with(|| {
+		register(ALICE);
+		stake(CHARLIE, MIN_STAKE);
+		vote(CHARLIE, vec![ALICE]);
+		assert_eq!(votes(ALICE), weight(MIN_STAKE));
+
+		stake(CHARLIE, MIN_STAKE);
+		assert_eq!(votes(ALICE), weight(2 * MIN_STAKE));
+		assert_eq!(EOSIOSystem::total_producer_vote_weight(), weight(2 * MIN_STAKE));
+	});
+}
+
+// ===== unstake_to_vote =====
+
+#[test]
+fn unstake_to_vote_works() {
+	new_test_ext().execute_with(|| {
+		stake(ALICE, 2 * MIN_STAKE);
+		assert_ok!(EOSIOSystem::unstake_to_vote(RuntimeOrigin::signed(ALICE), MIN_STAKE));
+		System::assert_last_event(
+			Event::UnstakedToVote {
+				voter: ALICE,
+				amount: MIN_STAKE,
+				unlock_at: 1 + UNBONDING_DURATION,
+			}
+			.into(),
+		);
+
+		let stake_info = EOSIOSystem::stakes(ALICE).unwrap();
+		assert_eq!(stake_info.staked, MIN_STAKE);
+		assert_eq!(
+			stake_info.unbonding.into_inner(),
+			vec![UnbondingChunk { amount: MIN_STAKE, unlock_at: 1 + UNBONDING_DURATION }]
+		);
+		// still locked while unbonding
+		assert_eq!(locked(ALICE), 2 * MIN_STAKE);
+	});
+}
+
+#[test]
+fn unstake_to_vote_merges_chunks_of_same_block() {
+	new_test_ext().execute_with(|| {
+		stake(ALICE, 3 * MIN_STAKE);
+		assert_ok!(EOSIOSystem::unstake_to_vote(RuntimeOrigin::signed(ALICE), MIN_STAKE));
+		assert_ok!(EOSIOSystem::unstake_to_vote(RuntimeOrigin::signed(ALICE), MIN_STAKE));
+
+		let stake_info = EOSIOSystem::stakes(ALICE).unwrap();
+		assert_eq!(
+			stake_info.unbonding.into_inner(),
+			vec![UnbondingChunk { amount: 2 * MIN_STAKE, unlock_at: 1 + UNBONDING_DURATION }]
+		);
+	});
+}
+
+#[test]
+fn unstake_to_vote_fails_for_zero_amount() {
+	new_test_ext().execute_with(|| {
+		stake(ALICE, MIN_STAKE);
+		assert_noop!(
+			EOSIOSystem::unstake_to_vote(RuntimeOrigin::signed(ALICE), 0),
+			Error::<Test>::ZeroUnstakeAmount
+		);
+	});
+}
+
+#[test]
+fn unstake_to_vote_fails_when_nothing_staked() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			EOSIOSystem::unstake_to_vote(RuntimeOrigin::signed(ALICE), MIN_STAKE),
+			Error::<Test>::NothingStaked
+		);
+	});
+}
+
+#[test]
+fn unstake_to_vote_fails_for_insufficient_stake() {
+	new_test_ext().execute_with(|| {
+		stake(ALICE, MIN_STAKE);
+		assert_noop!(
+			EOSIOSystem::unstake_to_vote(RuntimeOrigin::signed(ALICE), MIN_STAKE + 1),
+			Error::<Test>::InsufficientStakedAmount
+		);
+	});
+}
+
+#[test]
+fn unstake_to_vote_fails_for_remaining_stake_below_min() {
+	new_test_ext().execute_with(|| {
+		stake(ALICE, MIN_STAKE);
+		assert_noop!(
+			EOSIOSystem::unstake_to_vote(RuntimeOrigin::signed(ALICE), 1),
+			Error::<Test>::StakeAmountBelowMinimum
+		);
+	});
+}
+
+#[test]
+fn unstake_to_vote_fails_for_too_many_unbonding_chunks() {
+	new_test_ext().execute_with(|| {
+		stake(ALICE, 40 * MIN_STAKE);
+		for n in 1..=MaxUnbondingChunks::get() as u64 {
+			System::set_block_number(n);
+			assert_ok!(EOSIOSystem::unstake_to_vote(RuntimeOrigin::signed(ALICE), 1e10 as Balance));
+		}
+
+		System::set_block_number(MaxUnbondingChunks::get() as u64 + 1);
+		assert_noop!(
+			EOSIOSystem::unstake_to_vote(RuntimeOrigin::signed(ALICE), 1e10 as Balance),
+			Error::<Test>::TooManyUnbondingChunks
+		);
+	});
+}
+
+#[test]
+fn unstake_to_vote_updates_votes() {
+	new_test_ext().execute_with(|| {
+		register(ALICE);
+		stake(CHARLIE, 2 * MIN_STAKE);
+		vote(CHARLIE, vec![ALICE]);
+
+		assert_ok!(EOSIOSystem::unstake_to_vote(RuntimeOrigin::signed(CHARLIE), MIN_STAKE));
+		assert_eq!(votes(ALICE), weight(MIN_STAKE));
+	});
+}
+
+// ===== refund =====
+
+#[test]
+fn refund_works() {
+	new_test_ext().execute_with(|| {
+		stake(ALICE, 2 * MIN_STAKE);
+		assert_ok!(EOSIOSystem::unstake_to_vote(RuntimeOrigin::signed(ALICE), MIN_STAKE));
+
+		System::set_block_number(1 + UNBONDING_DURATION);
+		assert_ok!(EOSIOSystem::refund(RuntimeOrigin::signed(ALICE)));
+		System::assert_last_event(Event::Refunded { voter: ALICE, amount: MIN_STAKE }.into());
+
+		let stake_info = EOSIOSystem::stakes(ALICE).unwrap();
+		assert_eq!(stake_info.staked, MIN_STAKE);
+		assert!(stake_info.unbonding.is_empty());
+		assert_eq!(locked(ALICE), MIN_STAKE);
+	});
+}
+
+#[test]
+fn refund_removes_lock_when_fully_unstaked() {
+	new_test_ext().execute_with(|| {
+		stake(ALICE, MIN_STAKE);
+		assert_ok!(EOSIOSystem::unstake_to_vote(RuntimeOrigin::signed(ALICE), MIN_STAKE));
+
+		System::set_block_number(1 + UNBONDING_DURATION);
+		assert_ok!(EOSIOSystem::refund(RuntimeOrigin::signed(ALICE)));
+
+		assert_eq!(EOSIOSystem::stakes(ALICE), None);
+		assert_eq!(locked(ALICE), 0);
+	});
+}
+
+#[test]
+fn refund_fails_when_nothing_staked() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			EOSIOSystem::refund(RuntimeOrigin::signed(ALICE)),
+			Error::<Test>::NothingStaked
+		);
+	});
+}
+
+#[test]
+fn refund_fails_before_unbonding_ends() {
+	new_test_ext().execute_with(|| {
+		stake(ALICE, 2 * MIN_STAKE);
+		assert_ok!(EOSIOSystem::unstake_to_vote(RuntimeOrigin::signed(ALICE), MIN_STAKE));
+
+		System::set_block_number(UNBONDING_DURATION);
+		assert_noop!(
+			EOSIOSystem::refund(RuntimeOrigin::signed(ALICE)),
+			Error::<Test>::NoMaturedUnbonding
+		);
+	});
+}
+
+// ===== register_producer =====
+
+#[test]
+fn register_producer_works() {
+	new_test_ext().execute_with(|| {
+		register(ALICE);
+		System::assert_has_event(Event::ProducerRegistered { producer: ALICE, bond: BOND }.into());
+
+		let producer_info = EOSIOSystem::producer_table(ALICE).unwrap();
+		assert!(producer_info.is_active);
+		assert_eq!(producer_info.url.into_inner(), URL.to_vec());
+		assert_eq!(producer_info.location, LOCATION);
+		assert_eq!(producer_info.last_heartbeat, 1);
+		assert_eq!(EOSIOSystem::active(ALICE), Some(true));
+		assert_eq!(Balances::reserved_balance(ALICE), BOND);
+		assert_eq!(EOSIOSystem::candidates().into_inner(), vec![ALICE]);
+	});
+}
+
+#[test]
+fn register_producer_fails_when_already_registered() {
+	new_test_ext().execute_with(|| {
+		register(ALICE);
+		assert_noop!(
+			EOSIOSystem::register_producer(RuntimeOrigin::signed(ALICE), URL.to_vec(), LOCATION),
+			Error::<Test>::ProducerAlreadyRegistered
+		);
+	});
+}
+
+#[test]
+fn register_producer_fails_for_invalid_url() {
+	new_test_ext().execute_with(|| {
+		// not UTF-8
+		assert_noop!(
+			EOSIOSystem::register_producer(
+				RuntimeOrigin::signed(ALICE),
+				vec![0xff, 0xfe],
+				LOCATION
+			),
+			Error::<Test>::InvalidProducerInfoUrl
+		);
+		// too long
+		assert_noop!(
+			EOSIOSystem::register_producer(
+				RuntimeOrigin::signed(ALICE),
+				vec![b'a'; MaxProducerInfoUrlLen::get() as usize + 1],
+				LOCATION
+			),
+			Error::<Test>::InvalidProducerInfoUrl
+		);
+	});
+}
+
+#[test]
+fn register_producer_fails_for_invalid_location() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			EOSIOSystem::register_producer(RuntimeOrigin::signed(ALICE), URL.to_vec(), 1_000),
+			Error::<Test>::InvalidLocationCode
+		);
+	});
+}
+
+#[test]
+fn register_producer_again_keeps_votes() {
+	new_test_ext().execute_with(|| {
+		register(ALICE);
+		stake(CHARLIE, MIN_STAKE);
+		vote(CHARLIE, vec![ALICE]);
+		assert_ok!(EOSIOSystem::unregister_producer(RuntimeOrigin::signed(ALICE)));
+
+		System::set_block_number(5);
+		register(ALICE);
+
+		let producer_info = EOSIOSystem::producer_table(ALICE).unwrap();
+		assert!(producer_info.is_active);
+		assert_eq!(producer_info.total_votes, weight(MIN_STAKE));
+		assert_eq!(producer_info.last_heartbeat, 5);
+	});
+}
+
+#[test]
+fn register_producer_fails_for_too_many_producers() {
+	new_test_ext().execute_with(|| {
+		MaxProducers::set(1);
+		register(ALICE);
+		assert_noop!(
+			EOSIOSystem::register_producer(RuntimeOrigin::signed(BOB), URL.to_vec(), LOCATION),
+			Error::<Test>::TooManyProducers
+		);
+
+		// the slot is freed on unregistering
+		assert_ok!(EOSIOSystem::unregister_producer(RuntimeOrigin::signed(ALICE)));
+		register(BOB);
+		assert_eq!(EOSIOSystem::candidates().into_inner(), vec![BOB]);
+	});
+}
+
+// ===== update_producer =====
+
+#[test]
+fn update_producer_works() {
+	new_test_ext().execute_with(|| {
+		register(ALICE);
+		assert_ok!(EOSIOSystem::update_producer(
+			RuntimeOrigin::signed(ALICE),
+			b"https://eos.io".to_vec(),
+			840
+		));
+		System::assert_last_event(Event::ProducerUpdated { producer: ALICE }.into());
+
+		let producer_info = EOSIOSystem::producer_table(ALICE).unwrap();
+		assert_eq!(producer_info.url.into_inner(), b"https://eos.io".to_vec());
+		assert_eq!(producer_info.location, 840);
+	});
+}
+
+#[test]
+fn update_producer_fails_for_non_existent_producer() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			EOSIOSystem::update_producer(RuntimeOrigin::signed(ALICE), URL.to_vec(), LOCATION),
+			Error::<Test>::NonExistentProducer
+		);
+	});
+}
+
+#[test]
+fn update_producer_fails_for_inactive_producer() {
+	new_test_ext().execute_with(|| {
+		register(ALICE);
+		assert_ok!(EOSIOSystem::unregister_producer(RuntimeOrigin::signed(ALICE)));
+		assert_noop!(
+			EOSIOSystem::update_producer(RuntimeOrigin::signed(ALICE), URL.to_vec(), LOCATION),
+			Error::<Test>::ProducerNotActive
+		);
+	});
+}
+
+#[test]
+fn update_producer_fails_for_invalid_location() {
+	new_test_ext().execute_with(|| {
+		register(ALICE);
+		assert_noop!(
+			EOSIOSystem::update_producer(RuntimeOrigin::signed(ALICE), URL.to_vec(), 0),
+			Error::<Test>::InvalidLocationCode
+		);
+	});
+}
+
+// ===== unregister_producer =====
+
+#[test]
+fn unregister_producer_works() {
+	new_test_ext().execute_with(|| {
+		register(ALICE);
+		assert_ok!(EOSIOSystem::unregister_producer(RuntimeOrigin::signed(ALICE)));
+		System::assert_last_event(Event::ProducerUnregistered { producer: ALICE }.into());
+
+		assert_eq!(EOSIOSystem::active(ALICE), Some(false));
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert!(EOSIOSystem::candidates().is_empty());
+	});
+}
+
+#[test]
+fn unregister_producer_releases_the_reserved_bond() {
+	new_test_ext().execute_with(|| {
+		register(ALICE);
+		assert_eq!(EOSIOSystem::producer_bond(ALICE), BOND);
+		let free = Balances::free_balance(ALICE);
+
+		// the bond is changed after the registration
+		ProducerBond::set(2 * BOND);
+		assert_ok!(EOSIOSystem::unregister_producer(RuntimeOrigin::signed(ALICE)));
+
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_eq!(Balances::free_balance(ALICE), free + BOND);
+		assert_eq!(EOSIOSystem::producer_bond(ALICE), 0);
+	});
+}
+
+#[test]
+fn unregister_producer_fails_for_non_existent_producer() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			EOSIOSystem::unregister_producer(RuntimeOrigin::signed(ALICE)),
+			Error::<Test>::NonExistentProducer
+		);
+	});
+}
+
+#[test]
+fn unregister_producer_fails_for_inactive_producer() {
+	new_test_ext().execute_with(|| {
+		register(ALICE);
+		assert_ok!(EOSIOSystem::unregister_producer(RuntimeOrigin::signed(ALICE)));
+		assert_noop!(
+			EOSIOSystem::unregister_producer(RuntimeOrigin::signed(ALICE)),
+			Error::<Test>::ProducerNotActive
+		);
+	});
+}
+
+// ===== vote_producer =====
+
+#[test]
+fn vote_producer_works() {
+	new_test_ext().execute_with(|| {
+		register(ALICE);
+		register(BOB);
+		stake(CHARLIE, MIN_STAKE);
+
+		vote(CHARLIE, vec![ALICE, BOB]);
+		System::assert_last_event(
+			Event::Voted { voter: CHARLIE, producers: vec![ALICE, BOB].try_into().unwrap() }.into(),
+		);
+
+		assert_eq!(votes(ALICE), weight(MIN_STAKE));
+		assert_eq!(votes(BOB), weight(MIN_STAKE));
+		assert_eq!(EOSIOSystem::total_producer_vote_weight(), weight(2 * MIN_STAKE));
+		assert_eq!(EOSIOSystem::voting(CHARLIE).unwrap().producers.into_inner(), vec![ALICE, BOB]);
+	});
+}
+
+#[test]
+fn vote_producer_replaces_previous_votes() {
+	new_test_ext().execute_with(|| {
+		register(ALICE);
+		register(BOB);
+		stake(CHARLIE, MIN_STAKE);
+		vote(CHARLIE, vec![ALICE, BOB]);
+
+		vote(CHARLIE, vec![BOB]);
+		assert_eq!(votes(ALICE), weight(0));
+		assert_eq!(votes(BOB), weight(MIN_STAKE));
+
+		// an empty list removes the votes
+		vote(CHARLIE, vec![]);
+		assert_eq!(votes(BOB), weight(0));
+		assert_eq!(EOSIOSystem::voting(CHARLIE), None);
+		assert_eq!(EOSIOSystem::total_producer_vote_weight(), weight(0));
+	});
+}
+
+#[test]
+fn vote_producer_refunds_unused_votes() {
+	new_test_ext().execute_with(|| {
+		register(ALICE);
+		register(BOB);
+		stake(CHARLIE, MIN_STAKE);
+		stake(EVE, MIN_STAKE);
+		let vote_weight = |votes| <() as crate::WeightInfo>::vote_producer(votes);
+
+		let post_info =
+			EOSIOSystem::vote_producer(RuntimeOrigin::signed(CHARLIE), vec![ALICE, BOB]).unwrap();
+		assert_eq!(post_info.actual_weight, Some(vote_weight(2)));
+		assert!(vote_weight(2).all_lt(vote_weight(MaxVotesPerAccount::get())));
+
+		// the previous votes are removed, even if fewer producers are voted
+		let post_info = EOSIOSystem::vote_producer(RuntimeOrigin::signed(CHARLIE), vec![]).unwrap();
+		assert_eq!(post_info.actual_weight, Some(vote_weight(2)));
+
+		// as are the votes of the proxy voted for
+		assert_ok!(EOSIOSystem::register_proxy(RuntimeOrigin::signed(DAVE)));
+		stake(DAVE, MIN_STAKE);
+		vote(DAVE, vec![ALICE, BOB]);
+		assert_ok!(EOSIOSystem::vote_proxy(RuntimeOrigin::signed(EVE), DAVE));
+		let post_info =
+			EOSIOSystem::vote_producer(RuntimeOrigin::signed(EVE), vec![ALICE]).unwrap();
+		assert_eq!(post_info.actual_weight, Some(vote_weight(2)));
+	});
+}
+
+#[test]
+fn vote_producer_fails_for_too_many_votes() {
+	new_test_ext().execute_with(|| {
+		stake(CHARLIE, MIN_STAKE);
+		let producers: Vec<u64> = (10..11 + MaxVotesPerAccount::get() as u64).collect();
+		assert_noop!(
+			EOSIOSystem::vote_producer(RuntimeOrigin::signed(CHARLIE), producers),
+			Error::<Test>::TooManyVotes
+		);
+	});
+}
+
+#[test]
+fn vote_producer_fails_for_duplicate_producer() {
+	new_test_ext().execute_with(|| {
+		register(ALICE);
+		stake(CHARLIE, MIN_STAKE);
+		assert_noop!(
+			EOSIOSystem::vote_producer(RuntimeOrigin::signed(CHARLIE), vec![ALICE, ALICE]),
+			Error::<Test>::DuplicateProducer
+		);
+	});
+}
+
+#[test]
+fn vote_producer_fails_without_stake() {
+	new_test_ext().execute_with(|| {
+		register(ALICE);
+		assert_noop!(
+			EOSIOSystem::vote_producer(RuntimeOrigin::signed(CHARLIE), vec![ALICE]),
+			Error::<Test>::NothingStaked
+		);
+	});
+}
+
+#[test]
+fn vote_producer_fails_for_non_existent_producer() {
+	new_test_ext().execute_with(|| {
+		stake(CHARLIE, MIN_STAKE);
+		assert_noop!(
+			EOSIOSystem::vote_producer(RuntimeOrigin::signed(CHARLIE), vec![ALICE]),
+			Error::<Test>::NonExistentProducer
+		);
+	});
+}
+
+#[test]
+fn vote_producer_fails_for_inactive_producer() {
+	new_test_ext().execute_with(|| {
+		register(ALICE);
+		assert_ok!(EOSIOSystem::unregister_producer(RuntimeOrigin::signed(ALICE)));
+		stake(CHARLIE, MIN_STAKE);
+		assert_noop!(
+			EOSIOSystem::vote_producer(RuntimeOrigin::signed(CHARLIE), vec![ALICE]),
+			Error::<Test>::ProducerNotActive
+		);
+	});
+}
+
+// ===== proxies =====
+
+#[test]
+fn register_proxy_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(EOSIOSystem::register_proxy(RuntimeOrigin::signed(CHARLIE)));
+		System::assert_last_event(Event::ProxyRegistered { proxy: CHARLIE }.into());
+		assert!(EOSIOSystem::voting(CHARLIE).unwrap().is_proxy);
+	});
+}
+
+#[test]
+fn register_proxy_fails_when_already_proxy() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(EOSIOSystem::register_proxy(RuntimeOrigin::signed(CHARLIE)));
+		assert_noop!(
+			EOSIOSystem::register_proxy(RuntimeOrigin::signed(CHARLIE)),
+			Error::<Test>::AlreadyProxy
+		);
+	});
+}
+
+#[test]
+fn register_proxy_fails_when_delegating() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(EOSIOSystem::register_proxy(RuntimeOrigin::signed(CHARLIE)));
+		stake(DAVE, MIN_STAKE);
+		assert_ok!(EOSIOSystem::vote_proxy(RuntimeOrigin::signed(DAVE), CHARLIE));
+		assert_noop!(
+			EOSIOSystem::register_proxy(RuntimeOrigin::signed(DAVE)),
+			Error::<Test>::ProxyCannotDelegate
+		);
+	});
+}
+
+#[test]
+fn unregister_proxy_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(EOSIOSystem::register_proxy(RuntimeOrigin::signed(CHARLIE)));
+		assert_ok!(EOSIOSystem::unregister_proxy(RuntimeOrigin::signed(CHARLIE)));
+		System::assert_last_event(Event::ProxyUnregistered { proxy: CHARLIE }.into());
+		assert_eq!(EOSIOSystem::voting(CHARLIE), None);
+	});
+}
+
+#[test]
+fn unregister_proxy_fails_for_non_proxy() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			EOSIOSystem::unregister_proxy(RuntimeOrigin::signed(CHARLIE)),
+			Error::<Test>::NotAProxy
+		);
+
+		// a voter is not a proxy either
+		register(ALICE);
+		stake(CHARLIE, MIN_STAKE);
+		vote(CHARLIE, vec![ALICE]);
+		assert_noop!(
+			EOSIOSystem::unregister_proxy(RuntimeOrigin::signed(CHARLIE)),
+			Error::<Test>::NotAProxy
+		);
+	});
+}
+
+#[test]
+fn vote_proxy_works() {
+	new_test_ext().execute_with(|| {
+		register(ALICE);
+		assert_ok!(EOSIOSystem::register_proxy(RuntimeOrigin::signed(CHARLIE)));
+		stake(CHARLIE, MIN_STAKE);
+		vote(CHARLIE, vec![ALICE]);
+
+		stake(DAVE, 2 * MIN_STAKE);
+		assert_ok!(EOSIOSystem::vote_proxy(RuntimeOrigin::signed(DAVE), CHARLIE));
+		System::assert_last_event(Event::VotedProxy { voter: DAVE, proxy: CHARLIE }.into());
+
+		// the proxy votes with its own stake plus the delegated one
+		assert_eq!(votes(ALICE), weight(3 * MIN_STAKE));
+		assert_eq!(
+			EOSIOSystem::voting(CHARLIE).unwrap().proxied_vote_weight,
+			weight(2 * MIN_STAKE)
+		);
+		assert_eq!(EOSIOSystem::voting(DAVE).unwrap().delegate_to, Some(CHARLIE));
+	});
+}
+
+#[test]
+fn vote_proxy_propagates_stake_changes() {
+	new_test_ext().execute_with(|| {
+		register(ALICE);
+		assert_ok!(EOSIOSystem::register_proxy(RuntimeOrigin::signed(CHARLIE)));
+		stake(CHARLIE, MIN_STAKE);
+		vote(CHARLIE, vec![ALICE]);
+		stake(DAVE, 2 * MIN_STAKE);
+		assert_ok!(EOSIOSystem::vote_proxy(RuntimeOrigin::signed(DAVE), CHARLIE));
+
+		// the delegator stakes more
+		stake(DAVE, MIN_STAKE);
+		assert_eq!(votes(ALICE), weight(4 * MIN_STAKE));
+
+		// the proxy unregisters, so the delegated vote weight stops counting
+		assert_ok!(EOSIOSystem::unregister_proxy(RuntimeOrigin::signed(CHARLIE)));
+		assert_eq!(votes(ALICE), weight(MIN_STAKE));
+
+		// the delegator votes for producers directly
+		vote(DAVE, vec![ALICE]);
+		assert_eq!(votes(ALICE), weight(4 * MIN_STAKE));
+		assert_eq!(EOSIOSystem::voting(CHARLIE).unwrap().proxied_vote_weight, weight(0));
+	});
+}
+
+#[test]
+fn vote_proxy_fails_for_self() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			EOSIOSystem::vote_proxy(RuntimeOrigin::signed(DAVE), DAVE),
+			Error::<Test>::CannotProxyToSelf
+		);
+	});
+}
+
+#[test]
+fn vote_proxy_fails_without_stake() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(EOSIOSystem::register_proxy(RuntimeOrigin::signed(CHARLIE)));
+		assert_noop!(
+			EOSIOSystem::vote_proxy(RuntimeOrigin::signed(DAVE), CHARLIE),
+			Error::<Test>::NothingStaked
+		);
+	});
+}
+
+#[test]
+fn vote_proxy_fails_for_proxy_voter() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(EOSIOSystem::register_proxy(RuntimeOrigin::signed(CHARLIE)));
+		assert_ok!(EOSIOSystem::register_proxy(RuntimeOrigin::signed(DAVE)));
+		stake(DAVE, MIN_STAKE);
+		assert_noop!(
+			EOSIOSystem::vote_proxy(RuntimeOrigin::signed(DAVE), CHARLIE),
+			Error::<Test>::ProxyCannotDelegate
+		);
+	});
+}
+
+#[test]
+fn vote_proxy_fails_for_non_proxy() {
+	new_test_ext().execute_with(|| {
+		stake(DAVE, MIN_STAKE);
+		assert_noop!(
+			EOSIOSystem::vote_proxy(RuntimeOrigin::signed(DAVE), CHARLIE),
+			Error::<Test>::NotAProxy
+		);
+	});
+}
+
+// ===== heartbeat =====
+
+#[test]
+fn heartbeat_works() {
+	new_test_ext().execute_with(|| {
+		register(ALICE);
+
+		System::set_block_number(5);
+		send_heartbeat(ALICE);
+		System::assert_last_event(
+			Event::HeartbeatReceived { producer: ALICE, block_number: 5 }.into(),
+		);
+		assert_eq!(EOSIOSystem::producer_table(ALICE).unwrap().last_heartbeat, 5);
+	});
+}
+
+#[test]
+fn heartbeat_fails_for_signed_origin() {
+	new_test_ext().execute_with(|| {
+		register(ALICE);
+		let key = heartbeat_key(ALICE);
+		let heartbeat = HeartbeatPayload { block_number: 1, public: UintAuthorityId(key) };
+		let signature = TestSignature(key, heartbeat.encode());
+		assert_noop!(
+			EOSIOSystem::heartbeat(RuntimeOrigin::signed(ALICE), heartbeat, signature),
+			BadOrigin
+		);
+	});
+}
+
+#[test]
+fn heartbeat_fails_for_unknown_key() {
+	new_test_ext().execute_with(|| {
+		// the producer's own account isn't its heartbeat key
+		register(ALICE);
+		let heartbeat = HeartbeatPayload { block_number: 1, public: UintAuthorityId(ALICE) };
+		let signature = TestSignature(ALICE, heartbeat.encode());
+		assert_noop!(
+			EOSIOSystem::heartbeat(RuntimeOrigin::none(), heartbeat, signature),
+			Error::<Test>::UnknownHeartbeatKey
+		);
+	});
+}
+
+#[test]
+fn heartbeat_fails_for_inactive_producer() {
+	new_test_ext().execute_with(|| {
+		register(ALICE);
+		assert_ok!(EOSIOSystem::unregister_producer(RuntimeOrigin::signed(ALICE)));
+		let key = heartbeat_key(ALICE);
+		let heartbeat = HeartbeatPayload { block_number: 1, public: UintAuthorityId(key) };
+		let signature = TestSignature(key, heartbeat.encode());
+		assert_noop!(
+			EOSIOSystem::heartbeat(RuntimeOrigin::none(), heartbeat, signature),
+			Error::<Test>::ProducerNotActive
+		);
+	});
+}
+
+#[test]
+fn validate_unsigned_accepts_valid_heartbeat() {
+	new_test_ext().execute_with(|| {
+		register(ALICE);
+		System::set_block_number(5);
+
+		let key = heartbeat_key(ALICE);
+		let validity = EOSIOSystem::validate_unsigned(
+			TransactionSource::External,
+			&heartbeat_call(key, 5, key),
+		)
+		.unwrap();
+		assert_eq!(validity.priority, UnsignedPriority::get());
+		assert_eq!(validity.longevity, HeartbeatDuration::get() as u64);
+		// tagged by the producer & the block
+		assert_eq!(validity.provides, vec![("pallet-eosio-system", (ALICE, 5u64)).encode()]);
+	});
+}
+
+#[test]
+fn validate_unsigned_rejects_invalid_heartbeats() {
+	new_test_ext().execute_with(|| {
+		register(ALICE);
+		let key = heartbeat_key(ALICE);
+
+		// signed by someone else
+		assert_eq!(
+			EOSIOSystem::validate_unsigned(
+				TransactionSource::External,
+				&heartbeat_call(key, 1, BOB)
+			),
+			InvalidTransaction::BadProof.into()
+		);
+		// not a heartbeat key (but the producer's own account)
+		assert_eq!(
+			EOSIOSystem::validate_unsigned(
+				TransactionSource::External,
+				&heartbeat_call(ALICE, 1, ALICE)
+			),
+			InvalidTransaction::BadSigner.into()
+		);
+		// the key of an unregistered producer
+		assert_ok!(EOSIOSystem::register_producer(
+			RuntimeOrigin::signed(BOB),
+			URL.to_vec(),
+			LOCATION
+		));
+		assert_ok!(EOSIOSystem::set_heartbeat_key(RuntimeOrigin::signed(BOB), heartbeat_key(BOB)));
+		assert_ok!(EOSIOSystem::unregister_producer(RuntimeOrigin::signed(BOB)));
+		assert_eq!(
+			EOSIOSystem::validate_unsigned(
+				TransactionSource::External,
+				&heartbeat_call(heartbeat_key(BOB), 1, heartbeat_key(BOB))
+			),
+			InvalidTransaction::BadSigner.into()
+		);
+		// from the future
+		assert_eq!(
+			EOSIOSystem::validate_unsigned(
+				TransactionSource::External,
+				&heartbeat_call(key, 2, key)
+			),
+			InvalidTransaction::Future.into()
+		);
+		// not newer than the last heartbeat (i.e. the registration)
+		assert_eq!(
+			EOSIOSystem::validate_unsigned(
+				TransactionSource::External,
+				&heartbeat_call(key, 1, key)
+			),
+			InvalidTransaction::Stale.into()
+		);
+	});
+}
+
+// ===== set_heartbeat_key =====
+
+#[test]
+fn set_heartbeat_key_works() {
+	new_test_ext().execute_with(|| {
+		register(ALICE);
+		System::assert_last_event(
+			Event::HeartbeatKeySet { producer: ALICE, key: heartbeat_key(ALICE) }.into(),
+		);
+		assert_eq!(EOSIOSystem::heartbeat_keys(heartbeat_key(ALICE)), Some(ALICE));
+		assert_eq!(EOSIOSystem::heartbeat_key_of(ALICE), Some(heartbeat_key(ALICE)));
+
+		// the previous key is released
+		assert_ok!(EOSIOSystem::set_heartbeat_key(RuntimeOrigin::signed(ALICE), EVE));
+		assert_eq!(EOSIOSystem::heartbeat_keys(EVE), Some(ALICE));
+		assert_eq!(EOSIOSystem::heartbeat_keys(heartbeat_key(ALICE)), None);
+		assert_eq!(EOSIOSystem::heartbeat_key_of(ALICE), Some(EVE));
+	});
+}
+
+#[test]
+fn set_heartbeat_key_fails_for_non_existent_producer() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			EOSIOSystem::set_heartbeat_key(RuntimeOrigin::signed(ALICE), heartbeat_key(ALICE)),
+			Error::<Test>::NonExistentProducer
+		);
+	});
+}
+
+#[test]
+fn set_heartbeat_key_fails_for_key_in_use() {
+	new_test_ext().execute_with(|| {
+		register(ALICE);
+		register(BOB);
+		assert_noop!(
+			EOSIOSystem::set_heartbeat_key(RuntimeOrigin::signed(BOB), heartbeat_key(ALICE)),
+			Error::<Test>::HeartbeatKeyInUse
+		);
+	});
+}
+
+// ===== ranking =====
+
+#[test]
+fn update_elected_producers_ranks_by_votes() {
+	new_test_ext().execute_with(|| {
+		register(ALICE);
+		register(BOB);
+		register(CHARLIE);
+		stake(DAVE, 2 * MIN_STAKE);
+		vote(DAVE, vec![ALICE, BOB]);
+		stake(EVE, MIN_STAKE);
+		vote(EVE, vec![BOB]);
+
+		EOSIOSystem::update_elected_producers();
+
+		// CHARLIE has no votes, so isn't elected
+		let active: BoundedVec<u64, ActiveValidatorsCount> = vec![BOB, ALICE].try_into().unwrap();
+		assert_eq!(EOSIOSystem::active_producers(), active);
+		assert!(EOSIOSystem::standby_producers().is_empty());
+		assert_eq!(EOSIOSystem::schedule_version(), 1);
+		System::assert_last_event(
+			Event::NewProducerSchedule { version: 1, active, standby: BoundedVec::default() }
+				.into(),
+		);
+
+		// the same schedule keeps the version
+		EOSIOSystem::update_elected_producers();
+		assert_eq!(EOSIOSystem::schedule_version(), 1);
+	});
+}
+
+#[test]
+fn update_elected_producers_keeps_schedule_without_votes() {
+	new_test_ext().execute_with(|| {
+		register(ALICE);
+		EOSIOSystem::update_elected_producers();
+		assert!(EOSIOSystem::active_producers().is_empty());
+		assert_eq!(EOSIOSystem::schedule_version(), 0);
+	});
+}
+
+#[test]
+fn update_elected_producers_drops_unregistered_producers_without_votes() {
+	new_test_ext().execute_with(|| {
+		register(ALICE);
+		register(BOB);
+		register(CHARLIE);
+		stake(DAVE, MIN_STAKE);
+		vote(DAVE, vec![ALICE, BOB, CHARLIE]);
+		EOSIOSystem::update_elected_producers();
+		assert_eq!(EOSIOSystem::active_producers().into_inner(), vec![ALICE, BOB, CHARLIE]);
+
+		// none of the candidates has votes & BOB is no longer registered
+		assert_ok!(EOSIOSystem::unregister_producer(RuntimeOrigin::signed(BOB)));
+		vote(DAVE, vec![]);
+		EOSIOSystem::update_elected_producers();
+
+		let active: BoundedVec<u64, ActiveValidatorsCount> =
+			vec![ALICE, CHARLIE].try_into().unwrap();
+		assert_eq!(EOSIOSystem::active_producers(), active);
+		assert_eq!(EOSIOSystem::schedule_version(), 2);
+		System::assert_last_event(
+			Event::NewProducerSchedule { version: 2, active, standby: BoundedVec::default() }
+				.into(),
+		);
+	});
+}
+
+#[test]
+fn update_elected_producers_keeps_schedule_if_all_producers_offline() {
+	new_test_ext().execute_with(|| {
+		register(ALICE);
+		register(BOB);
+		stake(DAVE, MIN_STAKE);
+		vote(DAVE, vec![ALICE, BOB]);
+		EOSIOSystem::update_elected_producers();
+
+		// both miss their heartbeats, so none of the schedule would be left
+		System::set_block_number(ONE_DAY);
+		EOSIOSystem::update_elected_producers();
+
+		assert!(EOSIOSystem::candidates().is_empty());
+		assert_eq!(EOSIOSystem::active_producers().into_inner(), vec![ALICE, BOB]);
+		assert_eq!(EOSIOSystem::schedule_version(), 1);
+	});
+}
+
+#[test]
+fn ranking_deactivates_producers_missing_heartbeats() {
+	new_test_ext().execute_with(|| {
+		register(ALICE);
+		register(BOB);
+		stake(DAVE, MIN_STAKE);
+		vote(DAVE, vec![ALICE, BOB]);
+
+		// only ALICE stays alive
+		System::set_block_number(ONE_DAY - 5);
+		send_heartbeat(ALICE);
+
+		System::set_block_number(ONE_DAY);
+		EOSIOSystem::on_initialize(ONE_DAY);
+
+		System::assert_has_event(
+			Event::ProducerDeactivated { producer: BOB, last_heartbeat: 1 }.into(),
+		);
+		assert_eq!(EOSIOSystem::active(BOB), Some(false));
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(EOSIOSystem::active(ALICE), Some(true));
+		assert_eq!(EOSIOSystem::active_producers().into_inner(), vec![ALICE]);
+		assert_eq!(EOSIOSystem::candidates().into_inner(), vec![ALICE]);
+	});
+}
+
+#[test]
+fn new_session_returns_active_producers() {
+	new_test_ext().execute_with(|| {
+		// no producers elected yet
+		assert_eq!(EOSIOSystem::new_session(1), None);
+
+		register(ALICE);
+		stake(DAVE, MIN_STAKE);
+		vote(DAVE, vec![ALICE]);
+		EOSIOSystem::update_elected_producers();
+
+		assert_eq!(EOSIOSystem::new_session(2), Some(vec![ALICE]));
+	});
+}
+
+#[test]
+fn new_session_skips_producers_without_session_keys() {
+	new_test_ext().execute_with(|| {
+		register(ALICE);
+		register(BOB);
+		stake(DAVE, MIN_STAKE);
+		vote(DAVE, vec![ALICE, BOB]);
+		EOSIOSystem::update_elected_producers();
+
+		WithoutSessionKeys::set(vec![BOB]);
+		assert_eq!(EOSIOSystem::new_session(2), Some(vec![ALICE]));
+
+		// none of the elected producers can author blocks
+		WithoutSessionKeys::set(vec![ALICE, BOB]);
+		assert_eq!(EOSIOSystem::new_session(3), None);
+	});
+}
+
+// ===== rewards =====
+
+#[test]
+fn block_author_is_credited_with_unpaid_block() {
+	new_test_ext().execute_with(|| {
+		register(ALICE);
+
+		Author::set(Some(ALICE));
+		EOSIOSystem::on_initialize(2);
+		EOSIOSystem::on_initialize(3);
+		// not a producer
+		Author::set(Some(DAVE));
+		EOSIOSystem::on_initialize(4);
+
+		assert_eq!(EOSIOSystem::producer_table(ALICE).unwrap().unpaid_blocks, 2);
+		assert_eq!(EOSIOSystem::total_unpaid_blocks(), 2);
+	});
+}
+
+#[test]
+fn claim_rewards_works() {
+	new_test_ext().execute_with(|| {
+		register(ALICE);
+		register(BOB);
+		stake(DAVE, MIN_STAKE);
+		vote(DAVE, vec![ALICE]);
+
+		Author::set(Some(ALICE));
+		for n in 2..5 {
+			EOSIOSystem::on_initialize(n);
+		}
+		Author::set(Some(BOB));
+		EOSIOSystem::on_initialize(5);
+
+		let now = 10 * ONE_DAY;
+		System::set_block_number(now);
+		let (per_block_bucket, per_vote_bucket) = producers_inflation(now);
+		let balance = Balances::free_balance(ALICE);
+
+		assert_ok!(EOSIOSystem::claim_rewards(RuntimeOrigin::signed(ALICE)));
+
+		// 3 out of 4 unpaid blocks & all the votes
+		let per_block_pay =
+			FixedU128::saturating_from_rational(3, 4).saturating_mul_int(per_block_bucket);
+		let per_vote_pay = per_vote_bucket;
+		System::assert_last_event(
+			Event::RewardsClaimed { producer: ALICE, per_block_pay, per_vote_pay }.into(),
+		);
+		assert_eq!(Balances::free_balance(ALICE), balance + per_block_pay + per_vote_pay);
+
+		assert_eq!(EOSIOSystem::per_block_bucket(), per_block_bucket - per_block_pay);
+		assert_eq!(EOSIOSystem::per_vote_bucket(), 0);
+		assert_eq!(EOSIOSystem::total_unpaid_blocks(), 1);
+		let producer_info = EOSIOSystem::producer_table(ALICE).unwrap();
+		assert_eq!(producer_info.unpaid_blocks, 0);
+		assert_eq!(producer_info.last_claim_time, now);
+	});
+}
+
+#[test]
+fn claim_rewards_forfeits_per_vote_pay_below_min() {
+	new_test_ext().execute_with(|| {
+		register(ALICE);
+		stake(DAVE, MIN_STAKE);
+		vote(DAVE, vec![ALICE]);
+
+		// the per-vote bucket is below `MinPerVotePay` after a day
+		System::set_block_number(ONE_DAY);
+		let (per_block_bucket, per_vote_bucket) = producers_inflation(ONE_DAY);
+		assert!(per_vote_bucket < MinPerVotePay::get());
+
+		assert_ok!(EOSIOSystem::claim_rewards(RuntimeOrigin::signed(ALICE)));
+		System::assert_last_event(
+			Event::RewardsClaimed { producer: ALICE, per_block_pay: 0, per_vote_pay: 0 }.into(),
+		);
+		assert_eq!(EOSIOSystem::per_block_bucket(), per_block_bucket);
+		assert_eq!(EOSIOSystem::per_vote_bucket(), per_vote_bucket);
+	});
+}
+
+#[test]
+fn claim_rewards_fails_within_claim_interval() {
+	new_test_ext().execute_with(|| {
+		register(ALICE);
+		assert_noop!(
+			EOSIOSystem::claim_rewards(RuntimeOrigin::signed(ALICE)),
+			Error::<Test>::ClaimTooEarly
+		);
+
+		System::set_block_number(ONE_DAY);
+		assert_ok!(EOSIOSystem::claim_rewards(RuntimeOrigin::signed(ALICE)));
+
+		System::set_block_number(2 * ONE_DAY - 1);
+		assert_noop!(
+			EOSIOSystem::claim_rewards(RuntimeOrigin::signed(ALICE)),
+			Error::<Test>::ClaimTooEarly
+		);
+	});
+}
+
+#[test]
+fn claim_rewards_fails_for_non_existent_producer() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			EOSIOSystem::claim_rewards(RuntimeOrigin::signed(ALICE)),
+			Error::<Test>::NonExistentProducer
+		);
+	});
+}
+
+#[test]
+fn claim_rewards_fails_for_inactive_producer() {
+	new_test_ext().execute_with(|| {
+		register(ALICE);
+		assert_ok!(EOSIOSystem::unregister_producer(RuntimeOrigin::signed(ALICE)));
+		System::set_block_number(ONE_DAY);
+		assert_noop!(
+			EOSIOSystem::claim_rewards(RuntimeOrigin::signed(ALICE)),
+			Error::<Test>::ProducerNotActive
+		);
+	});
+}
+
+#[test]
+fn producers_inflation_is_split_per_block_and_per_vote() {
+	new_test_ext().execute_with(|| {
+		let (per_block, per_vote) = producers_inflation(BlocksPerYear::get() as u64);
+		let to_producers = Perbill::from_percent(1) * Balances::total_issuance();
+		assert_eq!(per_block + per_vote, to_producers);
+		assert_eq!(per_block, Perbill::from_percent(25) * to_producers);
+	});
+}
+
+// ===== runtime api =====
+
+#[test]
+fn get_producers_sorts_by_votes() {
+	new_test_ext().execute_with(|| {
+		register(ALICE);
+		register(BOB);
+		register(CHARLIE);
+		stake(DAVE, 2 * MIN_STAKE);
+		vote(DAVE, vec![ALICE, BOB]);
+		stake(EVE, MIN_STAKE);
+		vote(EVE, vec![BOB]);
+
+		let producers = EOSIOSystem::get_producers(10);
+		assert_eq!(
+			producers.iter().map(|p| (p.owner, p.total_votes)).collect::<Vec<_>>(),
+			vec![
+				(BOB, weight(3 * MIN_STAKE)),
+				(ALICE, weight(2 * MIN_STAKE)),
+				(CHARLIE, weight(0))
+			]
+		);
+		assert_eq!(producers[0].url, URL.to_vec());
+
+		// limited
+		assert_eq!(EOSIOSystem::get_producers(1).len(), 1);
+	});
+}
+
+#[test]
+fn get_voter_works() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(EOSIOSystem::get_voter(DAVE), None);
+
+		register(ALICE);
+		stake(DAVE, 2 * MIN_STAKE);
+		vote(DAVE, vec![ALICE]);
+		assert_ok!(EOSIOSystem::unstake_to_vote(RuntimeOrigin::signed(DAVE), MIN_STAKE));
+
+		let voter = EOSIOSystem::get_voter(DAVE).unwrap();
+		assert_eq!(voter.staked, MIN_STAKE);
+		assert_eq!(voter.unbonding, vec![(MIN_STAKE, 1 + UNBONDING_DURATION)]);
+		assert_eq!(voter.producers, vec![ALICE]);
+		assert_eq!(voter.proxy, None);
+		assert_eq!(voter.last_vote_weight, weight(MIN_STAKE));
+	});
+}
+
+#[test]
+fn get_producer_schedule_works() {
+	new_test_ext().execute_with(|| {
+		register(ALICE);
+		stake(DAVE, MIN_STAKE);
+		vote(DAVE, vec![ALICE]);
+		EOSIOSystem::update_elected_producers();
+
+		let schedule = EOSIOSystem::get_producer_schedule();
+		assert_eq!(schedule.version, 1);
+		assert_eq!(schedule.active, vec![ALICE]);
+		assert!(schedule.standby.is_empty());
+	});
+}
+
+#[test]
+fn get_unclaimed_rewards_matches_claim() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(EOSIOSystem::get_unclaimed_rewards(ALICE), None);
+
+		register(ALICE);
+		stake(DAVE, MIN_STAKE);
+		vote(DAVE, vec![ALICE]);
+		Author::set(Some(ALICE));
+		EOSIOSystem::on_initialize(2);
+
+		System::set_block_number(10 * ONE_DAY);
+		let estimate = EOSIOSystem::get_unclaimed_rewards(ALICE).unwrap();
+		assert_eq!(estimate.claimable_at, ONE_DAY);
+
+		assert_ok!(EOSIOSystem::claim_rewards(RuntimeOrigin::signed(ALICE)));
+		System::assert_last_event(
+			Event::RewardsClaimed {
+				producer: ALICE,
+				per_block_pay: estimate.per_block_pay,
+				per_vote_pay: estimate.per_vote_pay,
+			}
+			.into(),
+		);
+	});
+}
+
+// ===== migrations =====
+
+#[test]
+fn migration_to_v1_converts_fixed_point_vote_weights() {
+	use crate::migrations::v1::MigrateToV1;
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	new_test_ext().execute_with(|| {
+		register(ALICE);
+		assert_ok!(EOSIOSystem::register_proxy(RuntimeOrigin::signed(CHARLIE)));
+		stake(CHARLIE, MIN_STAKE);
+		vote(CHARLIE, vec![ALICE]);
+		stake(DAVE, MIN_STAKE);
+		assert_ok!(EOSIOSystem::vote_proxy(RuntimeOrigin::signed(DAVE), CHARLIE));
+
+		// the v0 vote weights i.e. the inner values of `FixedU128`
+		let to_fixed = |weight: VoteWeight| weight * FixedU128::DIV;
+		for voter in [CHARLIE, DAVE] {
+			crate::Voting::<Test>::mutate(voter, |info| {
+				let info = info.as_mut().unwrap();
+				info.last_vote_weight = to_fixed(info.last_vote_weight);
+				info.proxied_vote_weight = to_fixed(info.proxied_vote_weight);
+			});
+		}
+		crate::ProducerTable::<Test>::mutate(ALICE, |info| {
+			let info = info.as_mut().unwrap();
+			info.total_votes = to_fixed(info.total_votes);
+		});
+		crate::TotalProducerVoteWeight::<Test>::mutate(|total| *total = to_fixed(*total));
+		// the v0 candidates aren't tracked
+		crate::Candidates::<Test>::kill();
+		StorageVersion::new(0).put::<EOSIOSystem>();
+
+		MigrateToV1::<Test>::on_runtime_upgrade();
+		assert_eq!(EOSIOSystem::on_chain_storage_version(), 1);
+		assert_eq!(votes(ALICE), weight(2 * MIN_STAKE));
+		assert_eq!(EOSIOSystem::total_producer_vote_weight(), weight(2 * MIN_STAKE));
+		let voter = EOSIOSystem::voting(CHARLIE).unwrap();
+		assert_eq!(voter.last_vote_weight, weight(2 * MIN_STAKE));
+		assert_eq!(voter.proxied_vote_weight, weight(MIN_STAKE));
+		assert_eq!(EOSIOSystem::voting(DAVE).unwrap().last_vote_weight, weight(MIN_STAKE));
+		assert_eq!(EOSIOSystem::candidates().into_inner(), vec![ALICE]);
+
+		// runs once
+		MigrateToV1::<Test>::on_runtime_upgrade();
+		assert_eq!(votes(ALICE), weight(2 * MIN_STAKE));
+	});
+}
@@ -0,0 +1,22 @@
+//! Runtime API definition for the Lockable Currency pallet.
+//!
+//! The named locks of a user, as recorded by the pallet (excl. the vesting lock).
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+pub use pallet_lockable_currency::{LockInfo, LockReasons};
+
+sp_api::decl_runtime_apis! {
+	pub trait LockableCurrencyApi<AccountId, Balance, BlockNumber>
+	where
+		AccountId: Codec,
+		Balance: Codec,
+		BlockNumber: Codec,
+	{
+		/// Named locks of the user.
+		fn locks(who: AccountId) -> Vec<LockInfo<Balance, BlockNumber>>;
+	}
+}
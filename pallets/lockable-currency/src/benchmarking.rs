@@ -5,30 +5,213 @@ use super::*;
 #[allow(unused)]
 use crate::Pallet as LockableCurrency;
 use frame_benchmarking::v2::*;
+use frame_support::{
+	assert_ok,
+	sp_runtime::Saturating,
+	traits::{Currency, Get, LockIdentifier},
+	weights::Weight,
+};
 use frame_system::RawOrigin;
 
+const SEED: u32 = 0;
+
+/// Account with enough balance to lock a few times the existential deposit.
+fn funded_account<T: Config>(name: &'static str, index: u32) -> T::AccountId {
+	let account: T::AccountId = account(name, index, SEED);
+	T::StakeCurrency::make_free_balance_be(&account, amount::<T>().saturating_mul(1_000u32.into()));
+	account
+}
+
+fn amount<T: Config>() -> BalanceOf<T> {
+	T::StakeCurrency::minimum_balance().saturating_mul(10u32.into())
+}
+
+/// Amount transferred under a vesting schedule, at least the `MinVestedTransfer`.
+fn vested_amount<T: Config>() -> BalanceOf<T> {
+	amount::<T>().max(T::MinVestedTransfer::get())
+}
+
+fn lock_id(i: u32) -> LockIdentifier {
+	let mut id = *b"bench   ";
+	id[4..].copy_from_slice(&i.to_le_bytes());
+	id
+}
+
+/// `n` locks of the `user` (for all reasons), expiring at `expires_at`.
+fn add_locks<T: Config>(user: &T::AccountId, n: u32, expires_at: Option<T::BlockNumber>) {
+	for i in 0..n {
+		assert_ok!(LockableCurrency::<T>::lock_capital(
+			RawOrigin::Signed(user.clone()).into(),
+			lock_id(i),
+			amount::<T>(),
+			LockReasons::All,
+			expires_at
+		));
+	}
+}
+
 #[benchmarks]
 mod benchmarks {
 	use super::*;
 
+	// the last of `MaxLocks` locks is replaced, along with its expiry
 	#[benchmark]
-	fn do_something() {
-		// let value = 100u32.into();
-		// let caller: T::AccountId = whitelisted_caller();
-		// #[extrinsic_call]
-		// do_something(RawOrigin::Signed(caller), value);
+	fn lock_capital() {
+		let caller = funded_account::<T>("caller", 0);
+		let max_locks = T::MaxLocks::get();
+		add_locks::<T>(&caller, max_locks, Some(10u32.into()));
+		let amount = amount::<T>().saturating_mul(2u32.into());
+
+		#[extrinsic_call]
+		lock_capital(
+			RawOrigin::Signed(caller.clone()),
+			lock_id(max_locks - 1),
+			amount,
+			LockReasons::Misc,
+			Some(20u32.into()),
+		);
 
-		// assert_eq!(Something::<T>::get(), Some(value));
+		let lock = Locks::<T>::get(&caller).into_iter().last().unwrap();
+		assert_eq!(lock.amount, amount);
+		assert_eq!(lock.expires_at, Some(20u32.into()));
 	}
 
+	// the last of `MaxLocks` locks is extended
 	#[benchmark]
-	fn cause_error() {
-		// Something::<T>::put(100u32);
-		// let caller: T::AccountId = whitelisted_caller();
-		// #[extrinsic_call]
-		// cause_error(RawOrigin::Signed(caller));
+	fn extend_lock() {
+		let caller = funded_account::<T>("caller", 0);
+		let max_locks = T::MaxLocks::get();
+		add_locks::<T>(&caller, max_locks, None);
+		let amount = amount::<T>().saturating_mul(2u32.into());
+
+		#[extrinsic_call]
+		extend_lock(
+			RawOrigin::Signed(caller.clone()),
+			lock_id(max_locks - 1),
+			amount,
+			LockReasons::Fee,
+		);
+
+		assert_eq!(Locks::<T>::get(&caller).into_iter().last().unwrap().amount, amount);
+	}
+
+	// the last of `MaxLocks` locks is removed, along with its expiry
+	#[benchmark]
+	fn unlock() {
+		let caller = funded_account::<T>("caller", 0);
+		let max_locks = T::MaxLocks::get();
+		add_locks::<T>(&caller, max_locks, Some(10u32.into()));
+
+		#[extrinsic_call]
+		unlock(RawOrigin::Signed(caller.clone()), lock_id(max_locks - 1));
+
+		assert_eq!(Locks::<T>::get(&caller).len() as u32, max_locks - 1);
+	}
+
+	// the last of `MaxLocks` locks is drained & thus removed, along with its expiry
+	#[benchmark]
+	fn unlock_partial() {
+		let caller = funded_account::<T>("caller", 0);
+		let max_locks = T::MaxLocks::get();
+		add_locks::<T>(&caller, max_locks, Some(10u32.into()));
+
+		#[extrinsic_call]
+		unlock_partial(RawOrigin::Signed(caller.clone()), lock_id(max_locks - 1), amount::<T>());
+
+		assert_eq!(Locks::<T>::get(&caller).len() as u32, max_locks - 1);
+	}
+
+	// `l` locks are removed, along with their expiries
+	#[benchmark]
+	fn unlock_all(l: Linear<1, { T::MaxLocks::get() }>) {
+		let caller = funded_account::<T>("caller", 0);
+		add_locks::<T>(&caller, l, Some(10u32.into()));
+
+		#[extrinsic_call]
+		unlock_all(RawOrigin::Signed(caller.clone()));
+
+		assert!(Locks::<T>::get(&caller).is_empty());
+	}
+
+	// the last of `MaxLocks` locks expires
+	#[benchmark]
+	fn expire_lock() {
+		let caller = funded_account::<T>("caller", 0);
+		let max_locks = T::MaxLocks::get();
+		add_locks::<T>(&caller, max_locks - 1, None);
+		frame_system::Pallet::<T>::set_block_number(1u32.into());
+		let expires_at: T::BlockNumber = 2u32.into();
+		assert_ok!(LockableCurrency::<T>::lock_capital(
+			RawOrigin::Signed(caller.clone()).into(),
+			lock_id(max_locks - 1),
+			amount::<T>(),
+			LockReasons::All,
+			Some(expires_at)
+		));
+
+		#[block]
+		{
+			LockableCurrency::<T>::sweep_expired(expires_at, Weight::MAX);
+		}
+
+		assert_eq!(Locks::<T>::get(&caller).len() as u32, max_locks - 1);
+	}
+
+	// a block without expiries is swept
+	#[benchmark]
+	fn sweep_block() {
+		let now: T::BlockNumber = 2u32.into();
+
+		#[block]
+		{
+			LockableCurrency::<T>::sweep_expired(now, Weight::MAX);
+		}
+
+		assert_eq!(SweepCursor::<T>::get(), 3u32.into());
+	}
+
+	#[benchmark]
+	fn vested_transfer() {
+		let caller = funded_account::<T>("funder", 0);
+		let beneficiary = funded_account::<T>("beneficiary", 0);
+
+		#[extrinsic_call]
+		vested_transfer(
+			RawOrigin::Signed(caller),
+			beneficiary.clone(),
+			vested_amount::<T>(),
+			0u32.into(),
+			5u32.into(),
+			T::StakeCurrency::minimum_balance(),
+		);
+
+		assert!(Vesting::<T>::contains_key(&beneficiary));
+	}
+
+	// the lock shrinks, as only some of the tokens are vested
+	#[benchmark]
+	fn vest() {
+		let funder = funded_account::<T>("funder", 0);
+		let beneficiary = funded_account::<T>("beneficiary", 0);
+		let per_block = T::StakeCurrency::minimum_balance();
+		assert_ok!(LockableCurrency::<T>::vested_transfer(
+			RawOrigin::Signed(funder).into(),
+			beneficiary.clone(),
+			vested_amount::<T>(),
+			0u32.into(),
+			0u32.into(),
+			per_block
+		));
+		let now: T::BlockNumber = 5u32.into();
+		frame_system::Pallet::<T>::set_block_number(now);
+
+		#[extrinsic_call]
+		vest(RawOrigin::Signed(beneficiary.clone()));
 
-		// assert_eq!(Something::<T>::get(), Some(101u32));
+		assert_eq!(
+			Vesting::<T>::get(&beneficiary).unwrap().locked_at(now),
+			vested_amount::<T>().saturating_sub(per_block.saturating_mul(5u32.into()))
+		);
 	}
 
 	impl_benchmark_test_suite!(LockableCurrency, crate::mock::new_test_ext(), crate::mock::Test);
@@ -3,6 +3,34 @@
 //!
 //! A simple pallet demonstrating the usage of `LockableCurrency` trait.
 //!
+//! A user can lock its tokens under several named locks, each identified by a user chosen 8-byte
+//! `LockIdentifier` (upto `MaxLocks` locks per account). A lock restricts the withdrawals for the
+//! chosen `LockReasons` (i.e. the `WithdrawReasons` for paying fees, anything but fees or all) &
+//! can optionally expire at a block, when it's removed automatically.
+//!
+//! The user chosen id only names the lock within this pallet. The lock is set in `Balances` under
+//! an id derived by the pallet (`NAMED_LOCK_PREFIX` followed by a per-account slot), so a user
+//! can't touch the locks of the other pallets (e.g. the stake lock of `eosio_system`).
+//!
+//! The locks of a user (amount, reasons, creation & expiry block) are recorded in the `Locks`
+//! storage & exposed via the `LockableCurrencyApi` runtime API
+//! (`pallet-lockable-currency-runtime-api`). A lock can only be extended via `extend_lock` &
+//! reduced via `unlock_partial` (or replaced via `lock_capital`).
+//!
+//! The expired locks are swept in `on_initialize` (upto `MaxExpiriesPerBlock` locks per block) &
+//! the rest (if any) in `on_idle` with the remaining weight of the block.
+//!
+//! ### Vesting
+//!
+//! A funder can transfer tokens to a beneficiary under a linear vesting schedule via
+//! `vested_transfer`. The tokens vest `per_block` from the `start` block, but none of them can be
+//! withdrawn before the `cliff` block. The unvested tokens are locked under the reserved
+//! `VESTING_ID` lock, which shrinks as the beneficiary calls `vest` & is removed once all the
+//! tokens are vested. A beneficiary can have one schedule at a time, so a transferred schedule
+//! must be of at least `MinVestedTransfer` tokens.
+//!
+//! The schedules of the (already endowed) beneficiaries can also be set at genesis.
+//!
 //! NOTE: The runtime must include the `Balances` pallet to handle the
 //! accounts and balances for your chain.
 //!
@@ -10,9 +38,13 @@
 //!
 //! ### Dispatchables
 //!
-//! - `lock_capital`
-//! - `extend_lock`
-//! - `unlock_all`
+//! - `lock_capital`: Create or replace a named lock.
+//! - `extend_lock`: Extend the amount & reasons of a named lock.
+//! - `unlock`: Remove a named lock.
+//! - `unlock_partial`: Reduce the amount of a named lock.
+//! - `unlock_all`: Remove all the locks of the caller (incl. the legacy `LEGACY_LOCK_ID` lock).
+//! - `vested_transfer`: Transfer tokens to a beneficiary under a vesting schedule.
+//! - `vest`: Unlock the vested tokens of the caller.
 //!
 //! ## References
 //! - https://docs.substrate.io/reference/how-to-guides/pallet-design/implement-lockable-currency/
@@ -36,17 +68,41 @@ pub use weights::*;
 pub mod pallet {
 	use super::*;
 	use frame_support::{
+		inherent::Vec,
 		pallet_prelude::*,
-		traits::{Currency, LockIdentifier, LockableCurrency, WithdrawReasons},
+		sp_runtime::{
+			traits::{Saturating, Zero},
+			SaturatedConversion,
+		},
+		traits::{
+			Currency, ExistenceRequirement, LockIdentifier, LockableCurrency, WithdrawReasons,
+		},
 	};
 	use frame_system::pallet_prelude::*;
 
-	const EXAMPLE_ID: LockIdentifier = *b"example ";
+	/// Lock of the unvested tokens. Can't be used for the named locks.
+	///
+	/// NOTE: Differs from the `pallet_vesting` lock id, so that both pallets can be in a runtime.
+	pub const VESTING_ID: LockIdentifier = *b"lcvestng";
+
+	/// Lock id of the single lock set by the pallet before the named locks. Such (legacy) locks
+	/// are released via `unlock_all`.
+	pub const LEGACY_LOCK_ID: LockIdentifier = *b"example ";
+
+	/// Prefix of the `Balances` lock ids of the named locks, followed by the slot of the lock.
+	pub const NAMED_LOCK_PREFIX: [u8; 7] = *b"lockcur";
+
+	/// The `Balances` lock id of the named lock in the `slot`.
+	pub fn named_lock_id(slot: u8) -> LockIdentifier {
+		let mut id = [slot; 8];
+		id[..7].copy_from_slice(&NAMED_LOCK_PREFIX);
+		id
+	}
 
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
 
-	type BalanceOf<T> =
+	pub(crate) type BalanceOf<T> =
 		<<T as Config>::StakeCurrency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
@@ -58,78 +114,641 @@ pub mod pallet {
 		type WeightInfo: WeightInfo;
 		// The lockable currency type
 		type StakeCurrency: LockableCurrency<Self::AccountId, Moment = Self::BlockNumber>;
+
+		/// Max. no. of named locks per account.
+		///
+		/// NOTE: Should not exceed the max. locks of the `StakeCurrency` (`Balances`) nor 256, the
+		/// no. of slots of the `Balances` lock ids.
+		#[pallet::constant]
+		type MaxLocks: Get<u32>;
+
+		/// Max. no. of expired locks removed in `on_initialize`. The rest are removed in
+		/// `on_idle`.
+		#[pallet::constant]
+		type MaxExpiriesPerBlock: Get<u32>;
+
+		/// Min. amount transferred via `vested_transfer`, so that a beneficiary can't be blocked
+		/// from other schedules by a dust schedule.
+		#[pallet::constant]
+		type MinVestedTransfer: Get<BalanceOf<Self>>;
+	}
+
+	/// Withdrawals restricted by a lock i.e. the `WithdrawReasons` as tracked by `Balances`.
+	#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, TypeInfo, RuntimeDebug, MaxEncodedLen)]
+	pub enum LockReasons {
+		/// Paying transaction fees.
+		Fee,
+		/// Any reason other than paying transaction fees.
+		Misc,
+		/// Any reason.
+		All,
+	}
+
+	impl From<LockReasons> for WithdrawReasons {
+		fn from(reasons: LockReasons) -> Self {
+			match reasons {
+				LockReasons::Fee => WithdrawReasons::TRANSACTION_PAYMENT,
+				LockReasons::Misc => WithdrawReasons::except(WithdrawReasons::TRANSACTION_PAYMENT),
+				LockReasons::All => WithdrawReasons::all(),
+			}
+		}
+	}
+
+	impl From<WithdrawReasons> for LockReasons {
+		fn from(reasons: WithdrawReasons) -> Self {
+			if reasons == WithdrawReasons::TRANSACTION_PAYMENT {
+				LockReasons::Fee
+			} else if reasons.contains(WithdrawReasons::TRANSACTION_PAYMENT) {
+				LockReasons::All
+			} else {
+				LockReasons::Misc
+			}
+		}
+	}
+
+	impl LockReasons {
+		/// Withdrawals restricted by either of the reasons.
+		pub fn union(self, other: LockReasons) -> LockReasons {
+			(WithdrawReasons::from(self) | WithdrawReasons::from(other)).into()
+		}
+	}
+
+	/// A named lock of a user.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, TypeInfo, RuntimeDebug, MaxEncodedLen)]
+	pub struct LockInfo<Balance, BlockNumber> {
+		/// Identifier chosen by the user.
+		pub id: LockIdentifier,
+		/// Slot of the `Balances` lock id, unique among the locks of the user.
+		pub slot: u8,
+		/// Locked amount.
+		pub amount: Balance,
+		/// Withdrawals restricted by the lock.
+		pub reasons: LockReasons,
+		/// Block at which the lock was created (or last replaced).
+		pub created_at: BlockNumber,
+		/// Block at which the lock is removed (if any).
+		pub expires_at: Option<BlockNumber>,
+	}
+
+	impl<Balance, BlockNumber> LockInfo<Balance, BlockNumber> {
+		/// The id under which the lock is set in `Balances`.
+		pub fn lock_id(&self) -> LockIdentifier {
+			named_lock_id(self.slot)
+		}
+	}
+
+	pub type LockInfoOf<T> = LockInfo<BalanceOf<T>, <T as frame_system::Config>::BlockNumber>;
+
+	/// Linear vesting schedule of a beneficiary.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, TypeInfo, RuntimeDebug, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	pub struct VestingSchedule<T: Config> {
+		/// Tokens to vest.
+		pub total: BalanceOf<T>,
+		/// Block from which the tokens vest.
+		pub start: T::BlockNumber,
+		/// Block before which all the tokens are locked.
+		pub cliff: T::BlockNumber,
+		/// Tokens vested per block.
+		pub per_block: BalanceOf<T>,
+	}
+
+	impl<T: Config> VestingSchedule<T> {
+		/// Tokens still locked at the block `n`.
+		pub fn locked_at(&self, n: T::BlockNumber) -> BalanceOf<T> {
+			if n < self.cliff {
+				return self.total;
+			}
+			let elapsed: BalanceOf<T> =
+				n.saturating_sub(self.start).saturated_into::<u128>().saturated_into();
+			self.total.saturating_sub(self.per_block.saturating_mul(elapsed))
+		}
+
+		/// Ensure the schedule can vest.
+		fn validate(&self) -> DispatchResult {
+			ensure!(self.total >= T::StakeCurrency::minimum_balance(), Error::<T>::AmountLow);
+			ensure!(
+				!self.per_block.is_zero() && self.cliff >= self.start,
+				Error::<T>::InvalidSchedule
+			);
+			Ok(())
+		}
 	}
 
 	// Here, the pallet's storage items can be defined by
-	// having the person 🧍 -> locked_id -> locked_amount💰
-	// #[pallet::storage]
-	// #[pallet::getter(fn something)]
-	// pub type Something<T> = StorageValue<_, u32>;
+	// having the person 🧍 -> locks (id, locked_amount💰, ...)
+
+	/// The named locks of a user.
+	#[pallet::storage]
+	#[pallet::getter(fn locks)]
+	pub type Locks<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<LockInfoOf<T>, T::MaxLocks>,
+		ValueQuery,
+	>;
+
+	/// The locks (user, lock id) expiring at a block.
+	#[pallet::storage]
+	pub type Expiries<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		Blake2_128Concat,
+		(T::AccountId, LockIdentifier),
+		(),
+		OptionQuery,
+	>;
+
+	/// The earliest block whose expired locks may not have been swept yet.
+	#[pallet::storage]
+	pub type SweepCursor<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+
+	/// The vesting schedule of a beneficiary.
+	#[pallet::storage]
+	#[pallet::getter(fn vesting)]
+	pub type Vesting<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, VestingSchedule<T>, OptionQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// (beneficiary, total, start, cliff, per block) of the vesting schedules.
+		///
+		/// NOTE: The beneficiaries must be endowed with the total (via `Balances`).
+		pub vesting:
+			Vec<(T::AccountId, BalanceOf<T>, T::BlockNumber, T::BlockNumber, BalanceOf<T>)>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { vesting: Default::default() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			for (beneficiary, total, start, cliff, per_block) in self.vesting.iter() {
+				let schedule = VestingSchedule::<T> {
+					total: *total,
+					start: *start,
+					cliff: *cliff,
+					per_block: *per_block,
+				};
+				assert!(schedule.validate().is_ok(), "Invalid vesting schedule");
+				assert!(
+					T::StakeCurrency::free_balance(beneficiary) >= *total,
+					"Beneficiary must be endowed with the vesting total"
+				);
+				assert!(
+					!Vesting::<T>::contains_key(beneficiary),
+					"Beneficiary can have only one vesting schedule"
+				);
+
+				Pallet::<T>::update_vesting_lock(beneficiary, schedule);
+			}
+		}
+	}
 
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
-		Locked { user: T::AccountId, amount: BalanceOf<T> },
-		ExtendedLock { user: T::AccountId, amount: BalanceOf<T> },
-		Unlocked { user: T::AccountId },
+		Locked {
+			user: T::AccountId,
+			id: LockIdentifier,
+			amount: BalanceOf<T>,
+			reasons: LockReasons,
+			expires_at: Option<T::BlockNumber>,
+		},
+		ExtendedLock {
+			user: T::AccountId,
+			id: LockIdentifier,
+			amount: BalanceOf<T>,
+			reasons: LockReasons,
+		},
+		Unlocked {
+			user: T::AccountId,
+			id: LockIdentifier,
+		},
+		UnlockedAll {
+			user: T::AccountId,
+		},
+		PartiallyUnlocked {
+			user: T::AccountId,
+			id: LockIdentifier,
+			amount: BalanceOf<T>,
+			remaining: BalanceOf<T>,
+		},
+		LockExpired {
+			user: T::AccountId,
+			id: LockIdentifier,
+		},
+		VestingScheduleCreated {
+			funder: T::AccountId,
+			beneficiary: T::AccountId,
+			total: BalanceOf<T>,
+			start: T::BlockNumber,
+			cliff: T::BlockNumber,
+			per_block: BalanceOf<T>,
+		},
+		Vested {
+			user: T::AccountId,
+			locked: BalanceOf<T>,
+		},
+		VestingCompleted {
+			user: T::AccountId,
+		},
 	}
 
 	// Errors inform users that something went wrong.
-	// #[pallet::error]
-	// pub enum Error<T> {}
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Too Many Locks.
+		TooManyLocks,
+		/// Lock Not Found.
+		LockNotFound,
+		/// Expiry Not In Future.
+		ExpiryNotInFuture,
+		/// Lock Amount Less Than Locked.
+		LockNotExtended,
+		/// Insufficient Locked Amount.
+		InsufficientLockedAmount,
+		/// Lock Id Reserved For Vesting.
+		ReservedLockId,
+		/// Vesting Schedule Already Exists.
+		VestingScheduleExists,
+		/// Not Vesting.
+		NotVesting,
+		/// Invalid Vesting Schedule.
+		InvalidSchedule,
+		/// Vesting Amount Too Low.
+		AmountLow,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// Remove (upto `MaxExpiriesPerBlock`) expired locks.
+		fn on_initialize(n: T::BlockNumber) -> Weight {
+			let limit = T::WeightInfo::expire_lock()
+				.saturating_mul(T::MaxExpiriesPerBlock::get().into())
+				.saturating_add(T::WeightInfo::sweep_block())
+				.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+			Self::sweep_expired(n, limit)
+		}
+
+		/// Remove the expired locks left over by `on_initialize` with the remaining weight.
+		fn on_idle(n: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			Self::sweep_expired(n, remaining_weight)
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		/// Locks the specified amount of tokens from the caller.
+		/// Locks the specified amount of tokens from the caller under the lock `id` for the
+		/// `reasons`, optionally until the block `expires_at`.
+		///
+		/// Replaces the lock if it already exists.
 		#[pallet::call_index(0)]
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
+		#[pallet::weight(T::WeightInfo::lock_capital())]
 		pub fn lock_capital(
 			origin: OriginFor<T>,
+			id: LockIdentifier,
 			#[pallet::compact] amount: BalanceOf<T>,
+			reasons: LockReasons,
+			expires_at: Option<T::BlockNumber>,
 		) -> DispatchResult {
 			let user = ensure_signed(origin)?;
 
+			ensure!(id != VESTING_ID, Error::<T>::ReservedLockId);
+
+			if let Some(expires_at) = expires_at {
+				ensure!(
+					expires_at > <frame_system::Pallet<T>>::block_number(),
+					Error::<T>::ExpiryNotInFuture
+				);
+			}
+
+			let lock_id = Locks::<T>::try_mutate(&user, |locks| -> Result<_, DispatchError> {
+				let created_at = <frame_system::Pallet<T>>::block_number();
+				match locks.iter_mut().find(|l| l.id == id) {
+					Some(existing) => {
+						if let Some(old_expiry) = existing.expires_at {
+							Expiries::<T>::remove(old_expiry, (&user, id));
+						}
+						let slot = existing.slot;
+						*existing = LockInfo { id, slot, amount, reasons, created_at, expires_at };
+						Ok(existing.lock_id())
+					},
+					None => {
+						let slot = (0..=u8::MAX)
+							.find(|slot| locks.iter().all(|l| l.slot != *slot))
+							.ok_or(Error::<T>::TooManyLocks)?;
+						let lock = LockInfo { id, slot, amount, reasons, created_at, expires_at };
+						let lock_id = lock.lock_id();
+						locks.try_push(lock).map_err(|_| Error::<T>::TooManyLocks)?;
+						Ok(lock_id)
+					},
+				}
+			})?;
+
+			if let Some(expires_at) = expires_at {
+				Expiries::<T>::insert(expires_at, (&user, id), ());
+			}
+
 			// lock amount
-			T::StakeCurrency::set_lock(EXAMPLE_ID, &user, amount, WithdrawReasons::all());
+			T::StakeCurrency::set_lock(lock_id, &user, amount, reasons.into());
 
 			// Emit an event.
-			Self::deposit_event(Event::Locked { user, amount });
+			Self::deposit_event(Event::Locked { user, id, amount, reasons, expires_at });
 
 			// Return a successful DispatchResultWithPostInfo
 			Ok(())
 		}
 
-		/// Extends the lock period.
+		/// Extends the lock `id` to the specified amount (not less than the locked amount), for the
+		/// union of the existing & the specified reasons. The expiry is kept as is.
 		#[pallet::call_index(1)]
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1).ref_time())]
+		#[pallet::weight(T::WeightInfo::extend_lock())]
 		pub fn extend_lock(
 			origin: OriginFor<T>,
+			id: LockIdentifier,
 			#[pallet::compact] amount: BalanceOf<T>,
+			reasons: LockReasons,
 		) -> DispatchResult {
 			let user = ensure_signed(origin)?;
 
+			ensure!(id != VESTING_ID, Error::<T>::ReservedLockId);
+
+			let (lock_id, reasons) =
+				Locks::<T>::try_mutate(&user, |locks| -> Result<_, DispatchError> {
+					let lock =
+						locks.iter_mut().find(|l| l.id == id).ok_or(Error::<T>::LockNotFound)?;
+					ensure!(amount >= lock.amount, Error::<T>::LockNotExtended);
+					lock.amount = amount;
+					lock.reasons = lock.reasons.union(reasons);
+					Ok((lock.lock_id(), lock.reasons))
+				})?;
+
 			// extend lock amount
-			T::StakeCurrency::extend_lock(EXAMPLE_ID, &user, amount, WithdrawReasons::all());
+			T::StakeCurrency::set_lock(lock_id, &user, amount, reasons.into());
 
 			// Emit an event.
-			Self::deposit_event(Event::ExtendedLock { user, amount });
+			Self::deposit_event(Event::ExtendedLock { user, id, amount, reasons });
 
 			Ok(())
 		}
 
 		/// Releases all locked tokens.
 		#[pallet::call_index(2)]
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1).ref_time())]
-		pub fn unlock_all(origin: OriginFor<T>) -> DispatchResult {
+		#[pallet::weight(T::WeightInfo::unlock_all(T::MaxLocks::get()))]
+		pub fn unlock_all(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let user = ensure_signed(origin)?;
+
+			// unlock amount
+			let locks = Locks::<T>::take(&user);
+			for lock in locks.iter() {
+				if let Some(expires_at) = lock.expires_at {
+					Expiries::<T>::remove(expires_at, (&user, lock.id));
+				}
+				T::StakeCurrency::remove_lock(lock.lock_id(), &user);
+			}
+			T::StakeCurrency::remove_lock(LEGACY_LOCK_ID, &user);
+
+			// emit event
+			Self::deposit_event(Event::UnlockedAll { user });
+
+			Ok(Some(T::WeightInfo::unlock_all(locks.len() as u32)).into())
+		}
+
+		/// Releases the tokens locked under the lock `id`.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::unlock())]
+		pub fn unlock(origin: OriginFor<T>, id: LockIdentifier) -> DispatchResult {
+			let user = ensure_signed(origin)?;
+
+			ensure!(id != VESTING_ID, Error::<T>::ReservedLockId);
+
+			let lock_id =
+				Locks::<T>::try_mutate_exists(&user, |maybe_locks| -> Result<_, DispatchError> {
+					let locks = maybe_locks.as_mut().ok_or(Error::<T>::LockNotFound)?;
+					let index =
+						locks.iter().position(|l| l.id == id).ok_or(Error::<T>::LockNotFound)?;
+					let lock = locks.remove(index);
+					if let Some(expires_at) = lock.expires_at {
+						Expiries::<T>::remove(expires_at, (&user, id));
+					}
+					if locks.is_empty() {
+						*maybe_locks = None;
+					}
+					Ok(lock.lock_id())
+				})?;
+
+			// unlock amount
+			T::StakeCurrency::remove_lock(lock_id, &user);
+
+			// emit event
+			Self::deposit_event(Event::Unlocked { user, id });
+
+			Ok(())
+		}
+
+		/// Transfers `total` tokens from the caller to the `beneficiary`, vesting `per_block`
+		/// from the block `start`, with none of them withdrawable before the block `cliff`.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::vested_transfer())]
+		pub fn vested_transfer(
+			origin: OriginFor<T>,
+			beneficiary: T::AccountId,
+			#[pallet::compact] total: BalanceOf<T>,
+			start: T::BlockNumber,
+			cliff: T::BlockNumber,
+			#[pallet::compact] per_block: BalanceOf<T>,
+		) -> DispatchResult {
+			let funder = ensure_signed(origin)?;
+
+			ensure!(total >= T::MinVestedTransfer::get(), Error::<T>::AmountLow);
+			let schedule = VestingSchedule::<T> { total, start, cliff, per_block };
+			schedule.validate()?;
+			ensure!(!Vesting::<T>::contains_key(&beneficiary), Error::<T>::VestingScheduleExists);
+
+			T::StakeCurrency::transfer(
+				&funder,
+				&beneficiary,
+				total,
+				ExistenceRequirement::AllowDeath,
+			)?;
+			let locked = Self::update_vesting_lock(&beneficiary, schedule);
+
+			// Emit an event.
+			Self::deposit_event(Event::VestingScheduleCreated {
+				funder,
+				beneficiary: beneficiary.clone(),
+				total,
+				start,
+				cliff,
+				per_block,
+			});
+			if locked.is_zero() {
+				Self::deposit_event(Event::VestingCompleted { user: beneficiary });
+			}
+
+			Ok(())
+		}
+
+		/// Unlocks the tokens of the caller vested so far.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::vest())]
+		pub fn vest(origin: OriginFor<T>) -> DispatchResult {
 			let user = ensure_signed(origin)?;
 
+			let schedule = Vesting::<T>::get(&user).ok_or(Error::<T>::NotVesting)?;
+			let locked = Self::update_vesting_lock(&user, schedule);
+
+			// Emit an event.
+			Self::deposit_event(Event::Vested { user: user.clone(), locked });
+			if locked.is_zero() {
+				Self::deposit_event(Event::VestingCompleted { user });
+			}
+
+			Ok(())
+		}
+
+		/// Releases the `amount` of the tokens locked under the lock `id`. The lock is removed once
+		/// nothing remains locked.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::unlock_partial())]
+		pub fn unlock_partial(
+			origin: OriginFor<T>,
+			id: LockIdentifier,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let user = ensure_signed(origin)?;
+
+			ensure!(id != VESTING_ID, Error::<T>::ReservedLockId);
+
+			let (lock_id, remaining, reasons) =
+				Locks::<T>::try_mutate_exists(&user, |maybe_locks| -> Result<_, DispatchError> {
+					let locks = maybe_locks.as_mut().ok_or(Error::<T>::LockNotFound)?;
+					let index =
+						locks.iter().position(|l| l.id == id).ok_or(Error::<T>::LockNotFound)?;
+					let lock = &mut locks[index];
+					ensure!(amount <= lock.amount, Error::<T>::InsufficientLockedAmount);
+					lock.amount = lock.amount.saturating_sub(amount);
+					let (lock_id, remaining, reasons) = (lock.lock_id(), lock.amount, lock.reasons);
+
+					if remaining.is_zero() {
+						let lock = locks.remove(index);
+						if let Some(expires_at) = lock.expires_at {
+							Expiries::<T>::remove(expires_at, (&user, id));
+						}
+						if locks.is_empty() {
+							*maybe_locks = None;
+						}
+					}
+					Ok((lock_id, remaining, reasons))
+				})?;
+
 			// unlock amount
-			T::StakeCurrency::remove_lock(EXAMPLE_ID, &user);
+			if remaining.is_zero() {
+				T::StakeCurrency::remove_lock(lock_id, &user);
+			} else {
+				T::StakeCurrency::set_lock(lock_id, &user, remaining, reasons.into());
+			}
 
 			// emit event
-			Self::deposit_event(Event::Unlocked { user });
+			Self::deposit_event(Event::PartiallyUnlocked { user, id, amount, remaining });
 
 			Ok(())
 		}
 	}
+
+	impl<T: Config> Pallet<T> {
+		/// Lock the tokens of the `who` unvested at the current block as per the `schedule`.
+		///
+		/// The schedule is stored till all the tokens are vested, when the lock is removed.
+		/// Returns the locked amount.
+		fn update_vesting_lock(who: &T::AccountId, schedule: VestingSchedule<T>) -> BalanceOf<T> {
+			let locked = schedule.locked_at(<frame_system::Pallet<T>>::block_number());
+			if locked.is_zero() {
+				T::StakeCurrency::remove_lock(VESTING_ID, who);
+				Vesting::<T>::remove(who);
+			} else {
+				T::StakeCurrency::set_lock(VESTING_ID, who, locked, WithdrawReasons::all());
+				Vesting::<T>::insert(who, schedule);
+			}
+			locked
+		}
+
+		/// Remove the locks expired by the block `now`, starting from the `SweepCursor`, within the
+		/// `limit` weight.
+		///
+		/// Returns the consumed weight.
+		pub(crate) fn sweep_expired(now: T::BlockNumber, limit: Weight) -> Weight {
+			let (sweep_block, expire_lock) =
+				(T::WeightInfo::sweep_block(), T::WeightInfo::expire_lock());
+			// read & write the cursor
+			let mut weight = T::DbWeight::get().reads_writes(1, 1);
+			if weight.any_gt(limit) {
+				return Weight::zero();
+			}
+
+			// the cursor is set on the first sweep, as no lock can expire before that
+			let mut cursor = SweepCursor::<T>::get();
+			if cursor.is_zero() {
+				cursor = now;
+			}
+
+			while cursor <= now {
+				if weight.saturating_add(sweep_block).any_gt(limit) {
+					break;
+				}
+				weight.saturating_accrue(sweep_block);
+
+				// the drained expiries are removed from the storage as they are iterated
+				let mut expiries = Expiries::<T>::drain_prefix(cursor);
+				let mut swept = true;
+				loop {
+					if weight.saturating_add(expire_lock).any_gt(limit) {
+						swept = false;
+						break;
+					}
+					match expiries.next() {
+						Some(((user, id), ())) => {
+							weight.saturating_accrue(expire_lock);
+							Self::expire_lock(user, id, cursor);
+						},
+						None => break,
+					}
+				}
+
+				if !swept {
+					break;
+				}
+				cursor.saturating_inc();
+			}
+
+			SweepCursor::<T>::put(cursor);
+			weight
+		}
+
+		/// Remove the lock `id` of the `user` if it expires at the block `at`.
+		fn expire_lock(user: T::AccountId, id: LockIdentifier, at: T::BlockNumber) {
+			let expired = Locks::<T>::mutate_exists(&user, |maybe_locks| {
+				let locks = maybe_locks.as_mut()?;
+				let index = locks.iter().position(|l| l.id == id && l.expires_at == Some(at))?;
+				let lock = locks.remove(index);
+				if locks.is_empty() {
+					*maybe_locks = None;
+				}
+				Some(lock.lock_id())
+			});
+
+			if let Some(lock_id) = expired {
+				T::StakeCurrency::remove_lock(lock_id, &user);
+				Self::deposit_event(Event::LockExpired { user, id });
+			}
+		}
+	}
 }
@@ -1,5 +1,5 @@
 use crate as pallet_lockable_currency;
-use frame_support::traits::{ConstU128, ConstU16, ConstU32, ConstU64};
+use frame_support::traits::{ConstU128, ConstU16, ConstU32, ConstU64, Hooks};
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
@@ -56,7 +56,7 @@ impl pallet_balances::Config for Test {
 	type ExistentialDeposit = ConstU128<100>;
 	type AccountStore = System;
 	type WeightInfo = ();
-	type MaxLocks = ();
+	type MaxLocks = ConstU32<50>;
 	type MaxReserves = ConstU32<50>;
 	type ReserveIdentifier = [u8; 8];
 	type FreezeIdentifier = ();
@@ -69,9 +69,33 @@ impl pallet_lockable_currency::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = ();
 	type StakeCurrency = Balances;
+	type MaxLocks = ConstU32<3>;
+	type MaxExpiriesPerBlock = ConstU32<2>;
+	type MinVestedTransfer = ConstU128<500>;
 }
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	frame_system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(1, 10_000), (2, 10_000), (3, 10_000)],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	// 5_000 vesting 100 per block from genesis, with a cliff at block 10
+	pallet_lockable_currency::GenesisConfig::<Test> { vesting: vec![(3, 5_000, 0, 10, 100)] }
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// Move to the block `n`, sweeping the expired locks in `on_initialize` of each block.
+pub fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		System::set_block_number(System::block_number() + 1);
+		LockableCurrency::on_initialize(System::block_number());
+	}
 }
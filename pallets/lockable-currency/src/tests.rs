@@ -5,8 +5,25 @@
 
 #![allow(unused)]
 
-use crate::{mock::*, /* Error, */ Event};
-use frame_support::{assert_noop, assert_ok};
+use crate::{
+	mock::*, named_lock_id, Error, Event, LockInfo, LockReasons, Locks, SweepCursor, Vesting,
+	LEGACY_LOCK_ID, VESTING_ID,
+};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{Hooks, LockIdentifier, LockableCurrency as _, WithdrawReasons},
+	weights::Weight,
+};
+
+const ID: LockIdentifier = *b"example ";
+const OTHER_ID: LockIdentifier = *b"other   ";
+/// Lock id of another pallet (`eosio_system`).
+const FOREIGN_ID: LockIdentifier = *b"EOSIO___";
+
+/// The amount locked under the `Balances` lock `id` of the `who`.
+fn balances_lock(who: u64, id: LockIdentifier) -> Option<u128> {
+	Balances::locks(who).iter().find(|l| l.id == id).map(|l| l.amount)
+}
 
 //=====lock_capital=====
 
@@ -16,8 +33,23 @@ use frame_support::{assert_noop, assert_ok};
 fn lock_zero_amt() {
 	new_test_ext().execute_with(|| {
 		assert_eq!(Balances::free_balance(1), 10000);
-		assert_ok!(LockableCurrency::lock_capital(RuntimeOrigin::signed(1), 0));
-		System::assert_last_event(Event::Locked { user: 1, amount: 0 }.into());
+		assert_ok!(LockableCurrency::lock_capital(
+			RuntimeOrigin::signed(1),
+			ID,
+			0,
+			LockReasons::All,
+			None
+		));
+		System::assert_last_event(
+			Event::Locked {
+				user: 1,
+				id: ID,
+				amount: 0,
+				reasons: LockReasons::All,
+				expires_at: None,
+			}
+			.into(),
+		);
 		assert_eq!(Balances::free_balance(1), 10000); // free_balance is still 10000
 		assert_ok!(Balances::transfer(RuntimeOrigin::signed(1), 2, 10000)); // transfer all free_balance
 	});
@@ -29,8 +61,23 @@ fn lock_zero_amt() {
 fn lock_some_amt() {
 	new_test_ext().execute_with(|| {
 		assert_eq!(Balances::free_balance(1), 10000);
-		assert_ok!(LockableCurrency::lock_capital(RuntimeOrigin::signed(1), 100));
-		System::assert_last_event(Event::Locked { user: 1, amount: 100 }.into());
+		assert_ok!(LockableCurrency::lock_capital(
+			RuntimeOrigin::signed(1),
+			ID,
+			100,
+			LockReasons::All,
+			None
+		));
+		System::assert_last_event(
+			Event::Locked {
+				user: 1,
+				id: ID,
+				amount: 100,
+				reasons: LockReasons::All,
+				expires_at: None,
+			}
+			.into(),
+		);
 		assert_eq!(Balances::free_balance(1), 10000); // free_balance is still 10000
 		assert_ok!(Balances::transfer(RuntimeOrigin::signed(1), 2, 9900)); // transfer 9900 (remaining 100 is locked)
 	});
@@ -42,8 +89,23 @@ fn lock_some_amt() {
 fn lock_all_amt() {
 	new_test_ext().execute_with(|| {
 		assert_eq!(Balances::free_balance(1), 10_000);
-		assert_ok!(LockableCurrency::lock_capital(RuntimeOrigin::signed(1), 10_000));
-		System::assert_last_event(Event::Locked { user: 1, amount: 10_000 }.into());
+		assert_ok!(LockableCurrency::lock_capital(
+			RuntimeOrigin::signed(1),
+			ID,
+			10_000,
+			LockReasons::All,
+			None
+		));
+		System::assert_last_event(
+			Event::Locked {
+				user: 1,
+				id: ID,
+				amount: 10_000,
+				reasons: LockReasons::All,
+				expires_at: None,
+			}
+			.into(),
+		);
 		assert_eq!(Balances::free_balance(1), 10_000); // free_balance is still 10_000
 		assert_noop!(
 			Balances::transfer(RuntimeOrigin::signed(1), 2, 10), // transfer some
@@ -58,8 +120,23 @@ fn lock_all_amt() {
 fn lock_amt_that_exceeds_free_bal() {
 	new_test_ext().execute_with(|| {
 		assert_eq!(Balances::free_balance(1), 10_000);
-		assert_ok!(LockableCurrency::lock_capital(RuntimeOrigin::signed(1), 10_001));
-		System::assert_last_event(Event::Locked { user: 1, amount: 10_001 }.into());
+		assert_ok!(LockableCurrency::lock_capital(
+			RuntimeOrigin::signed(1),
+			ID,
+			10_001,
+			LockReasons::All,
+			None
+		));
+		System::assert_last_event(
+			Event::Locked {
+				user: 1,
+				id: ID,
+				amount: 10_001,
+				reasons: LockReasons::All,
+				expires_at: None,
+			}
+			.into(),
+		);
 		assert_eq!(Balances::free_balance(1), 10_000); // free_balance is still 10_000
 		assert_noop!(
 			Balances::transfer(RuntimeOrigin::signed(1), 2, 10), // transfer some
@@ -77,9 +154,22 @@ fn lock_amt_that_exceeds_free_bal() {
 fn extend_lock_zero_after_zero_locked() {
 	new_test_ext().execute_with(|| {
 		assert_eq!(Balances::free_balance(1), 10_000);
-		assert_ok!(LockableCurrency::lock_capital(RuntimeOrigin::signed(1), 0));
-		assert_ok!(LockableCurrency::extend_lock(RuntimeOrigin::signed(1), 0));
-		System::assert_last_event(Event::ExtendedLock { user: 1, amount: 0 }.into());
+		assert_ok!(LockableCurrency::lock_capital(
+			RuntimeOrigin::signed(1),
+			ID,
+			0,
+			LockReasons::All,
+			None
+		));
+		assert_ok!(LockableCurrency::extend_lock(
+			RuntimeOrigin::signed(1),
+			ID,
+			0,
+			LockReasons::All
+		));
+		System::assert_last_event(
+			Event::ExtendedLock { user: 1, id: ID, amount: 0, reasons: LockReasons::All }.into(),
+		);
 		assert_eq!(Balances::free_balance(1), 10_000); // free_balance is still 10_000
 		assert_ok!(Balances::transfer(RuntimeOrigin::signed(1), 2, 10_000)); // transfer all
 	});
@@ -94,10 +184,32 @@ fn extend_lock_zero_after_zero_locked() {
 fn extend_lock_same_after_some_locked() {
 	new_test_ext().execute_with(|| {
 		assert_eq!(Balances::free_balance(1), 10_000);
-		assert_ok!(LockableCurrency::lock_capital(RuntimeOrigin::signed(1), 100));
-		System::assert_last_event(Event::Locked { user: 1, amount: 100 }.into());
-		assert_ok!(LockableCurrency::extend_lock(RuntimeOrigin::signed(1), 100));
-		System::assert_last_event(Event::ExtendedLock { user: 1, amount: 100 }.into());
+		assert_ok!(LockableCurrency::lock_capital(
+			RuntimeOrigin::signed(1),
+			ID,
+			100,
+			LockReasons::All,
+			None
+		));
+		System::assert_last_event(
+			Event::Locked {
+				user: 1,
+				id: ID,
+				amount: 100,
+				reasons: LockReasons::All,
+				expires_at: None,
+			}
+			.into(),
+		);
+		assert_ok!(LockableCurrency::extend_lock(
+			RuntimeOrigin::signed(1),
+			ID,
+			100,
+			LockReasons::All
+		));
+		System::assert_last_event(
+			Event::ExtendedLock { user: 1, id: ID, amount: 100, reasons: LockReasons::All }.into(),
+		);
 		assert_eq!(Balances::free_balance(1), 10_000); // free_balance is still 10_000
 		assert_noop!(
 			Balances::transfer(RuntimeOrigin::signed(1), 2, 10_000), // fail in transfer of 10_000 free balance
@@ -111,15 +223,32 @@ fn extend_lock_same_after_some_locked() {
 /// 🧍 -> lock 100
 /// 🧍 -> extend lock 99
 ///
-/// take max(100, 99) as locked amount
+/// the lock can't be reduced, so 100 remains locked
 #[test]
 fn extend_lock_less_after_some_locked() {
 	new_test_ext().execute_with(|| {
 		assert_eq!(Balances::free_balance(1), 10_000);
-		assert_ok!(LockableCurrency::lock_capital(RuntimeOrigin::signed(1), 100));
-		System::assert_last_event(Event::Locked { user: 1, amount: 100 }.into());
-		assert_ok!(LockableCurrency::extend_lock(RuntimeOrigin::signed(1), 99));
-		System::assert_last_event(Event::ExtendedLock { user: 1, amount: 99 }.into());
+		assert_ok!(LockableCurrency::lock_capital(
+			RuntimeOrigin::signed(1),
+			ID,
+			100,
+			LockReasons::All,
+			None
+		));
+		System::assert_last_event(
+			Event::Locked {
+				user: 1,
+				id: ID,
+				amount: 100,
+				reasons: LockReasons::All,
+				expires_at: None,
+			}
+			.into(),
+		);
+		assert_noop!(
+			LockableCurrency::extend_lock(RuntimeOrigin::signed(1), ID, 99, LockReasons::All),
+			Error::<Test>::LockNotExtended
+		);
 		assert_eq!(Balances::free_balance(1), 10_000); // free_balance is still 10_000
 		assert_noop!(
 			Balances::transfer(RuntimeOrigin::signed(1), 2, 10_000), // fail in transfer of 10_000 free balance
@@ -138,10 +267,32 @@ fn extend_lock_less_after_some_locked() {
 fn extend_lock_more_after_some_locked() {
 	new_test_ext().execute_with(|| {
 		assert_eq!(Balances::free_balance(1), 10_000);
-		assert_ok!(LockableCurrency::lock_capital(RuntimeOrigin::signed(1), 100));
-		System::assert_last_event(Event::Locked { user: 1, amount: 100 }.into());
-		assert_ok!(LockableCurrency::extend_lock(RuntimeOrigin::signed(1), 101));
-		System::assert_last_event(Event::ExtendedLock { user: 1, amount: 101 }.into());
+		assert_ok!(LockableCurrency::lock_capital(
+			RuntimeOrigin::signed(1),
+			ID,
+			100,
+			LockReasons::All,
+			None
+		));
+		System::assert_last_event(
+			Event::Locked {
+				user: 1,
+				id: ID,
+				amount: 100,
+				reasons: LockReasons::All,
+				expires_at: None,
+			}
+			.into(),
+		);
+		assert_ok!(LockableCurrency::extend_lock(
+			RuntimeOrigin::signed(1),
+			ID,
+			101,
+			LockReasons::All
+		));
+		System::assert_last_event(
+			Event::ExtendedLock { user: 1, id: ID, amount: 101, reasons: LockReasons::All }.into(),
+		);
 		assert_eq!(Balances::free_balance(1), 10_000); // free_balance is still 10_000
 		assert_noop!(
 			Balances::transfer(RuntimeOrigin::signed(1), 2, 10_000), // fail in transfer of 10_000 free balance
@@ -160,7 +311,7 @@ fn unlocked_after_no_lock_op() {
 	new_test_ext().execute_with(|| {
 		assert_eq!(Balances::free_balance(1), 10_000);
 		assert_ok!(LockableCurrency::unlock_all(RuntimeOrigin::signed(1)));
-		System::assert_last_event(Event::Unlocked { user: 1 }.into());
+		System::assert_last_event(Event::UnlockedAll { user: 1 }.into());
 		assert_eq!(Balances::free_balance(1), 10_000); // free_balance is still 10_000
 		assert_ok!(Balances::transfer(RuntimeOrigin::signed(1), 2, 10_000)); // success in transfer of 10_000 free balance
 	});
@@ -173,9 +324,15 @@ fn unlocked_after_no_lock_op() {
 fn unlocked_after_zero_locked() {
 	new_test_ext().execute_with(|| {
 		assert_eq!(Balances::free_balance(1), 10_000);
-		assert_ok!(LockableCurrency::lock_capital(RuntimeOrigin::signed(1), 0));
+		assert_ok!(LockableCurrency::lock_capital(
+			RuntimeOrigin::signed(1),
+			ID,
+			0,
+			LockReasons::All,
+			None
+		));
 		assert_ok!(LockableCurrency::unlock_all(RuntimeOrigin::signed(1)));
-		System::assert_last_event(Event::Unlocked { user: 1 }.into());
+		System::assert_last_event(Event::UnlockedAll { user: 1 }.into());
 		assert_eq!(Balances::free_balance(1), 10_000); // free_balance is still 10_000
 		assert_ok!(Balances::transfer(RuntimeOrigin::signed(1), 2, 10_000)); // success in transfer of 10_000 free balance
 	});
@@ -188,9 +345,15 @@ fn unlocked_after_zero_locked() {
 fn unlocked_after_some_locked() {
 	new_test_ext().execute_with(|| {
 		assert_eq!(Balances::free_balance(1), 10_000);
-		assert_ok!(LockableCurrency::lock_capital(RuntimeOrigin::signed(1), 100));
+		assert_ok!(LockableCurrency::lock_capital(
+			RuntimeOrigin::signed(1),
+			ID,
+			100,
+			LockReasons::All,
+			None
+		));
 		assert_ok!(LockableCurrency::unlock_all(RuntimeOrigin::signed(1)));
-		System::assert_last_event(Event::Unlocked { user: 1 }.into());
+		System::assert_last_event(Event::UnlockedAll { user: 1 }.into());
 		assert_eq!(Balances::free_balance(1), 10_000); // free_balance is still 10_000
 		assert_ok!(Balances::transfer(RuntimeOrigin::signed(1), 2, 10_000)); // success in transfer of 10_000 free balance
 	});
@@ -203,9 +366,15 @@ fn unlocked_after_some_locked() {
 fn unlocked_after_all_locked() {
 	new_test_ext().execute_with(|| {
 		assert_eq!(Balances::free_balance(1), 10_000);
-		assert_ok!(LockableCurrency::lock_capital(RuntimeOrigin::signed(1), 10_000));
+		assert_ok!(LockableCurrency::lock_capital(
+			RuntimeOrigin::signed(1),
+			ID,
+			10_000,
+			LockReasons::All,
+			None
+		));
 		assert_ok!(LockableCurrency::unlock_all(RuntimeOrigin::signed(1)));
-		System::assert_last_event(Event::Unlocked { user: 1 }.into());
+		System::assert_last_event(Event::UnlockedAll { user: 1 }.into());
 		assert_eq!(Balances::free_balance(1), 10_000); // free_balance is still 10_000
 		assert_ok!(Balances::transfer(RuntimeOrigin::signed(1), 2, 10_000)); // success in transfer of 10_000 free balance
 	});
@@ -219,10 +388,21 @@ fn unlocked_after_all_locked() {
 fn unlocked_after_some_locked_and_then_extended_same() {
 	new_test_ext().execute_with(|| {
 		assert_eq!(Balances::free_balance(1), 10_000);
-		assert_ok!(LockableCurrency::lock_capital(RuntimeOrigin::signed(1), 100));
-		assert_ok!(LockableCurrency::extend_lock(RuntimeOrigin::signed(1), 100));
+		assert_ok!(LockableCurrency::lock_capital(
+			RuntimeOrigin::signed(1),
+			ID,
+			100,
+			LockReasons::All,
+			None
+		));
+		assert_ok!(LockableCurrency::extend_lock(
+			RuntimeOrigin::signed(1),
+			ID,
+			100,
+			LockReasons::All
+		));
 		assert_ok!(LockableCurrency::unlock_all(RuntimeOrigin::signed(1)));
-		System::assert_last_event(Event::Unlocked { user: 1 }.into());
+		System::assert_last_event(Event::UnlockedAll { user: 1 }.into());
 		assert_eq!(Balances::free_balance(1), 10_000); // free_balance is still 10_000
 		assert_ok!(Balances::transfer(RuntimeOrigin::signed(1), 2, 10_000)); // success in transfer of 10_000 free balance
 	});
@@ -236,10 +416,19 @@ fn unlocked_after_some_locked_and_then_extended_same() {
 fn unlocked_after_some_locked_and_then_extended_less() {
 	new_test_ext().execute_with(|| {
 		assert_eq!(Balances::free_balance(1), 10_000);
-		assert_ok!(LockableCurrency::lock_capital(RuntimeOrigin::signed(1), 100));
-		assert_ok!(LockableCurrency::extend_lock(RuntimeOrigin::signed(1), 99));
+		assert_ok!(LockableCurrency::lock_capital(
+			RuntimeOrigin::signed(1),
+			ID,
+			100,
+			LockReasons::All,
+			None
+		));
+		assert_noop!(
+			LockableCurrency::extend_lock(RuntimeOrigin::signed(1), ID, 99, LockReasons::All),
+			Error::<Test>::LockNotExtended
+		);
 		assert_ok!(LockableCurrency::unlock_all(RuntimeOrigin::signed(1)));
-		System::assert_last_event(Event::Unlocked { user: 1 }.into());
+		System::assert_last_event(Event::UnlockedAll { user: 1 }.into());
 		assert_eq!(Balances::free_balance(1), 10_000); // free_balance is still 10_000
 		assert_ok!(Balances::transfer(RuntimeOrigin::signed(1), 2, 10_000)); // success in transfer of 10_000 free balance
 	});
@@ -253,11 +442,770 @@ fn unlocked_after_some_locked_and_then_extended_less() {
 fn unlocked_after_some_locked_and_then_extended_more() {
 	new_test_ext().execute_with(|| {
 		assert_eq!(Balances::free_balance(1), 10_000);
-		assert_ok!(LockableCurrency::lock_capital(RuntimeOrigin::signed(1), 100));
-		assert_ok!(LockableCurrency::extend_lock(RuntimeOrigin::signed(1), 101));
+		assert_ok!(LockableCurrency::lock_capital(
+			RuntimeOrigin::signed(1),
+			ID,
+			100,
+			LockReasons::All,
+			None
+		));
+		assert_ok!(LockableCurrency::extend_lock(
+			RuntimeOrigin::signed(1),
+			ID,
+			101,
+			LockReasons::All
+		));
 		assert_ok!(LockableCurrency::unlock_all(RuntimeOrigin::signed(1)));
-		System::assert_last_event(Event::Unlocked { user: 1 }.into());
+		System::assert_last_event(Event::UnlockedAll { user: 1 }.into());
 		assert_eq!(Balances::free_balance(1), 10_000); // free_balance is still 10_000
 		assert_ok!(Balances::transfer(RuntimeOrigin::signed(1), 2, 10_000)); // success in transfer of 10_000 free balance
 	});
 }
+
+//=====named locks=====
+
+/// Here,
+/// 🧍 -> lock 100 (id 1)
+/// 🧍 -> lock 200 (id 2)
+///
+/// take max(100, 200) as locked amount
+#[test]
+fn lock_under_several_ids() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LockableCurrency::lock_capital(
+			RuntimeOrigin::signed(1),
+			ID,
+			100,
+			LockReasons::All,
+			None
+		));
+		assert_ok!(LockableCurrency::lock_capital(
+			RuntimeOrigin::signed(1),
+			OTHER_ID,
+			200,
+			LockReasons::Misc,
+			Some(10)
+		));
+		System::assert_last_event(
+			Event::Locked {
+				user: 1,
+				id: OTHER_ID,
+				amount: 200,
+				reasons: LockReasons::Misc,
+				expires_at: Some(10),
+			}
+			.into(),
+		);
+		assert_eq!(
+			LockableCurrency::locks(1).into_inner(),
+			vec![
+				LockInfo {
+					id: ID,
+					slot: 0,
+					amount: 100,
+					reasons: LockReasons::All,
+					created_at: 1,
+					expires_at: None
+				},
+				LockInfo {
+					id: OTHER_ID,
+					slot: 1,
+					amount: 200,
+					reasons: LockReasons::Misc,
+					created_at: 1,
+					expires_at: Some(10)
+				},
+			]
+		);
+		assert_noop!(
+			Balances::transfer(RuntimeOrigin::signed(1), 2, 9_801), // fail in transfer of (10_000 - 199)
+			pallet_balances::Error::<Test, _>::LiquidityRestrictions
+		);
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(1), 2, 9_800)); // success in transfer of (10_000 - 200)
+	});
+}
+
+/// Here,
+/// 🧍 -> lock 500 (id 1)
+/// 🧍 -> lock 100 (id 1)
+///
+/// the lock is replaced, unlike `extend_lock`
+#[test]
+fn lock_replaces_existing_lock() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LockableCurrency::lock_capital(
+			RuntimeOrigin::signed(1),
+			ID,
+			500,
+			LockReasons::All,
+			Some(10)
+		));
+		assert_ok!(LockableCurrency::lock_capital(
+			RuntimeOrigin::signed(1),
+			ID,
+			100,
+			LockReasons::All,
+			None
+		));
+		assert_eq!(
+			LockableCurrency::locks(1).into_inner(),
+			vec![LockInfo {
+				id: ID,
+				slot: 0,
+				amount: 100,
+				reasons: LockReasons::All,
+				created_at: 1,
+				expires_at: None
+			}]
+		);
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(1), 2, 9_900)); // success in transfer of (10_000 - 100)
+
+		// the replaced expiry is dropped
+		run_to_block(10);
+		assert_eq!(LockableCurrency::locks(1).len(), 1);
+	});
+}
+
+#[test]
+fn lock_fails_with_too_many_locks() {
+	new_test_ext().execute_with(|| {
+		for id in [*b"lock 1  ", *b"lock 2  ", *b"lock 3  "] {
+			assert_ok!(LockableCurrency::lock_capital(
+				RuntimeOrigin::signed(1),
+				id,
+				100,
+				LockReasons::All,
+				None
+			));
+		}
+		assert_noop!(
+			LockableCurrency::lock_capital(
+				RuntimeOrigin::signed(1),
+				*b"lock 4  ",
+				100,
+				LockReasons::All,
+				None
+			),
+			Error::<Test>::TooManyLocks
+		);
+
+		// an existing lock can still be replaced
+		assert_ok!(LockableCurrency::lock_capital(
+			RuntimeOrigin::signed(1),
+			*b"lock 3  ",
+			200,
+			LockReasons::All,
+			None
+		));
+	});
+}
+
+/// Here,
+/// 🧍 -> lock (id 1), lock (id 2)
+/// 🧍 -> unlock (id 1)
+/// 🧍 -> lock (id 3), which takes the freed slot
+#[test]
+fn lock_reuses_freed_slot() {
+	new_test_ext().execute_with(|| {
+		for id in [ID, OTHER_ID] {
+			assert_ok!(LockableCurrency::lock_capital(
+				RuntimeOrigin::signed(1),
+				id,
+				100,
+				LockReasons::All,
+				None
+			));
+		}
+		assert_eq!(balances_lock(1, named_lock_id(1)), Some(100));
+		assert_ok!(LockableCurrency::unlock(RuntimeOrigin::signed(1), ID));
+		assert_eq!(balances_lock(1, named_lock_id(0)), None);
+
+		assert_ok!(LockableCurrency::lock_capital(
+			RuntimeOrigin::signed(1),
+			*b"third   ",
+			300,
+			LockReasons::All,
+			None
+		));
+		let slots: Vec<_> = LockableCurrency::locks(1).iter().map(|l| l.slot).collect();
+		assert_eq!(slots, vec![1, 0]);
+		assert_eq!(balances_lock(1, named_lock_id(0)), Some(300));
+	});
+}
+
+#[test]
+fn lock_fails_with_expiry_not_in_future() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			LockableCurrency::lock_capital(
+				RuntimeOrigin::signed(1),
+				ID,
+				100,
+				LockReasons::All,
+				Some(1)
+			),
+			Error::<Test>::ExpiryNotInFuture
+		);
+	});
+}
+
+/// Here,
+/// 🧍 -> lock 100 (fee)
+/// 🧍 -> extend lock 150 (misc.)
+///
+/// take 150 as locked amount for all the reasons
+#[test]
+fn extend_lock_unions_reasons() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LockableCurrency::lock_capital(
+			RuntimeOrigin::signed(1),
+			ID,
+			100,
+			LockReasons::Fee,
+			Some(10)
+		));
+		assert_ok!(LockableCurrency::extend_lock(
+			RuntimeOrigin::signed(1),
+			ID,
+			150,
+			LockReasons::Misc
+		));
+		System::assert_last_event(
+			Event::ExtendedLock { user: 1, id: ID, amount: 150, reasons: LockReasons::All }.into(),
+		);
+		assert_eq!(
+			LockableCurrency::locks(1).into_inner(),
+			vec![LockInfo {
+				id: ID,
+				slot: 0,
+				amount: 150,
+				reasons: LockReasons::All,
+				created_at: 1,
+				expires_at: Some(10)
+			}]
+		);
+	});
+}
+
+/// Here,
+/// 🧍 -> lock 100 @ block 5
+#[test]
+fn lock_records_creation_block() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(5);
+		assert_ok!(LockableCurrency::lock_capital(
+			RuntimeOrigin::signed(1),
+			ID,
+			100,
+			LockReasons::All,
+			None
+		));
+		assert_eq!(LockableCurrency::locks(1)[0].created_at, 5);
+
+		// extending doesn't recreate the lock
+		System::set_block_number(6);
+		assert_ok!(LockableCurrency::extend_lock(
+			RuntimeOrigin::signed(1),
+			ID,
+			200,
+			LockReasons::All
+		));
+		assert_eq!(LockableCurrency::locks(1)[0].created_at, 5);
+	});
+}
+
+#[test]
+fn extend_lock_fails_for_missing_lock() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			LockableCurrency::extend_lock(RuntimeOrigin::signed(1), ID, 100, LockReasons::All),
+			Error::<Test>::LockNotFound
+		);
+	});
+}
+
+//=====unlock=====
+
+/// Here,
+/// 🧍 -> lock 100 (id 1)
+/// 🧍 -> lock 200 (id 2)
+/// 🧍 -> unlock (id 2)
+#[test]
+fn unlock_removes_only_named_lock() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LockableCurrency::lock_capital(
+			RuntimeOrigin::signed(1),
+			ID,
+			100,
+			LockReasons::All,
+			None
+		));
+		assert_ok!(LockableCurrency::lock_capital(
+			RuntimeOrigin::signed(1),
+			OTHER_ID,
+			200,
+			LockReasons::All,
+			Some(10)
+		));
+		assert_ok!(LockableCurrency::unlock(RuntimeOrigin::signed(1), OTHER_ID));
+		System::assert_last_event(Event::Unlocked { user: 1, id: OTHER_ID }.into());
+		assert_eq!(LockableCurrency::locks(1).len(), 1);
+		assert_eq!(crate::Expiries::<Test>::iter().count(), 0);
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(1), 2, 9_900)); // success in transfer of (10_000 - 100)
+	});
+}
+
+#[test]
+fn unlock_fails_for_missing_lock() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			LockableCurrency::unlock(RuntimeOrigin::signed(1), ID),
+			Error::<Test>::LockNotFound
+		);
+		assert_ok!(LockableCurrency::lock_capital(
+			RuntimeOrigin::signed(1),
+			ID,
+			100,
+			LockReasons::All,
+			None
+		));
+		assert_noop!(
+			LockableCurrency::unlock(RuntimeOrigin::signed(1), OTHER_ID),
+			Error::<Test>::LockNotFound
+		);
+	});
+}
+
+#[test]
+fn unlock_all_removes_all_locks() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LockableCurrency::lock_capital(
+			RuntimeOrigin::signed(1),
+			ID,
+			100,
+			LockReasons::All,
+			Some(10)
+		));
+		assert_ok!(LockableCurrency::lock_capital(
+			RuntimeOrigin::signed(1),
+			OTHER_ID,
+			200,
+			LockReasons::All,
+			Some(20)
+		));
+		assert_ok!(LockableCurrency::unlock_all(RuntimeOrigin::signed(1)));
+		assert!(!Locks::<Test>::contains_key(1));
+		assert_eq!(crate::Expiries::<Test>::iter().count(), 0);
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(1), 2, 10_000)); // success in transfer of 10_000 free balance
+	});
+}
+
+/// Here,
+/// 🧍 -> locked 1_000 under the legacy lock (set before the named locks)
+/// 🧍 -> unlock all
+#[test]
+fn unlock_all_removes_legacy_lock() {
+	new_test_ext().execute_with(|| {
+		Balances::set_lock(LEGACY_LOCK_ID, &1, 1_000, WithdrawReasons::all());
+
+		assert_ok!(LockableCurrency::unlock_all(RuntimeOrigin::signed(1)));
+		assert_eq!(balances_lock(1, LEGACY_LOCK_ID), None);
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(1), 2, 10_000)); // success in transfer of 10_000 free balance
+	});
+}
+
+/// Here,
+/// 🧍 -> locked 1_000 by another pallet
+/// 🧍 -> lock, extend, unlock & unlock all under the id of that lock
+#[test]
+fn foreign_lock_is_untouched() {
+	new_test_ext().execute_with(|| {
+		Balances::set_lock(FOREIGN_ID, &1, 1_000, WithdrawReasons::all());
+
+		assert_noop!(
+			LockableCurrency::unlock(RuntimeOrigin::signed(1), FOREIGN_ID),
+			Error::<Test>::LockNotFound
+		);
+		assert_noop!(
+			LockableCurrency::extend_lock(
+				RuntimeOrigin::signed(1),
+				FOREIGN_ID,
+				2_000,
+				LockReasons::All
+			),
+			Error::<Test>::LockNotFound
+		);
+
+		// the named lock doesn't replace the foreign lock, nor is the latter removed with it
+		assert_ok!(LockableCurrency::lock_capital(
+			RuntimeOrigin::signed(1),
+			FOREIGN_ID,
+			1,
+			LockReasons::All,
+			None
+		));
+		assert_eq!(balances_lock(1, FOREIGN_ID), Some(1_000));
+		assert_eq!(balances_lock(1, named_lock_id(0)), Some(1));
+		assert_ok!(LockableCurrency::unlock(RuntimeOrigin::signed(1), FOREIGN_ID));
+		assert_ok!(LockableCurrency::unlock_all(RuntimeOrigin::signed(1)));
+		assert_eq!(balances_lock(1, FOREIGN_ID), Some(1_000));
+		assert_noop!(
+			Balances::transfer(RuntimeOrigin::signed(1), 2, 9_001), // fail in transfer of (10_000 - 999)
+			pallet_balances::Error::<Test, _>::LiquidityRestrictions
+		);
+	});
+}
+
+/// Here,
+/// 🧍 -> lock 1_000
+/// 🧍 -> unlock 400
+#[test]
+fn unlock_partial_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LockableCurrency::lock_capital(
+			RuntimeOrigin::signed(1),
+			ID,
+			1_000,
+			LockReasons::Misc,
+			Some(10)
+		));
+		assert_ok!(LockableCurrency::unlock_partial(RuntimeOrigin::signed(1), ID, 400));
+		System::assert_last_event(
+			Event::PartiallyUnlocked { user: 1, id: ID, amount: 400, remaining: 600 }.into(),
+		);
+		assert_eq!(
+			LockableCurrency::locks(1).into_inner(),
+			vec![LockInfo {
+				id: ID,
+				slot: 0,
+				amount: 600,
+				reasons: LockReasons::Misc,
+				created_at: 1,
+				expires_at: Some(10)
+			}]
+		);
+		assert_noop!(
+			Balances::transfer(RuntimeOrigin::signed(1), 2, 9_401), // fail in transfer of (10_000 - 599)
+			pallet_balances::Error::<Test, _>::LiquidityRestrictions
+		);
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(1), 2, 9_400)); // success in transfer of (10_000 - 600)
+	});
+}
+
+/// Here,
+/// 🧍 -> lock 1_000 till block 10
+/// 🧍 -> unlock 1_000
+#[test]
+fn unlock_partial_removes_drained_lock() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LockableCurrency::lock_capital(
+			RuntimeOrigin::signed(1),
+			ID,
+			1_000,
+			LockReasons::All,
+			Some(10)
+		));
+		assert_ok!(LockableCurrency::unlock_partial(RuntimeOrigin::signed(1), ID, 1_000));
+		System::assert_last_event(
+			Event::PartiallyUnlocked { user: 1, id: ID, amount: 1_000, remaining: 0 }.into(),
+		);
+		assert!(!Locks::<Test>::contains_key(1));
+		assert_eq!(crate::Expiries::<Test>::iter().count(), 0);
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(1), 2, 10_000)); // success in transfer of 10_000 free balance
+	});
+}
+
+#[test]
+fn unlock_partial_fails() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			LockableCurrency::unlock_partial(RuntimeOrigin::signed(1), ID, 100),
+			Error::<Test>::LockNotFound
+		);
+		assert_ok!(LockableCurrency::lock_capital(
+			RuntimeOrigin::signed(1),
+			ID,
+			100,
+			LockReasons::All,
+			None
+		));
+		assert_noop!(
+			LockableCurrency::unlock_partial(RuntimeOrigin::signed(1), ID, 101),
+			Error::<Test>::InsufficientLockedAmount
+		);
+		assert_noop!(
+			LockableCurrency::unlock_partial(RuntimeOrigin::signed(1), VESTING_ID, 100),
+			Error::<Test>::ReservedLockId
+		);
+	});
+}
+
+/// Here,
+/// 🧍 -> locked 1_000 by another pallet
+/// 🧍 -> lock 100 & unlock partially under the id of that lock
+#[test]
+fn unlock_partial_leaves_foreign_lock() {
+	new_test_ext().execute_with(|| {
+		Balances::set_lock(FOREIGN_ID, &1, 1_000, WithdrawReasons::all());
+		assert_noop!(
+			LockableCurrency::unlock_partial(RuntimeOrigin::signed(1), FOREIGN_ID, 1_000),
+			Error::<Test>::LockNotFound
+		);
+
+		assert_ok!(LockableCurrency::lock_capital(
+			RuntimeOrigin::signed(1),
+			FOREIGN_ID,
+			100,
+			LockReasons::All,
+			None
+		));
+		assert_ok!(LockableCurrency::unlock_partial(RuntimeOrigin::signed(1), FOREIGN_ID, 60));
+		assert_eq!(balances_lock(1, FOREIGN_ID), Some(1_000));
+		assert_eq!(balances_lock(1, named_lock_id(0)), Some(40));
+		assert_ok!(LockableCurrency::unlock_partial(RuntimeOrigin::signed(1), FOREIGN_ID, 40));
+		assert_eq!(balances_lock(1, FOREIGN_ID), Some(1_000));
+		assert_eq!(balances_lock(1, named_lock_id(0)), None);
+	});
+}
+
+//=====expiry=====
+
+/// Here,
+/// 🧍 -> lock 1_000 till block 3
+#[test]
+fn lock_expires_on_initialize() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LockableCurrency::lock_capital(
+			RuntimeOrigin::signed(1),
+			ID,
+			1_000,
+			LockReasons::All,
+			Some(3)
+		));
+
+		run_to_block(2);
+		assert_eq!(LockableCurrency::locks(1).len(), 1);
+		assert_noop!(
+			Balances::transfer(RuntimeOrigin::signed(1), 2, 10_000), // fail in transfer of 10_000 free balance
+			pallet_balances::Error::<Test, _>::LiquidityRestrictions
+		);
+
+		run_to_block(3);
+		System::assert_last_event(Event::LockExpired { user: 1, id: ID }.into());
+		assert!(!Locks::<Test>::contains_key(1));
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(1), 2, 10_000)); // success in transfer of 10_000 free balance
+	});
+}
+
+/// Here,
+/// 🧍 -> lock 100 till block 3
+/// 🧍 -> lock 100 till block 5 (same id)
+#[test]
+fn relocked_lock_expires_at_new_expiry() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LockableCurrency::lock_capital(
+			RuntimeOrigin::signed(1),
+			ID,
+			100,
+			LockReasons::All,
+			Some(3)
+		));
+		assert_ok!(LockableCurrency::lock_capital(
+			RuntimeOrigin::signed(1),
+			ID,
+			100,
+			LockReasons::All,
+			Some(5)
+		));
+
+		run_to_block(4);
+		assert_eq!(LockableCurrency::locks(1).len(), 1);
+
+		run_to_block(5);
+		assert!(!Locks::<Test>::contains_key(1));
+	});
+}
+
+/// Here,
+/// 🧍 -> 3 locks till block 3
+///
+/// 2 (`MaxExpiriesPerBlock`) locks are removed in `on_initialize` & the rest in `on_idle`
+#[test]
+fn expired_locks_beyond_limit_are_swept_on_idle() {
+	new_test_ext().execute_with(|| {
+		for id in [*b"lock 1  ", *b"lock 2  ", *b"lock 3  "] {
+			assert_ok!(LockableCurrency::lock_capital(
+				RuntimeOrigin::signed(1),
+				id,
+				100,
+				LockReasons::All,
+				Some(3)
+			));
+		}
+
+		run_to_block(3);
+		assert_eq!(LockableCurrency::locks(1).len(), 1);
+		assert_eq!(SweepCursor::<Test>::get(), 3);
+
+		// no weight left
+		assert_eq!(LockableCurrency::on_idle(3, Weight::zero()), Weight::zero());
+		assert_eq!(LockableCurrency::locks(1).len(), 1);
+
+		assert!(LockableCurrency::on_idle(3, Weight::MAX).all_gt(Weight::zero()));
+		assert!(!Locks::<Test>::contains_key(1));
+		assert_eq!(SweepCursor::<Test>::get(), 4);
+	});
+}
+
+//=====vesting=====
+
+/// Here, (genesis)
+/// 🧍 -> vesting 5_000 @ 100 per block from block 0, cliff at block 10
+#[test]
+fn genesis_vesting_locks_unvested_tokens() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(LockableCurrency::vesting(3).unwrap().locked_at(1), 5_000);
+		assert_noop!(
+			Balances::transfer(RuntimeOrigin::signed(3), 2, 5_001), // fail in transfer of (10_000 - 4_999)
+			pallet_balances::Error::<Test, _>::LiquidityRestrictions
+		);
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(3), 2, 5_000)); // success in transfer of (10_000 - 5_000)
+	});
+}
+
+#[test]
+fn vest_before_cliff_keeps_all_locked() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(9);
+		assert_ok!(LockableCurrency::vest(RuntimeOrigin::signed(3)));
+		System::assert_last_event(Event::Vested { user: 3, locked: 5_000 }.into());
+		assert_noop!(
+			Balances::transfer(RuntimeOrigin::signed(3), 2, 5_001),
+			pallet_balances::Error::<Test, _>::LiquidityRestrictions
+		);
+	});
+}
+
+/// Here,
+/// 🧍 -> vest @ block 20 i.e. 20 * 100 vested
+#[test]
+fn vest_after_cliff_unlocks_linearly() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(20);
+		assert_ok!(LockableCurrency::vest(RuntimeOrigin::signed(3)));
+		System::assert_last_event(Event::Vested { user: 3, locked: 3_000 }.into());
+		assert_noop!(
+			Balances::transfer(RuntimeOrigin::signed(3), 2, 7_001), // fail in transfer of (10_000 - 2_999)
+			pallet_balances::Error::<Test, _>::LiquidityRestrictions
+		);
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(3), 2, 7_000)); // success in transfer of (10_000 - 3_000)
+	});
+}
+
+#[test]
+fn vest_completes_schedule() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(50);
+		assert_ok!(LockableCurrency::vest(RuntimeOrigin::signed(3)));
+		System::assert_has_event(Event::Vested { user: 3, locked: 0 }.into());
+		System::assert_last_event(Event::VestingCompleted { user: 3 }.into());
+		assert!(!Vesting::<Test>::contains_key(3));
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(3), 2, 10_000)); // success in transfer of 10_000 free balance
+
+		assert_noop!(LockableCurrency::vest(RuntimeOrigin::signed(3)), Error::<Test>::NotVesting);
+	});
+}
+
+/// Here,
+/// 🧍 (1) -> vested transfer 1_000 @ 10 per block from block 5 to 🧍 (2)
+#[test]
+fn vested_transfer_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LockableCurrency::vested_transfer(RuntimeOrigin::signed(1), 2, 1_000, 5, 5, 10));
+		System::assert_last_event(
+			Event::VestingScheduleCreated {
+				funder: 1,
+				beneficiary: 2,
+				total: 1_000,
+				start: 5,
+				cliff: 5,
+				per_block: 10,
+			}
+			.into(),
+		);
+		assert_eq!(Balances::free_balance(1), 9_000);
+		assert_eq!(Balances::free_balance(2), 11_000);
+		assert_noop!(
+			Balances::transfer(RuntimeOrigin::signed(2), 1, 10_001), // fail in transfer of (11_000 - 999)
+			pallet_balances::Error::<Test, _>::LiquidityRestrictions
+		);
+
+		System::set_block_number(55);
+		assert_ok!(LockableCurrency::vest(RuntimeOrigin::signed(2)));
+		System::assert_last_event(Event::Vested { user: 2, locked: 500 }.into());
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(2), 1, 10_500)); // success in transfer of (11_000 - 500)
+	});
+}
+
+#[test]
+fn vested_transfer_fails() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			LockableCurrency::vested_transfer(RuntimeOrigin::signed(1), 2, 99, 5, 5, 10),
+			Error::<Test>::AmountLow
+		);
+		// above the existential deposit, but below the `MinVestedTransfer`
+		assert_noop!(
+			LockableCurrency::vested_transfer(RuntimeOrigin::signed(1), 2, 499, 5, 5, 10),
+			Error::<Test>::AmountLow
+		);
+		assert_noop!(
+			LockableCurrency::vested_transfer(RuntimeOrigin::signed(1), 2, 1_000, 5, 5, 0),
+			Error::<Test>::InvalidSchedule
+		);
+		assert_noop!(
+			LockableCurrency::vested_transfer(RuntimeOrigin::signed(1), 2, 1_000, 5, 4, 10),
+			Error::<Test>::InvalidSchedule
+		);
+		assert_noop!(
+			LockableCurrency::vested_transfer(RuntimeOrigin::signed(1), 3, 1_000, 5, 5, 10),
+			Error::<Test>::VestingScheduleExists
+		);
+	});
+}
+
+#[test]
+fn vesting_lock_id_is_reserved() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			LockableCurrency::lock_capital(
+				RuntimeOrigin::signed(3),
+				VESTING_ID,
+				0,
+				LockReasons::All,
+				None
+			),
+			Error::<Test>::ReservedLockId
+		);
+		assert_noop!(
+			LockableCurrency::extend_lock(
+				RuntimeOrigin::signed(3),
+				VESTING_ID,
+				0,
+				LockReasons::All
+			),
+			Error::<Test>::ReservedLockId
+		);
+		assert_noop!(
+			LockableCurrency::unlock(RuntimeOrigin::signed(3), VESTING_ID),
+			Error::<Test>::ReservedLockId
+		);
+
+		// the vesting lock isn't a named lock
+		assert_ok!(LockableCurrency::unlock_all(RuntimeOrigin::signed(3)));
+		assert_noop!(
+			Balances::transfer(RuntimeOrigin::signed(3), 2, 5_001),
+			pallet_balances::Error::<Test, _>::LiquidityRestrictions
+		);
+	});
+}
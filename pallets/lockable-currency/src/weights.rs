@@ -1,30 +1,8 @@
-
-//! Autogenerated weights for pallet_lockable_currency
+//! Weights for pallet_lockable_currency
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
-//! DATE: 2023-04-06, STEPS: `50`, REPEAT: `20`, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! WORST CASE MAP SIZE: `1000000`
-//! HOSTNAME: `Alexs-MacBook-Pro-2.local`, CPU: `<UNKNOWN>`
-//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
-
-// Executed Command:
-// ../../target/release/node-template
-// benchmark
-// pallet
-// --chain
-// dev
-// --pallet
-// pallet_lockable_currency
-// --extrinsic
-// *
-// --steps=50
-// --repeat=20
-// --execution=wasm
-// --wasm-execution=compiled
-// --output
-// pallets/template/src/weights.rs
-// --template
-// ../../.maintain/frame-weight-template.hbs
+//! NOTE: Not generated by the benchmark CLI yet. The storage accesses follow the worst cases set
+//! up in `src/benchmarking.rs`, but the execution times are placeholders, not measurements.
+//! Regenerate via `./scripts/benchmark.sh lockable-currency` before relying on them.
 
 #![cfg_attr(rustfmt, rustfmt_skip)]
 #![allow(unused_parens)]
@@ -36,82 +14,235 @@ use core::marker::PhantomData;
 /// Weight functions needed for pallet_lockable_currency.
 pub trait WeightInfo {
 	fn lock_capital() -> Weight;
-	fn extend_capital() -> Weight;
-	fn unlock_all() -> Weight;
+	fn extend_lock() -> Weight;
+	fn unlock() -> Weight;
+	fn unlock_all(l: u32, ) -> Weight;
+	fn expire_lock() -> Weight;
+	fn sweep_block() -> Weight;
+	fn vested_transfer() -> Weight;
+	fn vest() -> Weight;
+	fn unlock_partial() -> Weight;
 }
 
 /// Weights for pallet_lockable_currency using the Substrate node and recommended hardware.
 pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
-	/// Storage: LockableCurrency Something (r:0 w:1)
-	/// Proof: LockableCurrency Something (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: LockableCurrency Locks (r:1 w:1)
+	/// Proof: LockableCurrency Locks (max_values: None, max_size: Some(389), added: 2864, mode: MaxEncodedLen)
+	/// Storage: Balances Locks (r:1 w:1)
+	/// Proof: Balances Locks (max_values: None, max_size: Some(1299), added: 3774, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: LockableCurrency Expiries (r:0 w:2)
+	/// Proof: LockableCurrency Expiries (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
 	fn lock_capital() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `0`
-		//  Estimated: `0`
-		// Minimum execution time: 8_000_000 picoseconds.
-		Weight::from_parts(9_000_000, 0)
-			.saturating_add(T::DbWeight::get().writes(1_u64))
-	}
-	/// Storage: LockableCurrency Something (r:1 w:1)
-	/// Proof: LockableCurrency Something (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
-	fn extend_capital() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `32`
-		//  Estimated: `1489`
-		// Minimum execution time: 6_000_000 picoseconds.
-		Weight::from_parts(6_000_000, 1489)
-			.saturating_add(T::DbWeight::get().reads(1_u64))
-			.saturating_add(T::DbWeight::get().writes(1_u64))
+		Weight::from_parts(33_000_000, 9241)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
 	}
-
-	/// Storage: LockableCurrency Something (r:1 w:1)
-	/// Proof: LockableCurrency Something (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
-	fn unlock_all() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `32`
-		//  Estimated: `1489`
-		// Minimum execution time: 6_000_000 picoseconds.
-		Weight::from_parts(6_000_000, 1489)
+	/// Storage: LockableCurrency Locks (r:1 w:1)
+	/// Proof: LockableCurrency Locks (max_values: None, max_size: Some(389), added: 2864, mode: MaxEncodedLen)
+	/// Storage: Balances Locks (r:1 w:1)
+	/// Proof: Balances Locks (max_values: None, max_size: Some(1299), added: 3774, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn extend_lock() -> Weight {
+		Weight::from_parts(31_000_000, 9241)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: LockableCurrency Locks (r:1 w:1)
+	/// Proof: LockableCurrency Locks (max_values: None, max_size: Some(389), added: 2864, mode: MaxEncodedLen)
+	/// Storage: Balances Locks (r:1 w:1)
+	/// Proof: Balances Locks (max_values: None, max_size: Some(1299), added: 3774, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: LockableCurrency Expiries (r:0 w:1)
+	/// Proof: LockableCurrency Expiries (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	fn unlock() -> Weight {
+		Weight::from_parts(32_000_000, 9241)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: LockableCurrency Locks (r:1 w:1)
+	/// Proof: LockableCurrency Locks (max_values: None, max_size: Some(389), added: 2864, mode: MaxEncodedLen)
+	/// Storage: Balances Locks (r:1 w:1)
+	/// Proof: Balances Locks (max_values: None, max_size: Some(1299), added: 3774, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: LockableCurrency Expiries (r:0 w:10)
+	/// Proof: LockableCurrency Expiries (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	/// The range of component `l` is `[1, 10]`.
+	fn unlock_all(l: u32, ) -> Weight {
+		Weight::from_parts(22_000_000, 9241)
+			.saturating_add(Weight::from_parts(7_500_000, 0).saturating_mul(l.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(l.into())))
+	}
+	/// Storage: LockableCurrency Expiries (r:1 w:1)
+	/// Proof: LockableCurrency Expiries (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	/// Storage: LockableCurrency Locks (r:1 w:1)
+	/// Proof: LockableCurrency Locks (max_values: None, max_size: Some(389), added: 2864, mode: MaxEncodedLen)
+	/// Storage: Balances Locks (r:1 w:1)
+	/// Proof: Balances Locks (max_values: None, max_size: Some(1299), added: 3774, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn expire_lock() -> Weight {
+		Weight::from_parts(31_000_000, 11784)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: LockableCurrency Expiries (r:1 w:0)
+	/// Proof: LockableCurrency Expiries (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	fn sweep_block() -> Weight {
+		Weight::from_parts(5_000_000, 2543)
 			.saturating_add(T::DbWeight::get().reads(1_u64))
-			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: LockableCurrency Vesting (r:1 w:1)
+	/// Proof: LockableCurrency Vesting (max_values: None, max_size: Some(88), added: 2563, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Balances Locks (r:1 w:1)
+	/// Proof: Balances Locks (max_values: None, max_size: Some(1299), added: 3774, mode: MaxEncodedLen)
+	fn vested_transfer() -> Weight {
+		Weight::from_parts(49_000_000, 11543)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: LockableCurrency Vesting (r:1 w:1)
+	/// Proof: LockableCurrency Vesting (max_values: None, max_size: Some(88), added: 2563, mode: MaxEncodedLen)
+	/// Storage: Balances Locks (r:1 w:1)
+	/// Proof: Balances Locks (max_values: None, max_size: Some(1299), added: 3774, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn vest() -> Weight {
+		Weight::from_parts(28_000_000, 8940)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: LockableCurrency Locks (r:1 w:1)
+	/// Proof: LockableCurrency Locks (max_values: None, max_size: Some(389), added: 2864, mode: MaxEncodedLen)
+	/// Storage: Balances Locks (r:1 w:1)
+	/// Proof: Balances Locks (max_values: None, max_size: Some(1299), added: 3774, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: LockableCurrency Expiries (r:0 w:1)
+	/// Proof: LockableCurrency Expiries (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	fn unlock_partial() -> Weight {
+		Weight::from_parts(32_000_000, 9241)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
 	}
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
-	/// Storage: LockableCurrency Something (r:0 w:1)
-	/// Proof: LockableCurrency Something (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: LockableCurrency Locks (r:1 w:1)
+	/// Proof: LockableCurrency Locks (max_values: None, max_size: Some(389), added: 2864, mode: MaxEncodedLen)
+	/// Storage: Balances Locks (r:1 w:1)
+	/// Proof: Balances Locks (max_values: None, max_size: Some(1299), added: 3774, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: LockableCurrency Expiries (r:0 w:2)
+	/// Proof: LockableCurrency Expiries (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
 	fn lock_capital() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `0`
-		//  Estimated: `0`
-		// Minimum execution time: 8_000_000 picoseconds.
-		Weight::from_parts(9_000_000, 0)
-			.saturating_add(RocksDbWeight::get().writes(1_u64))
-	}
-	/// Storage: LockableCurrency Something (r:1 w:1)
-	/// Proof: LockableCurrency Something (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
-	fn extend_capital() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `32`
-		//  Estimated: `1489`
-		// Minimum execution time: 6_000_000 picoseconds.
-		Weight::from_parts(6_000_000, 1489)
-			.saturating_add(RocksDbWeight::get().reads(1_u64))
-			.saturating_add(RocksDbWeight::get().writes(1_u64))
+		Weight::from_parts(33_000_000, 9241)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
 	}
-
-	/// Storage: LockableCurrency Something (r:1 w:1)
-	/// Proof: LockableCurrency Something (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
-	fn unlock_all() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `32`
-		//  Estimated: `1489`
-		// Minimum execution time: 6_000_000 picoseconds.
-		Weight::from_parts(6_000_000, 1489)
+	/// Storage: LockableCurrency Locks (r:1 w:1)
+	/// Proof: LockableCurrency Locks (max_values: None, max_size: Some(389), added: 2864, mode: MaxEncodedLen)
+	/// Storage: Balances Locks (r:1 w:1)
+	/// Proof: Balances Locks (max_values: None, max_size: Some(1299), added: 3774, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn extend_lock() -> Weight {
+		Weight::from_parts(31_000_000, 9241)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: LockableCurrency Locks (r:1 w:1)
+	/// Proof: LockableCurrency Locks (max_values: None, max_size: Some(389), added: 2864, mode: MaxEncodedLen)
+	/// Storage: Balances Locks (r:1 w:1)
+	/// Proof: Balances Locks (max_values: None, max_size: Some(1299), added: 3774, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: LockableCurrency Expiries (r:0 w:1)
+	/// Proof: LockableCurrency Expiries (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	fn unlock() -> Weight {
+		Weight::from_parts(32_000_000, 9241)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: LockableCurrency Locks (r:1 w:1)
+	/// Proof: LockableCurrency Locks (max_values: None, max_size: Some(389), added: 2864, mode: MaxEncodedLen)
+	/// Storage: Balances Locks (r:1 w:1)
+	/// Proof: Balances Locks (max_values: None, max_size: Some(1299), added: 3774, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: LockableCurrency Expiries (r:0 w:10)
+	/// Proof: LockableCurrency Expiries (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	/// The range of component `l` is `[1, 10]`.
+	fn unlock_all(l: u32, ) -> Weight {
+		Weight::from_parts(22_000_000, 9241)
+			.saturating_add(Weight::from_parts(7_500_000, 0).saturating_mul(l.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(l.into())))
+	}
+	/// Storage: LockableCurrency Expiries (r:1 w:1)
+	/// Proof: LockableCurrency Expiries (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	/// Storage: LockableCurrency Locks (r:1 w:1)
+	/// Proof: LockableCurrency Locks (max_values: None, max_size: Some(389), added: 2864, mode: MaxEncodedLen)
+	/// Storage: Balances Locks (r:1 w:1)
+	/// Proof: Balances Locks (max_values: None, max_size: Some(1299), added: 3774, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn expire_lock() -> Weight {
+		Weight::from_parts(31_000_000, 11784)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: LockableCurrency Expiries (r:1 w:0)
+	/// Proof: LockableCurrency Expiries (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	fn sweep_block() -> Weight {
+		Weight::from_parts(5_000_000, 2543)
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
-			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
-
+	/// Storage: LockableCurrency Vesting (r:1 w:1)
+	/// Proof: LockableCurrency Vesting (max_values: None, max_size: Some(88), added: 2563, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Balances Locks (r:1 w:1)
+	/// Proof: Balances Locks (max_values: None, max_size: Some(1299), added: 3774, mode: MaxEncodedLen)
+	fn vested_transfer() -> Weight {
+		Weight::from_parts(49_000_000, 11543)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: LockableCurrency Vesting (r:1 w:1)
+	/// Proof: LockableCurrency Vesting (max_values: None, max_size: Some(88), added: 2563, mode: MaxEncodedLen)
+	/// Storage: Balances Locks (r:1 w:1)
+	/// Proof: Balances Locks (max_values: None, max_size: Some(1299), added: 3774, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn vest() -> Weight {
+		Weight::from_parts(28_000_000, 8940)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: LockableCurrency Locks (r:1 w:1)
+	/// Proof: LockableCurrency Locks (max_values: None, max_size: Some(389), added: 2864, mode: MaxEncodedLen)
+	/// Storage: Balances Locks (r:1 w:1)
+	/// Proof: Balances Locks (max_values: None, max_size: Some(1299), added: 3774, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: LockableCurrency Expiries (r:0 w:1)
+	/// Proof: LockableCurrency Expiries (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	fn unlock_partial() -> Weight {
+		Weight::from_parts(32_000_000, 9241)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
 }
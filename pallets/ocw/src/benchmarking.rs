@@ -0,0 +1,185 @@
+//! Benchmarking setup for pallet-ocw
+#![cfg(feature = "runtime-benchmarks")]
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as OCW;
+use frame_benchmarking::v2::*;
+use frame_support::sp_runtime::RuntimeAppPublic;
+use frame_system::RawOrigin;
+use sp_std::vec;
+
+const SEED: u32 = 0;
+const PAIR: PairId = 0;
+
+fn price() -> FixedU128 {
+	FixedU128::saturating_from_integer(100u32)
+}
+
+/// A feed with the max. no. of endpoints & all its fields of the max. length.
+fn max_feed<T: Config>() -> FeedInfo<T> {
+	let field = || -> BoundedVec<u8, T::MaxSourceLen> {
+		vec![b'a'; T::MaxSourceLen::get() as usize]
+			.try_into()
+			.expect("of the max. length; qed")
+	};
+	FeedInfo {
+		source: SourceConfig {
+			base: field(),
+			quote: field(),
+			endpoints: (0..T::MaxEndpoints::get())
+				.map(|_| field())
+				.collect::<Vec<_>>()
+				.try_into()
+				.expect("the max. no. of endpoints; qed"),
+			json_path: field(),
+		},
+		interval: 100u32.into(),
+		min_sources: 1,
+		max_deviation: Percent::from_percent(5),
+	}
+}
+
+/// `MaxMembers` oracle members (incl. `member`), of which all but `member` are needed to
+/// finalise the open round of `PAIR` & have submitted a price.
+fn setup_round<T: Config>(member: &T::AccountId) {
+	Feeds::<T>::insert(PAIR, max_feed::<T>());
+
+	let mut members: Vec<T::AccountId> =
+		(1..T::MaxMembers::get()).map(|i| account("member", i, SEED)).collect();
+	members.push(member.clone());
+	members.sort();
+	Members::<T>::put(BoundedVec::truncate_from(members.clone()));
+
+	let submissions: Vec<(T::AccountId, FixedU128)> = members
+		.into_iter()
+		.filter(|other| other != member)
+		.take(OCW::<T>::threshold().saturating_sub(1) as usize)
+		.map(|other| (other, price()))
+		.collect();
+	Submissions::<T>::insert(PAIR, BoundedVec::truncate_from(submissions));
+
+	// a full price history & a pending price, within `MaxPriceChange` of the submitted one
+	let now = frame_system::Pallet::<T>::block_number();
+	NextRoundAt::<T>::insert(PAIR, now);
+	Prices::<T>::mutate(PAIR, |history| {
+		for _ in 0..T::MaxPrices::get() {
+			history.push(PricePoint { price: price(), block_number: now });
+		}
+	});
+	PendingPrices::<T>::insert(PAIR, price());
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	// the submission finalising the round
+	#[benchmark]
+	fn submit_price() {
+		let caller: T::AccountId = account("member", 0, SEED);
+		setup_round::<T>(&caller);
+
+		#[extrinsic_call]
+		submit_price(RawOrigin::Signed(caller), PAIR, price());
+
+		assert!(Submissions::<T>::get(PAIR).is_empty());
+		assert_eq!(Prices::<T>::get(PAIR).points.len() as u32, T::MaxPrices::get());
+	}
+
+	// the submission finalising the round
+	#[benchmark]
+	fn submit_price_unsigned_with_signed_payload() {
+		let key =
+			<T::AuthorityId as AppCrypto<T::Public, T::Signature>>::RuntimeAppPublic::generate_pair(
+				None,
+			);
+		let public: T::Public =
+			<T::AuthorityId as AppCrypto<T::Public, T::Signature>>::GenericPublic::from(
+				key.clone(),
+			)
+			.into();
+		setup_round::<T>(&public.clone().into_account());
+
+		let price_payload = PricePayload {
+			pair: PAIR,
+			price: price(),
+			block_number: frame_system::Pallet::<T>::block_number(),
+			public,
+		};
+		let signature: T::Signature =
+			<T::AuthorityId as AppCrypto<T::Public, T::Signature>>::GenericSignature::from(
+				key.sign(&price_payload.encode()).expect("the key was generated above; qed"),
+			)
+			.into();
+
+		#[extrinsic_call]
+		submit_price_unsigned_with_signed_payload(RawOrigin::None, price_payload, signature);
+
+		assert!(Submissions::<T>::get(PAIR).is_empty());
+	}
+
+	#[benchmark]
+	fn set_feed() -> Result<(), BenchmarkError> {
+		let origin =
+			T::ConfigOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let feed = max_feed::<T>();
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, PAIR, feed.clone());
+
+		assert_eq!(Feeds::<T>::get(PAIR), Some(feed));
+		Ok(())
+	}
+
+	// along with a round & a full price history
+	#[benchmark]
+	fn remove_feed() -> Result<(), BenchmarkError> {
+		let origin =
+			T::ConfigOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		setup_round::<T>(&account("member", 0, SEED));
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, PAIR);
+
+		assert!(!Feeds::<T>::contains_key(PAIR));
+		assert!(Prices::<T>::get(PAIR).points.is_empty());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn add_member() -> Result<(), BenchmarkError> {
+		let origin =
+			T::ConfigOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let mut members: Vec<T::AccountId> =
+			(1..T::MaxMembers::get()).map(|i| account("member", i, SEED)).collect();
+		members.sort();
+		Members::<T>::put(BoundedVec::truncate_from(members));
+		let member: T::AccountId = account("member", 0, SEED);
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, member.clone());
+
+		assert!(OCW::<T>::is_member(&member));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn remove_member() -> Result<(), BenchmarkError> {
+		let origin =
+			T::ConfigOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let mut members: Vec<T::AccountId> =
+			(0..T::MaxMembers::get()).map(|i| account("member", i, SEED)).collect();
+		members.sort();
+		Members::<T>::put(BoundedVec::truncate_from(members));
+		let member: T::AccountId = account("member", 0, SEED);
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, member.clone());
+
+		assert!(!OCW::<T>::is_member(&member));
+		Ok(())
+	}
+
+	impl_benchmark_test_suite!(OCW, crate::mock::new_bench_ext(), crate::mock::Test);
+}
@@ -3,30 +3,33 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 use codec::{Decode, Encode};
 use frame_support::{
-	log,
+	ensure, log,
 	sp_runtime::{
 		offchain::{
 			http,
 			storage::{MutateStorageError, StorageValueRef},
 		},
-		traits::{BlockNumberProvider, Get, Zero},
+		traits::{
+			BlockNumberProvider, Get, IdentifyAccount, SaturatedConversion, Saturating, Zero,
+		},
 		transaction_validity::{
 			InvalidTransaction, TransactionPriority, TransactionValidity, ValidTransaction,
 		},
-		RuntimeDebug,
+		BoundedVec, FixedPointNumber, FixedU128, Percent, RuntimeDebug,
 	},
+	traits::Time,
 };
 use frame_system::{
 	limits::BlockLength,
 	offchain::{
 		AppCrypto, CreateSignedTransaction, SendSignedTransaction, SendUnsignedTransaction,
-		SignedPayload, Signer, SigningTypes, SubmitTransaction,
+		SignedPayload, Signer, SigningTypes,
 	},
 	pallet_prelude::BlockNumberFor,
 };
 use lite_json::{parse_json, JsonValue};
 use sp_core::{crypto::KeyTypeId, offchain::Duration};
-use sp_std::vec::Vec;
+use sp_std::{string::String, vec::Vec};
 
 #[cfg(test)]
 mod mock;
@@ -34,8 +37,30 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod migrations;
+pub mod weights;
+pub use weights::*;
+
 pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"demo");
 
+/// Offchain (persistent) storage key prefix of a node's local overrides of the on-chain price
+/// sources, see [`source_override_key`].
+pub const SOURCE_OVERRIDE_PREFIX: &[u8] = b"palletocw::price_source::";
+
+/// Symbol id of a price feed (asset pair).
+pub type PairId = u32;
+
+/// Offchain (persistent) storage key of a node's local override of the price source of `pair`.
+///
+/// Node operators can set it to a SCALE-encoded [`SourceConfig`] (e.g. via the
+/// `offchain_localStorageSet` RPC) to point their worker at other endpoints, e.g. a local HTTP
+/// stand-in.
+pub fn source_override_key(pair: PairId) -> Vec<u8> {
+	[SOURCE_OVERRIDE_PREFIX, &pair.encode()].concat()
+}
+
 pub mod crypto {
 	use super::KEY_TYPE;
 	use frame_support::sp_runtime::{
@@ -74,7 +99,11 @@ pub mod pallet {
 
 	use frame_system::pallet_prelude::*;
 
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
@@ -90,15 +119,303 @@ pub mod pallet {
 		#[pallet::constant]
 		type GracePeriod: Get<BlockNumberFor<Self>>;
 
-		#[pallet::constant]
-		type UnsignedInterval: Get<BlockNumberFor<Self>>;
-
-		/// Maximum number of prices.
+		/// Maximum number of prices (per feed).
 		#[pallet::constant]
 		type MaxPrices: Get<u32>;
 
 		/// to decide the transaction priority
 		type UnsignedPriority: Get<TransactionPriority>;
+
+		/// How the offchain workers submit the prices they fetch.
+		#[pallet::constant]
+		type SubmissionStrategy: Get<SubmissionStrategy>;
+
+		/// Origin allowed to configure the price feeds & the oracle members.
+		type ConfigOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Max. number of oracle members.
+		#[pallet::constant]
+		type MaxMembers: Get<u32>;
+
+		/// Share of the oracle members that must submit a price in a round for it to be
+		/// finalised (with the median of the submitted prices).
+		#[pallet::constant]
+		type Threshold: Get<Percent>;
+
+		/// Weight of a new price in the exponential moving average of a feed.
+		#[pallet::constant]
+		type EmaSmoothing: Get<Percent>;
+
+		/// Time provider (`pallet_timestamp`) for timestamping the price updates.
+		type TimeProvider: Time;
+
+		/// Blocks after the last accepted price of a feed from which it's stale, i.e. no longer
+		/// provided to other pallets.
+		#[pallet::constant]
+		type MaxStaleness: Get<BlockNumberFor<Self>>;
+
+		/// Max. change of the price of a feed from the previous one accepted in a round. Beyond it
+		/// the price is held till confirmed by the next round.
+		#[pallet::constant]
+		type MaxPriceChange: Get<Percent>;
+
+		/// Max. number of HTTP endpoints of a price source.
+		#[pallet::constant]
+		type MaxEndpoints: Get<u32>;
+
+		/// Max. length (in bytes) of an endpoint URL, an asset symbol or the JSON path.
+		#[pallet::constant]
+		type MaxSourceLen: Get<u32>;
+
+		/// Type representing the weight of this pallet
+		type WeightInfo: WeightInfo;
+	}
+
+	/// Where & how the offchain worker fetches the price of an asset pair.
+	///
+	/// The `{base}` & `{quote}` placeholders in the endpoints & the JSON path are replaced by the
+	/// symbols of the pair.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, TypeInfo, RuntimeDebug, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	pub struct SourceConfig<T: Config> {
+		/// Symbol of the priced asset, e.g. `BTC`.
+		pub base: BoundedVec<u8, T::MaxSourceLen>,
+		/// Symbol of the asset the price is expressed in, e.g. `USD`.
+		pub quote: BoundedVec<u8, T::MaxSourceLen>,
+		/// HTTP endpoints, queried concurrently.
+		pub endpoints: BoundedVec<BoundedVec<u8, T::MaxSourceLen>, T::MaxEndpoints>,
+		/// `.`-separated keys leading to the price in the JSON response, e.g. `{quote}`.
+		pub json_path: BoundedVec<u8, T::MaxSourceLen>,
+	}
+
+	impl<T: Config> SourceConfig<T> {
+		/// Whether the source has an endpoint & all its fields are UTF-8.
+		pub fn is_valid(&self) -> bool {
+			!self.endpoints.is_empty() &&
+				self.endpoints
+					.iter()
+					.map(|endpoint| endpoint.as_slice())
+					.chain([self.base.as_slice(), self.quote.as_slice(), self.json_path.as_slice()])
+					.all(|field| sp_std::str::from_utf8(field).is_ok())
+		}
+
+		/// The `template` with the `{base}` & `{quote}` placeholders replaced by the pair symbols.
+		pub fn fill(&self, template: &[u8]) -> Vec<u8> {
+			let mut filled = Vec::with_capacity(template.len());
+			let mut rest = template;
+			while !rest.is_empty() {
+				if let Some(tail) = rest.strip_prefix(b"{base}".as_slice()) {
+					filled.extend_from_slice(&self.base);
+					rest = tail;
+				} else if let Some(tail) = rest.strip_prefix(b"{quote}".as_slice()) {
+					filled.extend_from_slice(&self.quote);
+					rest = tail;
+				} else {
+					filled.push(rest[0]);
+					rest = &rest[1..];
+				}
+			}
+			filled
+		}
+	}
+
+	impl<T: Config> FeedInfo<T> {
+		/// Whether the source is valid & enough endpoints can agree.
+		pub fn is_valid(&self) -> bool {
+			self.source.is_valid() &&
+				(1..=self.source.endpoints.len() as u32).contains(&self.min_sources)
+		}
+	}
+
+	pub type PricePointOf<T> = PricePoint<<T as frame_system::Config>::BlockNumber>;
+	pub type MomentOf<T> = <<T as Config>::TimeProvider as Time>::Moment;
+	pub type PriceUpdateOf<T> = PriceUpdate<<T as frame_system::Config>::BlockNumber, MomentOf<T>>;
+
+	/// Ring buffer of the prices of a feed.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, TypeInfo, RuntimeDebug, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	pub struct PriceHistory<T: Config> {
+		/// The prices, in the order of recording till full.
+		pub points: BoundedVec<PricePointOf<T>, T::MaxPrices>,
+		/// Index of the oldest price (to be overwritten next) once full.
+		pub cursor: u32,
+		/// Exponential moving average of the prices, see `EmaSmoothing`.
+		pub ema: FixedU128,
+	}
+
+	impl<T: Config> Default for PriceHistory<T> {
+		fn default() -> Self {
+			Self { points: Default::default(), cursor: 0, ema: Default::default() }
+		}
+	}
+
+	impl<T: Config> PriceHistory<T> {
+		/// Record the price, overwriting the oldest one if full.
+		pub fn push(&mut self, point: PricePointOf<T>) {
+			self.ema = match self.points.is_empty() {
+				true => point.price,
+				false => {
+					let smoothing = T::EmaSmoothing::get();
+					FixedU128::from_inner(
+						(smoothing * point.price.into_inner())
+							.saturating_add(smoothing.left_from_one() * self.ema.into_inner()),
+					)
+				},
+			};
+
+			if let Err(point) = self.points.try_push(point) {
+				let len = self.points.len() as u32;
+				if let Some(oldest) = self.points.get_mut(self.cursor as usize) {
+					*oldest = point;
+					self.cursor = (self.cursor + 1) % len;
+				}
+			}
+		}
+
+		/// The latest price.
+		pub fn latest(&self) -> Option<&PricePointOf<T>> {
+			match self.cursor {
+				0 => self.points.last(),
+				cursor => self.points.get(cursor as usize - 1),
+			}
+		}
+
+		/// The prices, oldest first.
+		pub fn to_vec(&self) -> Vec<PricePointOf<T>> {
+			let (newer, older) = self.points.split_at(self.cursor as usize);
+			older.iter().chain(newer.iter()).cloned().collect()
+		}
+
+		/// The mean of the prices.
+		pub fn mean(&self) -> Option<FixedU128> {
+			if self.points.is_empty() {
+				return None;
+			}
+			let sum = self
+				.points
+				.iter()
+				.fold(0u128, |sum, point| sum.saturating_add(point.price.into_inner()));
+			Some(FixedU128::from_inner(sum / self.points.len() as u128))
+		}
+
+		/// The exponential moving average of the prices.
+		pub fn ema(&self) -> Option<FixedU128> {
+			(!self.points.is_empty()).then_some(self.ema)
+		}
+
+		/// The average of the prices over the `window` blocks till `now`, each weighted by the
+		/// number of blocks it was the latest one (the latest price till `now`).
+		///
+		/// NOTE: Only the recorded prices are considered, i.e. the window is cut to the oldest one.
+		pub fn twap(&self, now: T::BlockNumber, window: T::BlockNumber) -> Option<FixedU128> {
+			let start = now.saturating_sub(window);
+			let points = self.to_vec();
+
+			let (mut weighted, mut total) = (0u128, 0u128);
+			for (index, point) in points.iter().enumerate() {
+				let until = points.get(index + 1).map_or(now, |next| next.block_number);
+				let from = point.block_number.max(start);
+				if until > from {
+					let blocks = (until - from).saturated_into::<u128>();
+					weighted =
+						weighted.saturating_add(point.price.into_inner().saturating_mul(blocks));
+					total = total.saturating_add(blocks);
+				}
+			}
+
+			match total {
+				// no block elapsed since the latest price
+				0 => self.latest().map(|point| point.price),
+				total => Some(FixedU128::from_inner(weighted / total)),
+			}
+		}
+	}
+
+	/// A price feed of an asset pair.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, TypeInfo, RuntimeDebug, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	pub struct FeedInfo<T: Config> {
+		/// Where & how the offchain workers fetch the price (unless overridden locally, see
+		/// [`source_override_key`]).
+		pub source: SourceConfig<T>,
+		/// Blocks after a finalised round till the next one opens.
+		pub interval: T::BlockNumber,
+		/// Min. number of endpoints that must agree (i.e. answer within `max_deviation` of the
+		/// median price) for a price to be submitted.
+		pub min_sources: u32,
+		/// Max. deviation from the median price of the endpoints beyond which a price is dropped.
+		pub max_deviation: Percent,
+	}
+
+	/// (pair id, (base, quote) symbols, endpoints, JSON path, interval,
+	/// (min. sources, max. deviation)) of a feed.
+	pub type GenesisFeed<BlockNumber> =
+		(PairId, (Vec<u8>, Vec<u8>), Vec<Vec<u8>>, Vec<u8>, BlockNumber, (u32, Percent));
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// The price feeds.
+		pub feeds: Vec<GenesisFeed<T::BlockNumber>>,
+		/// The oracle members.
+		pub members: Vec<T::AccountId>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self {
+				feeds: vec![(
+					0,
+					(b"BTC".to_vec(), b"USD".to_vec()),
+					vec![b"https://min-api.cryptocompare.com/data/price?fsym={base}&tsyms={quote}"
+						.to_vec()],
+					b"{quote}".to_vec(),
+					128u32.into(),
+					(1, Percent::from_percent(5)),
+				)],
+				members: Default::default(),
+			}
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			for (
+				pair,
+				(base, quote),
+				endpoints,
+				json_path,
+				interval,
+				(min_sources, max_deviation),
+			) in self.feeds.iter()
+			{
+				let source = SourceConfig::<T> {
+					base: base.clone().try_into().expect("Base symbol too long"),
+					quote: quote.clone().try_into().expect("Quote symbol too long"),
+					endpoints: endpoints
+						.iter()
+						.map(|endpoint| endpoint.clone().try_into().expect("Endpoint too long"))
+						.collect::<Vec<_>>()
+						.try_into()
+						.expect("Too many endpoints"),
+					json_path: json_path.clone().try_into().expect("JSON path too long"),
+				};
+				let feed = FeedInfo::<T> {
+					source,
+					interval: *interval,
+					min_sources: *min_sources,
+					max_deviation: *max_deviation,
+				};
+				assert!(feed.is_valid(), "Invalid price feed");
+				Feeds::<T>::insert(pair, feed);
+			}
+
+			let mut members = self.members.clone();
+			members.sort();
+			members.dedup();
+			Members::<T>::put(BoundedVec::try_from(members).expect("Too many oracle members"));
+		}
 	}
 
 	#[pallet::hooks]
@@ -112,20 +429,17 @@ pub mod pallet {
 		/// be cases where some blocks are skipped, or for some the worker runs twice (re-orgs),
 		/// so the code should be able to handle that.
 		fn offchain_worker(block_number: T::BlockNumber) {
-			log::info!("Hello from pallet-ocw.");
-			// The entry point of your code called by offchain worker
-
-			let should_send = Self::choose_transaction_type(block_number);
+			let should_send = Self::choose_strategy(block_number);
+			log::debug!("Offchain worker at {:?}, strategy {:?}", block_number, should_send);
 			let res = match should_send {
-				TransactionType::Signed => Self::fetch_price_and_send_signed(),
-				TransactionType::UnsignedForAny => {
+				Some(SubmissionStrategy::Signed) => Self::fetch_price_and_send_signed(block_number),
+				Some(SubmissionStrategy::UnsignedForAny) => {
 					Self::fetch_price_and_send_unsigned_for_any_account(block_number)
 				},
-				TransactionType::UnsignedForAll => {
-					Self::fetch_price_and_send_unsigned_for_any_account(block_number)
+				Some(SubmissionStrategy::UnsignedForAll) => {
+					Self::fetch_price_and_send_unsigned_for_all_accounts(block_number)
 				},
-				TransactionType::Raw => Self::fetch_price_and_send_raw_unsigned(block_number),
-				TransactionType::None => Ok(()),
+				None => Ok(()),
 			};
 			if let Err(e) = res {
 				log::error!("Error: {}", e);
@@ -133,13 +447,52 @@ pub mod pallet {
 		}
 	}
 
+	/// The price feeds.
+	#[pallet::storage]
+	#[pallet::getter(fn feeds)]
+	pub type Feeds<T: Config> = StorageMap<_, Twox64Concat, PairId, FeedInfo<T>, OptionQuery>;
+
+	/// Recently finalised prices of a feed.
+	#[pallet::storage]
+	#[pallet::getter(fn prices)]
+	pub type Prices<T: Config> = StorageMap<_, Twox64Concat, PairId, PriceHistory<T>, ValueQuery>;
+
+	/// When the latest price of a feed was accepted.
 	#[pallet::storage]
-	#[pallet::getter(fn something)]
-	pub type Prices<T: Config> = StorageValue<_, BoundedVec<u32, T::MaxPrices>, ValueQuery>;
+	#[pallet::getter(fn last_update)]
+	pub type LastUpdates<T: Config> =
+		StorageMap<_, Twox64Concat, PairId, PriceUpdateOf<T>, OptionQuery>;
 
+	/// Price of a feed moving beyond `MaxPriceChange`, held till confirmed by the next round.
 	#[pallet::storage]
-	#[pallet::getter(fn next_unsigned_at)]
-	pub type NextUnsignedAt<T: Config> = StorageValue<_, BlockNumberFor<T>, ValueQuery>;
+	#[pallet::getter(fn pending_price)]
+	pub type PendingPrices<T: Config> = StorageMap<_, Twox64Concat, PairId, FixedU128, OptionQuery>;
+
+	/// Block from which the next round of a feed is open to submissions.
+	///
+	/// NOTE: A round not finalised within the interval of the feed expires along with its
+	/// submissions, i.e. the rounds restart every interval till one is finalised.
+	#[pallet::storage]
+	#[pallet::getter(fn next_round_at)]
+	pub type NextRoundAt<T: Config> =
+		StorageMap<_, Twox64Concat, PairId, BlockNumberFor<T>, ValueQuery>;
+
+	/// The oracle members (sorted), allowed to submit prices.
+	#[pallet::storage]
+	#[pallet::getter(fn members)]
+	pub type Members<T: Config> =
+		StorageValue<_, BoundedVec<T::AccountId, T::MaxMembers>, ValueQuery>;
+
+	/// Prices submitted by the oracle members in the open round (at `NextRoundAt`) of a feed.
+	#[pallet::storage]
+	#[pallet::getter(fn submissions)]
+	pub type Submissions<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		PairId,
+		BoundedVec<(T::AccountId, FixedU128), T::MaxMembers>,
+		ValueQuery,
+	>;
 
 	// Pallets use events to inform users when important changes are made.
 	// https://docs.substrate.io/main-docs/build/events-errors/
@@ -147,7 +500,19 @@ pub mod pallet {
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
 		/// New price added
-		NewPrice { price: u32, who_maybe: Option<T::AccountId> },
+		NewPrice { pair: PairId, price: FixedU128, who_maybe: Option<T::AccountId> },
+		/// Price feed added or updated
+		FeedUpdated { pair: PairId, feed: FeedInfo<T> },
+		/// Price feed removed
+		FeedRemoved { pair: PairId },
+		/// Price submitted by an oracle member in the open round of a feed
+		PriceSubmitted { pair: PairId, who: T::AccountId, price: FixedU128 },
+		/// Price moving beyond `MaxPriceChange` held till confirmed by the next round
+		PriceHeld { pair: PairId, price: FixedU128, previous: FixedU128 },
+		/// Oracle member added
+		MemberAdded { who: T::AccountId },
+		/// Oracle member removed
+		MemberRemoved { who: T::AccountId },
 	}
 
 	// Errors inform users that something went wrong.
@@ -159,6 +524,20 @@ pub mod pallet {
 		StorageOverflow,
 		/// error in calculating avg price
 		AvgPriceCalculationError,
+		/// Price Source Without Endpoints Or Not UTF-8 Or Invalid Min. Sources.
+		InvalidPriceFeed,
+		/// No Price Feed For The Pair.
+		UnknownPair,
+		/// Not An Oracle Member.
+		NotMember,
+		/// Already An Oracle Member.
+		AlreadyMember,
+		/// Too Many Oracle Members.
+		TooManyMembers,
+		/// Round Of The Feed Not Open Yet.
+		RoundNotOpen,
+		/// Price Already Submitted In The Round.
+		AlreadySubmitted,
 	}
 
 	// Dispatchable functions allows users to interact with the pallet and invoke state changes.
@@ -166,43 +545,29 @@ pub mod pallet {
 	// Dispatchable functions must be annotated with a weight and must return a DispatchResult.
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		/// An example dispatchable that takes a singles value as a parameter, writes the value to
-		/// storage and emits an event. This function must be dispatched by a signed extrinsic.
+		/// Submit the price of `pair` in its open round. Only callable by the oracle members.
+		///
+		/// The round is finalised once `Threshold` of the members submitted a price.
 		#[pallet::call_index(0)]
-		#[pallet::weight({10_000})]
-		pub fn submit_price(origin: OriginFor<T>, price: u32) -> DispatchResult {
-			let who = ensure_signed(origin)?;
-
-			Self::add_price(Some(who), price);
-
-			Ok(())
-		}
-
-		/// Here, although this call is unsigned, but still we need to specify the weight.
-		/// Otherwise, there won't be any limit to no. of unsigned txs added in a block.
-
-		/// Each unsigned tx has to go through the `validate_unsigned()` defined in the implementation
-		/// of `ValidateUnsigned` trait for the pallet.
-		#[pallet::call_index(1)]
-		#[pallet::weight({10_000})]
-		pub fn submit_price_unsigned(
+		#[pallet::weight(T::WeightInfo::submit_price())]
+		pub fn submit_price(
 			origin: OriginFor<T>,
-			_block_number: BlockNumberFor<T>,
-			price: u32,
-		) -> DispatchResultWithPostInfo {
-			ensure_none(origin)?;
-
-			//
-			Self::add_price(None, price);
-			NextUnsignedAt::<T>::put(Self::current_block_number() + T::UnsignedInterval::get());
+			pair: PairId,
+			price: FixedU128,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
 
-			Ok(().into())
+			Self::submit(who, pair, price)
 		}
 
 		// Here, although this call is unsigned, but still we need to specify the weight.
 		// Otherwise, there won't be any limit to no. of unsigned txs.
+
+		// Each unsigned tx has to go through the `validate_unsigned()` defined in the implementation
+		// of `ValidateUnsigned` trait for the pallet, which only accepts payloads signed by the
+		// oracle members.
 		#[pallet::call_index(2)]
-		#[pallet::weight({10_000})]
+		#[pallet::weight(T::WeightInfo::submit_price_unsigned_with_signed_payload())]
 		pub fn submit_price_unsigned_with_signed_payload(
 			origin: OriginFor<T>,
 			price_payload: PricePayload<T::Public, BlockNumberFor<T>>,
@@ -210,11 +575,86 @@ pub mod pallet {
 		) -> DispatchResultWithPostInfo {
 			ensure_none(origin)?;
 
-			Self::add_price(None, price_payload.price);
-			NextUnsignedAt::<T>::put(Self::current_block_number() + T::UnsignedInterval::get());
+			let who = price_payload.public.into_account();
+			Self::submit(who, price_payload.pair, price_payload.price)?;
 
 			Ok(().into())
 		}
+
+		/// Add or update the price feed of `pair`: the endpoints, asset pair & JSON path the
+		/// offchain workers fetch the price from and its submission interval.
+		/// Only callable by `ConfigOrigin`.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::set_feed())]
+		pub fn set_feed(origin: OriginFor<T>, pair: PairId, feed: FeedInfo<T>) -> DispatchResult {
+			T::ConfigOrigin::ensure_origin(origin)?;
+			ensure!(feed.is_valid(), Error::<T>::InvalidPriceFeed);
+
+			Feeds::<T>::insert(pair, &feed);
+
+			// Emit an event.
+			Self::deposit_event(Event::FeedUpdated { pair, feed });
+
+			Ok(())
+		}
+
+		/// Remove the price feed of `pair` along with its prices.
+		/// Only callable by `ConfigOrigin`.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::remove_feed())]
+		pub fn remove_feed(origin: OriginFor<T>, pair: PairId) -> DispatchResult {
+			T::ConfigOrigin::ensure_origin(origin)?;
+			ensure!(Feeds::<T>::contains_key(pair), Error::<T>::UnknownPair);
+
+			Feeds::<T>::remove(pair);
+			Prices::<T>::remove(pair);
+			LastUpdates::<T>::remove(pair);
+			PendingPrices::<T>::remove(pair);
+			NextRoundAt::<T>::remove(pair);
+			Submissions::<T>::remove(pair);
+
+			// Emit an event.
+			Self::deposit_event(Event::FeedRemoved { pair });
+
+			Ok(())
+		}
+
+		/// Add `who` to the oracle members. Only callable by `ConfigOrigin`.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::add_member())]
+		pub fn add_member(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			T::ConfigOrigin::ensure_origin(origin)?;
+
+			Members::<T>::try_mutate(|members| {
+				let index = members.binary_search(&who).err().ok_or(Error::<T>::AlreadyMember)?;
+				members.try_insert(index, who.clone()).map_err(|_| Error::<T>::TooManyMembers)
+			})?;
+
+			// Emit an event.
+			Self::deposit_event(Event::MemberAdded { who });
+
+			Ok(())
+		}
+
+		/// Remove `who` from the oracle members. Only callable by `ConfigOrigin`.
+		///
+		/// NOTE: The prices `who` submitted in the open rounds still count.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::remove_member())]
+		pub fn remove_member(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			T::ConfigOrigin::ensure_origin(origin)?;
+
+			Members::<T>::try_mutate(|members| {
+				let index = members.binary_search(&who).map_err(|_| Error::<T>::NotMember)?;
+				members.remove(index);
+				Ok::<_, Error<T>>(())
+			})?;
+
+			// Emit an event.
+			Self::deposit_event(Event::MemberRemoved { who });
+
+			Ok(())
+		}
 	}
 
 	#[pallet::validate_unsigned]
@@ -238,9 +678,12 @@ pub mod pallet {
 				if !signature_valid {
 					return InvalidTransaction::BadProof.into();
 				}
-				Self::validate_transaction_parameters(&payload.block_number, &payload.price)
-			} else if let Call::submit_price_unsigned { block_number, price: new_price } = call {
-				Self::validate_transaction_parameters(block_number, new_price)
+				Self::validate_transaction_parameters(
+					&payload.block_number,
+					&payload.pair,
+					&payload.price,
+					&payload.public.clone().into_account(),
+				)
 			} else {
 				InvalidTransaction::Call.into()
 			}
@@ -260,7 +703,8 @@ pub mod pallet {
 /// data required to submit a transaction.
 #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
 pub struct PricePayload<Public, BlockNumber> {
-	price: u32,
+	pair: PairId,
+	price: FixedU128,
 	block_number: BlockNumber,
 	public: Public,
 }
@@ -271,202 +715,318 @@ impl<T: SigningTypes> SignedPayload<T> for PricePayload<T::Public, BlockNumberFo
 	}
 }
 
-enum TransactionType {
+/// A price of a feed & the block at which it was recorded.
+#[derive(
+	Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, scale_info::TypeInfo, MaxEncodedLen,
+)]
+pub struct PricePoint<BlockNumber> {
+	pub price: FixedU128,
+	pub block_number: BlockNumber,
+}
+
+/// The block & the time at which the latest price of a feed was accepted.
+#[derive(
+	Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, scale_info::TypeInfo, MaxEncodedLen,
+)]
+pub struct PriceUpdate<BlockNumber, Moment> {
+	pub block_number: BlockNumber,
+	pub moment: Moment,
+}
+
+/// Why a price can't be provided.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, scale_info::TypeInfo)]
+pub enum PriceError {
+	/// No price feed for the pair.
+	UnknownPair,
+	/// No price recorded yet.
+	NoPrice,
+	/// No price accepted for more than `MaxStaleness` blocks.
+	Stale,
+}
+
+/// The prices of the feeds, for other pallets. Stale feeds have no price, see `MaxStaleness`.
+pub trait PriceProvider<BlockNumber> {
+	/// The latest price of `pair`.
+	fn latest_price(pair: PairId) -> Result<FixedU128, PriceError>;
+
+	/// The mean of the recorded prices of `pair`.
+	fn mean_price(pair: PairId) -> Result<FixedU128, PriceError>;
+
+	/// The time-weighted average price of `pair` over the last `window` blocks.
+	fn twap(pair: PairId, window: BlockNumber) -> Result<FixedU128, PriceError>;
+
+	/// The exponential moving average price of `pair`.
+	fn ema(pair: PairId) -> Result<FixedU128, PriceError>;
+}
+
+impl<T: Config> PriceProvider<BlockNumberFor<T>> for Pallet<T> {
+	fn latest_price(pair: PairId) -> Result<FixedU128, PriceError> {
+		Self::price_history(pair)?
+			.latest()
+			.map(|point| point.price)
+			.ok_or(PriceError::NoPrice)
+	}
+
+	fn mean_price(pair: PairId) -> Result<FixedU128, PriceError> {
+		Self::price_history(pair)?.mean().ok_or(PriceError::NoPrice)
+	}
+
+	fn twap(pair: PairId, window: BlockNumberFor<T>) -> Result<FixedU128, PriceError> {
+		Self::price_history(pair)?
+			.twap(Self::current_block_number(), window)
+			.ok_or(PriceError::NoPrice)
+	}
+
+	fn ema(pair: PairId) -> Result<FixedU128, PriceError> {
+		Self::price_history(pair)?.ema().ok_or(PriceError::NoPrice)
+	}
+}
+
+/// How the offchain workers submit the prices they fetch, see `Config::SubmissionStrategy`.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, scale_info::TypeInfo)]
+pub enum SubmissionStrategy {
+	/// A signed `submit_price` transaction by each local key (paying the fees).
 	Signed,
+	/// An unsigned transaction with the price payload signed by any local key.
 	UnsignedForAny,
+	/// An unsigned transaction with the price payload signed by each local key.
 	UnsignedForAll,
-	Raw,
-	None,
 }
 
 impl<T: Config> Pallet<T> {
-	fn choose_transaction_type(block_number: BlockNumberFor<T>) -> TransactionType {
+	/// The configured strategy, unless a transaction was sent within the `GracePeriod`.
+	fn choose_strategy(block_number: BlockNumberFor<T>) -> Option<SubmissionStrategy> {
 		const RECENTLY_SENT: () = ();
 
 		let val = StorageValueRef::persistent(b"palletocw::last_send");
 
 		let res = val.mutate(|last_send| match last_send {
-			Ok(Some(block)) if block < block_number + T::GracePeriod::get() => Err(RECENTLY_SENT),
+			Ok(Some(block)) if block_number < block + T::GracePeriod::get() => Err(RECENTLY_SENT),
 			_ => Ok(block_number),
 		});
 
 		match res {
-			Ok(_) => {
-				let transaction_type = block_number % 4u32.into();
-				if transaction_type == Zero::zero() {
-					TransactionType::Signed
-				} else if transaction_type == BlockNumberFor::<T>::from(1u32) {
-					TransactionType::UnsignedForAny
-				} else if transaction_type == BlockNumberFor::<T>::from(2u32) {
-					TransactionType::UnsignedForAll
-				} else if transaction_type == BlockNumberFor::<T>::from(3u32) {
-					TransactionType::Raw
-				} else {
-					TransactionType::None
-				}
-			},
+			Ok(_) => Some(T::SubmissionStrategy::get()),
 
 			Err(MutateStorageError::ValueFunctionFailed(RECENTLY_SENT)) => {
 				frame_support::log::info!("skipping sending tx, sent recently");
-				TransactionType::None
+				None
 			},
 			Err(MutateStorageError::ConcurrentModification(_)) => {
 				frame_support::log::error!("error working with storage");
-				TransactionType::None
+				None
 			},
 		}
 	}
 
-	/// A helper function to fetch the price and send signed transaction.
-	fn fetch_price_and_send_signed() -> Result<(), &'static str> {
+	/// The feeds due for a new price at `block_number`.
+	fn due_feeds(block_number: BlockNumberFor<T>) -> Vec<(PairId, FeedInfo<T>)> {
+		Feeds::<T>::iter()
+			.filter(|(pair, _)| Self::next_round_at(pair) <= block_number)
+			.collect()
+	}
+
+	/// Fetch the prices of the feeds due at `block_number`, skipping (& logging) the failed ones.
+	fn fetch_prices(
+		block_number: BlockNumberFor<T>,
+	) -> Result<Vec<(PairId, FixedU128)>, &'static str> {
+		let prices: Vec<_> = Self::due_feeds(block_number)
+			.into_iter()
+			.filter_map(|(pair, feed)| match Self::fetch_price(pair, &feed) {
+				Ok(price) => Some((pair, price)),
+				Err(e) => {
+					log::info!("Failed to fetch price of pair {}: {:?}", pair, e);
+					None
+				},
+			})
+			.collect();
+		if prices.is_empty() {
+			return Err("No price to submit");
+		}
+		Ok(prices)
+	}
+
+	/// A helper function to fetch the prices and send signed transactions.
+	fn fetch_price_and_send_signed(block_number: BlockNumberFor<T>) -> Result<(), &'static str> {
 		let signer = Signer::<T, T::AuthorityId>::all_accounts();
 		if !signer.can_sign() {
 			return Err(
 				"No local accounts available. Consider adding one via `author_insertKey` RPC.",
 			);
 		}
-		// Make an external HTTP request to fetch the current price.
-		// Note this call will block until response is received.
-		let price = Self::fetch_price().map_err(|_| "Failed to fetch price")?;
-
-		// Using `send_signed_transaction` associated type we create and submit a transaction
-		// representing the call, we've just created.
-		// Submit signed will return a vector of results for all accounts that were found in the
-		// local keystore with expected `KEY_TYPE`.
-		// `send_signed_transaction()` return type is `Option<(Account<T>, Result<(), ()>)>`. It is:
-		//	 - `None`: no account is available for sending transaction
-		//	 - `Some((account, Ok(())))`: transaction is successfully sent
-		//	 - `Some((account, Err(())))`: error occurred when sending the transaction
-		let results = signer.send_signed_transaction(|_account| {
-			// Received price is wrapped into a call to `submit_price` public function of this
-			// pallet. This means that the transaction, when executed, will simply call that
-			// function passing `price` as an argument.
-			Call::submit_price { price }
-		});
-
-		for (acc, res) in &results {
-			match res {
-				Ok(()) => log::info!("[{:?}] Submitted price of {} cents", acc.id, price),
-				Err(e) => log::error!("[{:?}] Failed to submit transaction: {:?}", acc.id, e),
+		// Make external HTTP requests to fetch the current prices.
+		// Note this call will block until the responses are received.
+		let prices = Self::fetch_prices(block_number)?;
+
+		for (pair, price) in prices {
+			// Using `send_signed_transaction` associated type we create and submit a transaction
+			// representing the call, we've just created.
+			// Submit signed will return a vector of results for all accounts that were found in the
+			// local keystore with expected `KEY_TYPE`.
+			// `send_signed_transaction()` return type is `Option<(Account<T>, Result<(), ()>)>`. It is:
+			//	 - `None`: no account is available for sending transaction
+			//	 - `Some((account, Ok(())))`: transaction is successfully sent
+			//	 - `Some((account, Err(())))`: error occurred when sending the transaction
+			let results = signer.send_signed_transaction(|_account| {
+				// Received price is wrapped into a call to `submit_price` public function of this
+				// pallet. This means that the transaction, when executed, will simply call that
+				// function passing `pair` & `price` as arguments.
+				Call::submit_price { pair, price }
+			});
+
+			for (acc, res) in &results {
+				match res {
+					Ok(()) => log::info!("[{:?}] Submitted {:?} for pair {}", acc.id, price, pair),
+					Err(e) => log::error!("[{:?}] Failed to submit transaction: {:?}", acc.id, e),
+				}
 			}
 		}
 
 		Ok(())
 	}
 
-	/// A helper function to fetch the price and send a raw unsigned transaction.
-	fn fetch_price_and_send_raw_unsigned(
-		block_number: BlockNumberFor<T>,
-	) -> Result<(), &'static str> {
-		// Make sure we don't fetch the price if unsigned transaction is going to be rejected
-		// anyway.
-		let next_unsigned_at = <NextUnsignedAt<T>>::get();
-		if next_unsigned_at > block_number {
-			return Err("Too early to send unsigned transaction");
-		}
-
-		// Make an external HTTP request to fetch the current price.
-		// Note this call will block until response is received.
-		let price = Self::fetch_price().map_err(|_| "Failed to fetch price")?;
-
-		// Received price is wrapped into a call to `submit_price_unsigned` public function of this
-		// pallet. This means that the transaction, when executed, will simply call that function
-		// passing `price` as an argument.
-		let call = Call::submit_price_unsigned { block_number, price };
-
-		// Now let's create a transaction out of this call and submit it to the pool.
-		// Here we showcase two ways to send an unsigned transaction / unsigned payload (raw)
-		//
-		// By default unsigned transactions are disallowed, so we need to whitelist this case
-		// by writing `UnsignedValidator`. Note that it's EXTREMELY important to carefuly
-		// implement unsigned validation logic, as any mistakes can lead to opening DoS or spam
-		// attack vectors. See validation logic docs for more details.
-		//
-		SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into())
-			.map_err(|()| "Unable to submit unsigned transaction.")?;
-
-		Ok(())
-	}
-
-	/// A helper function to fetch the price, sign payload and send an unsigned transaction
+	/// A helper function to fetch the prices, sign payloads and send unsigned transactions
 	fn fetch_price_and_send_unsigned_for_any_account(
 		block_number: BlockNumberFor<T>,
 	) -> Result<(), &'static str> {
-		// Make sure we don't fetch the price if unsigned transaction is going to be rejected
-		// anyway.
-		let next_unsigned_at = <NextUnsignedAt<T>>::get();
-		if next_unsigned_at > block_number {
-			return Err("Too early to send unsigned transaction");
+		// Make external HTTP requests to fetch the current prices of the feeds whose round is
+		// open.
+		// Note this call will block until the responses are received.
+		let prices = Self::fetch_prices(block_number)?;
+
+		for (pair, price) in prices {
+			// -- Sign using any account
+			let (_, result) = Signer::<T, T::AuthorityId>::any_account()
+				.send_unsigned_transaction(
+					|account| PricePayload {
+						pair,
+						price,
+						block_number,
+						public: account.public.clone(),
+					},
+					|payload, signature| Call::submit_price_unsigned_with_signed_payload {
+						price_payload: payload,
+						signature,
+					},
+				)
+				.ok_or("No local accounts accounts available.")?;
+			result.map_err(|()| "Unable to submit transaction")?;
 		}
 
-		// Make an external HTTP request to fetch the current price.
-		// Note this call will block until response is received.
-		let price = Self::fetch_price().map_err(|_| "Failed to fetch price")?;
-
-		// -- Sign using any account
-		let (_, result) = Signer::<T, T::AuthorityId>::any_account()
-			.send_unsigned_transaction(
-				|account| PricePayload { price, block_number, public: account.public.clone() },
-				|payload, signature| Call::submit_price_unsigned_with_signed_payload {
-					price_payload: payload,
-					signature,
-				},
-			)
-			.ok_or("No local accounts accounts available.")?;
-		result.map_err(|()| "Unable to submit transaction")?;
-
 		Ok(())
 	}
 
-	/// A helper function to fetch the price, sign payload and send an unsigned transaction
+	/// A helper function to fetch the prices, sign payloads and send unsigned transactions
 	fn fetch_price_and_send_unsigned_for_all_accounts(
 		block_number: BlockNumberFor<T>,
 	) -> Result<(), &'static str> {
-		// Make sure we don't fetch the price if unsigned transaction is going to be rejected
-		// anyway.
-		let next_unsigned_at = <NextUnsignedAt<T>>::get();
-		if next_unsigned_at > block_number {
-			return Err("Too early to send unsigned transaction");
+		let signer = Signer::<T, T::AuthorityId>::all_accounts();
+		if !signer.can_sign() {
+			return Err(
+				"No local accounts available. Consider adding one via `author_insertKey` RPC.",
+			);
 		}
-
-		// Make an external HTTP request to fetch the current price.
-		// Note this call will block until response is received.
-		let price = Self::fetch_price().map_err(|_| "Failed to fetch price")?;
-
-		// -- Sign using all accounts
-		let transaction_results = Signer::<T, T::AuthorityId>::all_accounts()
-			.send_unsigned_transaction(
-				|account| PricePayload { price, block_number, public: account.public.clone() },
+		// Make external HTTP requests to fetch the current prices of the feeds whose round is
+		// open.
+		// Note this call will block until the responses are received.
+		let prices = Self::fetch_prices(block_number)?;
+
+		for (pair, price) in prices {
+			// -- Sign using all accounts
+			let transaction_results = signer.send_unsigned_transaction(
+				|account| PricePayload {
+					pair,
+					price,
+					block_number,
+					public: account.public.clone(),
+				},
 				|payload, signature| Call::submit_price_unsigned_with_signed_payload {
 					price_payload: payload,
 					signature,
 				},
 			);
-		for (_account_id, result) in transaction_results.into_iter() {
-			if result.is_err() {
-				return Err("Unable to submit transaction");
+			for (_account_id, result) in transaction_results.into_iter() {
+				if result.is_err() {
+					return Err("Unable to submit transaction");
+				}
 			}
 		}
 
 		Ok(())
 	}
 
-	fn fetch_price() -> Result<u32, http::Error> {
-		// set a deadline
+	/// The price source of the `feed` of `pair` on this node: the local override if set, else
+	/// the on-chain one.
+	pub fn effective_source(pair: PairId, feed: &FeedInfo<T>) -> SourceConfig<T> {
+		let key = source_override_key(pair);
+		match StorageValueRef::persistent(&key).get::<SourceConfig<T>>() {
+			Ok(Some(source)) => source,
+			Ok(None) => feed.source.clone(),
+			Err(_) => {
+				log::warn!("Undecodable local price source override of pair {}", pair);
+				feed.source.clone()
+			},
+		}
+	}
+
+	/// Fetch the price of the `feed` of `pair` from all the endpoints of its source concurrently
+	/// & aggregate them, see [`Self::aggregate_prices`].
+	fn fetch_price(pair: PairId, feed: &FeedInfo<T>) -> Result<FixedU128, http::Error> {
+		let source = Self::effective_source(pair, feed);
+		let json_path = source.fill(&source.json_path);
+		let json_path = sp_std::str::from_utf8(&json_path).map_err(|_| http::Error::Unknown)?;
+
+		// set a deadline (common to all the requests)
 		let deadline = sp_io::offchain::timestamp().add(Duration::from_millis(2_000));
 
-		// Here we are preparing the http GET request call
-		let request =
-			http::Request::get("https://min-api.cryptocompare.com/data/price?fsym=BTC&tsyms=USD");
+		// Send the http GET requests to all the endpoints, before waiting for any response.
+		let mut urls = Vec::new();
+		let mut requests = Vec::new();
+		for endpoint in source.endpoints.iter() {
+			let url = match String::from_utf8(source.fill(endpoint)) {
+				Ok(url) => url,
+				Err(_) => {
+					log::info!("Non UTF-8 endpoint of pair {}", pair);
+					continue;
+				},
+			};
+			match http::Request::get(&url).deadline(deadline).send() {
+				Ok(pending) => {
+					urls.push(url);
+					requests.push(pending);
+				},
+				Err(e) => log::info!("Failed to fetch pair {} price from {}: {:?}", pair, url, e),
+			}
+		}
 
-		// Get the pending request
-		let pending = request.deadline(deadline).send().map_err(|_| http::Error::IoError)?;
+		// Get the responses after waiting for the deadline
+		let responses = http::PendingRequest::try_wait_all(requests, deadline);
+
+		let mut prices = Vec::new();
+		for (url, response) in urls.into_iter().zip(responses) {
+			let price = response
+				.map_err(|_| http::Error::DeadlineReached)
+				.and_then(|response| Self::read_price(response?, json_path));
+			match price {
+				Ok(price) => prices.push((url, price)),
+				Err(e) => log::info!("Failed to fetch pair {} price from {}: {:?}", pair, url, e),
+			}
+		}
+
+		let price = Self::aggregate_prices(prices, feed.min_sources, feed.max_deviation)
+			.ok_or_else(|| {
+				log::info!("Not enough sources agree on the price of pair {}", pair);
+				http::Error::Unknown
+			})?;
 
-		// Get the response after waiting for the deadline
-		let response = pending.try_wait(deadline).map_err(|_| http::Error::DeadlineReached)??;
+		log::info!("price of pair {pair}: {price:?}");
 
+		Ok(price)
+	}
+
+	fn read_price(response: http::Response, json_path: &str) -> Result<FixedU128, http::Error> {
 		// let's check the response before reading the response
-		if response.code == 200 {
+		if response.code != 200 {
 			log::info!("Unexpected response code: {}", response.code);
 			return Err(http::Error::Unknown);
 		}
@@ -478,68 +1038,267 @@ impl<T: Config> Pallet<T> {
 		let body_str = sp_std::str::from_utf8(&body).map_err(|_| http::Error::Unknown)?;
 
 		// extract the price value
-		let price = match Self::parse_price(body_str) {
+		match Self::parse_price(body_str, json_path) {
 			Some(price) => Ok(price),
 			None => {
 				log::info!("Unable to extract price from the response: {body_str}");
 				Err(http::Error::Unknown)
 			},
-		}?;
+		}
+	}
 
-		log::info!("price: {price}");
+	/// The median of the `prices` (by source) within `max_deviation` of the median of all of
+	/// them, or `None` if fewer than `min_sources` are.
+	fn aggregate_prices<S: core::fmt::Display>(
+		prices: Vec<(S, FixedU128)>,
+		min_sources: u32,
+		max_deviation: Percent,
+	) -> Option<FixedU128> {
+		let median = Self::median(prices.iter().map(|(_, price)| *price).collect())?;
+		let max_deviation = max_deviation * median.into_inner();
+
+		let mut agreeing = Vec::with_capacity(prices.len());
+		for (source, price) in prices {
+			if price.into_inner().abs_diff(median.into_inner()) <= max_deviation {
+				agreeing.push(price);
+			} else {
+				log::info!(
+					"Dropping price {:?} of {}, too far from the median {:?}",
+					price,
+					source,
+					median
+				);
+			}
+		}
 
-		Ok(price)
+		if (agreeing.len() as u32) < min_sources {
+			return None;
+		}
+		Self::median(agreeing)
 	}
 
-	// Get the number from string slice price input fetched from HTTP request.
-	fn parse_price(price_str: &str) -> Option<u32> {
-		let val = parse_json(price_str);
-		let price = match val.ok()? {
-			JsonValue::Object(obj) => {
-				let (_, v) = obj.into_iter().find(|(k, _)| k.iter().copied().eq("USD".chars()))?;
-				match v {
-					JsonValue::Number(number) => number,
-					_ => return None,
-				}
+	/// The median of the `prices` (the mean of the middle two ones if even), if any.
+	fn median(mut prices: Vec<FixedU128>) -> Option<FixedU128> {
+		prices.sort_unstable();
+		let mid = prices.len() / 2;
+		match prices.len() {
+			0 => None,
+			len if len % 2 == 1 => Some(prices[mid]),
+			_ => {
+				let (lower, upper) = (prices[mid - 1].into_inner(), prices[mid].into_inner());
+				Some(FixedU128::from_inner(lower + (upper - lower) / 2))
 			},
-			_ => return None,
+		}
+	}
+
+	// Get the number (or decimal string) at the `.`-separated `json_path` from string slice price
+	// input fetched from HTTP request, truncated to the precision of `FixedU128`.
+	fn parse_price(price_str: &str, json_path: &str) -> Option<FixedU128> {
+		let mut val = parse_json(price_str).ok()?;
+		for key in json_path.split('.') {
+			val = match val {
+				JsonValue::Object(obj) => {
+					let (_, v) =
+						obj.into_iter().find(|(k, _)| k.iter().copied().eq(key.chars()))?;
+					v
+				},
+				_ => return None,
+			};
+		}
+		match val {
+			JsonValue::Number(number) if !number.negative => Self::to_fixed(
+				number.integer.into(),
+				number.fraction.into(),
+				number.fraction_length,
+				number.exponent,
+			),
+			JsonValue::String(chars) => Self::parse_decimal(&chars.into_iter().collect::<String>()),
+			_ => None,
+		}
+	}
+
+	/// The non-negative decimal number `s`, e.g. `6536.92` or `6.53692e3`, with any number of
+	/// fraction digits (truncated to the precision of `FixedU128`).
+	fn parse_decimal(s: &str) -> Option<FixedU128> {
+		let (mantissa, exponent) = match s.find(['e', 'E']) {
+			Some(index) => (&s[..index], s[index + 1..].parse::<i32>().ok()?),
+			None => (s, 0),
 		};
+		let (integer, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+		if integer.is_empty() || !integer.bytes().all(|c| c.is_ascii_digit()) {
+			return None;
+		}
+		if !fraction.bytes().all(|c| c.is_ascii_digit()) {
+			return None;
+		}
 
-		let exp = price.fraction_length.saturating_sub(2);
-		Some(price.integer as u32 * 100 + (price.fraction / 10_u64.pow(exp)) as u32)
+		// digits beyond the `u128` ones are way below the precision
+		let fraction = &fraction[..fraction.len().min(38)];
+		Self::to_fixed(
+			integer.parse().ok()?,
+			if fraction.is_empty() { 0 } else { fraction.parse().ok()? },
+			fraction.len() as u32,
+			exponent,
+		)
 	}
 
-	fn add_price(who_maybe: Option<T::AccountId>, price: u32) {
-		frame_support::log::info!("Adding price: {}", price);
-		// update the price, calcualate the average.
-		<Prices<T>>::mutate(|prices| {
-			if prices.try_push(price).is_err() {
-				prices[(price % T::MaxPrices::get()) as usize] = price;
-			}
-		});
+	/// `integer.fraction * 10^exponent` (with `fraction_length` fraction digits), if it fits.
+	fn to_fixed(
+		integer: u128,
+		fraction: u128,
+		fraction_length: u32,
+		exponent: i32,
+	) -> Option<FixedU128> {
+		// `FixedU128` has 18 decimals
+		let decimals = 18 + i64::from(exponent);
+		let inner = Self::scale(integer, decimals)?
+			.checked_add(Self::scale(fraction, decimals - i64::from(fraction_length))?)?;
+		Some(FixedU128::from_inner(inner))
+	}
 
-		let avg_price = Self::average_price().expect("error in Calculation of avg price");
-		frame_support::log::info!("Average price: {}", avg_price);
+	/// `value * 10^shift` (truncated if `shift` is negative), if it fits.
+	fn scale(value: u128, shift: i64) -> Option<u128> {
+		let pow = u32::try_from(shift.unsigned_abs()).ok().and_then(|pow| 10u128.checked_pow(pow));
+		match shift {
+			shift if shift >= 0 => value.checked_mul(pow?),
+			_ => Some(pow.map_or(0, |pow| value / pow)),
+		}
+	}
+
+	/// Record the price submitted by the oracle member `who` in the open round of `pair`,
+	/// finalising the round if `Threshold` of the members submitted.
+	fn submit(who: T::AccountId, pair: PairId, price: FixedU128) -> DispatchResult {
+		ensure!(Self::is_member(&who), Error::<T>::NotMember);
+		let feed = Feeds::<T>::get(pair).ok_or(Error::<T>::UnknownPair)?;
+		let now = Self::current_block_number();
+		let (round_at, mut submissions) = Self::open_round(pair, &feed, now);
+		ensure!(round_at <= now, Error::<T>::RoundNotOpen);
+		ensure!(
+			!submissions.iter().any(|(member, _)| member == &who),
+			Error::<T>::AlreadySubmitted
+		);
+		submissions
+			.try_push((who.clone(), price))
+			.map_err(|_| Error::<T>::StorageOverflow)?;
 
 		// Emit an event.
-		Self::deposit_event(Event::NewPrice { price, who_maybe });
+		Self::deposit_event(Event::PriceSubmitted { pair, who, price });
+
+		if submissions.len() as u32 >= Self::threshold() {
+			Submissions::<T>::remove(pair);
+			let prices = submissions.into_iter().map(|(_, price)| price).collect();
+			if let Some(median) = Self::median(prices) {
+				Self::finalise_round(pair, median);
+			}
+			NextRoundAt::<T>::insert(pair, now + feed.interval);
+		} else {
+			Submissions::<T>::insert(pair, submissions);
+			NextRoundAt::<T>::insert(pair, round_at);
+		}
+
+		Ok(())
 	}
 
-	fn average_price() -> Option<u32> {
-		let prices = <Prices<T>>::get();
-		if prices.is_empty() {
-			None
+	/// Start of the open round of `pair` at `now` & the prices submitted in it.
+	///
+	/// A round not finalised within the `interval` of the feed has expired, in which case the
+	/// round restarts (without any submissions) at the last multiple of `interval` since.
+	fn open_round(
+		pair: PairId,
+		feed: &FeedInfo<T>,
+		now: BlockNumberFor<T>,
+	) -> (BlockNumberFor<T>, BoundedVec<(T::AccountId, FixedU128), T::MaxMembers>) {
+		let round_at = Self::next_round_at(pair);
+		let elapsed = now.saturating_sub(round_at);
+		if !feed.interval.is_zero() && elapsed >= feed.interval {
+			(now.saturating_sub(elapsed % feed.interval), Default::default())
 		} else {
-			Some(prices.iter().fold(0, |acc, x| acc.saturating_add(*x) / prices.len() as u32))
+			(round_at, Self::submissions(pair))
+		}
+	}
+
+	/// Whether `who` is an oracle member.
+	pub fn is_member(who: &T::AccountId) -> bool {
+		Self::members().binary_search(who).is_ok()
+	}
+
+	/// Number of submissions finalising a round: `Threshold` of the oracle members (min. 1).
+	pub fn threshold() -> u32 {
+		T::Threshold::get().mul_ceil(Self::members().len() as u32).max(1)
+	}
+
+	/// Accept the `price` of a round of `pair` unless it moves beyond `MaxPriceChange` from the
+	/// previous one, in which case it's held till the next round confirms it (i.e. moves within
+	/// `MaxPriceChange` of it).
+	fn finalise_round(pair: PairId, price: FixedU128) {
+		let within_max_change = |reference: FixedU128| {
+			price.into_inner().abs_diff(reference.into_inner()) <=
+				T::MaxPriceChange::get() * reference.into_inner()
+		};
+
+		let previous = Self::prices(pair).latest().map(|point| point.price);
+		let pending = PendingPrices::<T>::take(pair);
+		let confirmed = previous.map_or(true, within_max_change) ||
+			pending.map_or(false, within_max_change);
+		match previous {
+			Some(previous) if !confirmed => {
+				log::info!("Holding price {:?} of pair {}", price, pair);
+				PendingPrices::<T>::insert(pair, price);
+
+				// Emit an event.
+				Self::deposit_event(Event::PriceHeld { pair, price, previous });
+			},
+			_ => Self::add_price(None, pair, price),
 		}
 	}
 
+	fn add_price(who_maybe: Option<T::AccountId>, pair: PairId, price: FixedU128) {
+		frame_support::log::info!("Adding price of pair {}: {:?}", pair, price);
+		// update the price, calcualate the average.
+		let block_number = Self::current_block_number();
+		let avg_price = <Prices<T>>::mutate(pair, |prices| {
+			prices.push(PricePoint { price, block_number });
+			prices.mean()
+		});
+		LastUpdates::<T>::insert(
+			pair,
+			PriceUpdate { block_number, moment: T::TimeProvider::now() },
+		);
+		frame_support::log::info!("Average price: {:?}", avg_price);
+
+		// Emit an event.
+		Self::deposit_event(Event::NewPrice { pair, price, who_maybe });
+	}
+
+	/// The price history of `pair`, if it has a feed & isn't stale.
+	fn price_history(pair: PairId) -> Result<PriceHistory<T>, PriceError> {
+		ensure!(Feeds::<T>::contains_key(pair), PriceError::UnknownPair);
+		let last_update = Self::last_update(pair).ok_or(PriceError::NoPrice)?;
+		ensure!(
+			Self::current_block_number() <=
+				last_update.block_number.saturating_add(T::MaxStaleness::get()),
+			PriceError::Stale
+		);
+		Ok(Self::prices(pair))
+	}
+
 	fn validate_transaction_parameters(
 		block_number: &BlockNumberFor<T>,
-		new_price: &u32,
+		pair: &PairId,
+		new_price: &FixedU128,
+		who: &T::AccountId,
 	) -> TransactionValidity {
-		let next_unsigned_at = NextUnsignedAt::<T>::get();
-		if &next_unsigned_at > block_number {
+		if !Self::is_member(who) {
+			return InvalidTransaction::BadSigner.into();
+		}
+
+		let feed = match Feeds::<T>::get(pair) {
+			Some(feed) => feed,
+			None => return InvalidTransaction::Call.into(),
+		};
+
+		let (round_at, submissions) = Self::open_round(*pair, &feed, Self::current_block_number());
+		if &round_at > block_number || submissions.iter().any(|(member, _)| member == who) {
 			return InvalidTransaction::Stale.into();
 		}
 
@@ -548,15 +1307,23 @@ impl<T: Config> Pallet<T> {
 		}
 
 		// in order to set the priority, we ensure the difference from the current avg price is highest possible.
-		let avg_price = Self::average_price()
-			.map(|price| if &price > new_price { price - new_price } else { new_price - price })
-			.unwrap_or(0);
+		// (relative to it, in parts per million, as the feeds have different magnitudes)
+		let avg_price = Self::prices(pair)
+			.mean()
+			.and_then(|price| {
+				let diff =
+					FixedU128::from_inner(price.into_inner().abs_diff(new_price.into_inner()));
+				diff.checked_div(&price)
+			})
+			.map_or(0, |deviation| deviation.saturating_mul_int(1_000_000u64));
 
 		ValidTransaction::with_tag_prefix("pallet-ocw")
 			// Next we tweak the priority depending on how much
 			// it differs from the current average. (the more it differs the more priority it
 			// has).
 			.priority(T::UnsignedPriority::get().saturating_add(avg_price as _))
+			// one unsigned price per feed, round & member
+			.and_provides((pair, round_at, who))
 			// transaction valid for next 5 blocks, after which it has to be revalidated by the pool
 			.longevity(5)
 			.propagate(true)
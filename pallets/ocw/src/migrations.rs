@@ -0,0 +1,41 @@
+//! Storage migrations of the OCW pallet.
+
+use super::*;
+
+/// Storage version 1: `Prices` is a map of the price history per pair instead of a single list of
+/// `u32` prices & `NextUnsignedAt` is replaced by the rounds of the feeds.
+pub mod v1 {
+	use super::*;
+	use core::marker::PhantomData;
+	use frame_support::{
+		storage::{storage_prefix, unhashed},
+		traits::{GetStorageVersion, OnRuntimeUpgrade, PalletInfoAccess, StorageVersion},
+		weights::Weight,
+	};
+
+	/// Remove the v0 `Prices` & `NextUnsignedAt` values.
+	///
+	/// The v0 prices aren't tied to a pair (nor scaled as `FixedU128`), so they are dropped rather
+	/// than converted. The v0 `Prices` value is stored at the prefix of the v1 map, which it would
+	/// otherwise be left in.
+	pub struct MigrateToV1<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() >= 1 {
+				log::info!(target: "pallet_ocw", "Skipping the v1 migration");
+				return T::DbWeight::get().reads(1);
+			}
+
+			let pallet = <Pallet<T> as PalletInfoAccess>::name().as_bytes();
+			unhashed::kill(&storage_prefix(pallet, b"Prices"));
+			unhashed::kill(&storage_prefix(pallet, b"NextUnsignedAt"));
+
+			StorageVersion::new(1).put::<Pallet<T>>();
+			log::info!(target: "pallet_ocw", "Removed the v0 prices");
+
+			// the version & the values
+			T::DbWeight::get().reads_writes(1, 3)
+		}
+	}
+}
@@ -1,20 +1,14 @@
 use crate as pallet_ocw;
-use codec::Decode;
 use frame_support::{
-	assert_ok, parameter_types,
+	parameter_types,
 	traits::{ConstU16, ConstU32, ConstU64},
 };
-use sp_core::{
-	offchain::{testing, OffchainWorkerExt, TransactionPoolExt},
-	sr25519::Signature,
-	H256,
-};
-
-use sp_keystore::{testing::MemoryKeystore, Keystore, KeystoreExt};
+use pallet_ocw::SubmissionStrategy;
+use sp_core::{sr25519::Signature, H256};
 use sp_runtime::{
 	testing::{Header, TestXt},
 	traits::{BlakeTwo256, Extrinsic as ExtrinsicT, IdentifyAccount, IdentityLookup, Verify},
-	RuntimeAppPublic,
+	Percent,
 };
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
@@ -28,6 +22,8 @@ frame_support::construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system,
+		// used as dependency (for timestamping the price updates) for pallet_ocw
+		Timestamp: pallet_timestamp,
 		OCW: pallet_ocw,
 	}
 );
@@ -59,7 +55,7 @@ impl frame_system::Config for Test {
 	type MaxConsumers = frame_support::traits::ConstU32<16>;
 }
 
-type Extrinsic = TestXt<RuntimeCall, ()>;
+pub type Extrinsic = TestXt<RuntimeCall, ()>;
 type AccountId = <<Signature as Verify>::Signer as IdentifyAccount>::AccountId;
 
 impl frame_system::offchain::SigningTypes for Test {
@@ -89,8 +85,19 @@ where
 	}
 }
 
+impl pallet_timestamp::Config for Test {
+	type Moment = u64;
+	type OnTimestampSet = ();
+	type MinimumPeriod = ConstU64<5>;
+	type WeightInfo = ();
+}
+
 parameter_types! {
 	pub const UnsignedPriority: u64 = 1 << 20; // 2**20
+	pub const Threshold: Percent = Percent::from_percent(50);
+	pub const EmaSmoothing: Percent = Percent::from_percent(20);
+	pub const MaxPriceChange: Percent = Percent::from_percent(10);
+	pub static Strategy: SubmissionStrategy = SubmissionStrategy::UnsignedForAll;
 }
 
 impl pallet_ocw::Config for Test {
@@ -99,14 +106,42 @@ impl pallet_ocw::Config for Test {
 	type GracePeriod = ConstU64<5>;
 	type MaxPrices = ConstU32<64>;
 	type UnsignedPriority = UnsignedPriority;
-	type UnsignedInterval = ConstU64<128>;
+	type SubmissionStrategy = Strategy;
+	type ConfigOrigin = frame_system::EnsureRoot<AccountId>;
+	type MaxEndpoints = ConstU32<4>;
+	type MaxSourceLen = ConstU32<128>;
+	type MaxMembers = ConstU32<4>;
+	type Threshold = Threshold;
+	type EmaSmoothing = EmaSmoothing;
+	type TimeProvider = Timestamp;
+	type MaxStaleness = ConstU64<200>;
+	type MaxPriceChange = MaxPriceChange;
+	type WeightInfo = ();
 }
 
-fn test_pub() -> sp_core::sr25519::Public {
-	sp_core::sr25519::Public::from_raw([1u8; 32])
-}
+pub const ALICE: sp_core::sr25519::Public = sp_core::sr25519::Public([1u8; 32]);
+pub const BOB: sp_core::sr25519::Public = sp_core::sr25519::Public([2u8; 32]);
+pub const CHARLIE: sp_core::sr25519::Public = sp_core::sr25519::Public([3u8; 32]);
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	frame_system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_ocw::GenesisConfig::<Test> { feeds: vec![], members: vec![ALICE, BOB, CHARLIE] }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	// events aren't deposited at genesis
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// [`new_test_ext`] with an (empty) keystore, for the benchmarks to generate the keys signing
+/// the unsigned submissions.
+#[cfg(feature = "runtime-benchmarks")]
+pub fn new_bench_ext() -> sp_io::TestExternalities {
+	use sp_keystore::{testing::MemoryKeystore, KeystoreExt};
+
+	let mut ext = new_test_ext();
+	ext.register_extension(KeystoreExt::new(MemoryKeystore::new()));
+	ext
 }
@@ -1,2 +1,936 @@
-use crate::{mock::*, Error, Event};
-use frame_support::{assert_noop, assert_ok};
+use crate::{
+	crypto, mock::*, source_override_key, Call, Error, Event, FeedInfo, PairId, PriceError,
+	PriceHistory, PricePayload, PricePoint, PriceProvider, PriceUpdate, SourceConfig,
+	SubmissionStrategy,
+};
+use codec::{Decode, Encode};
+use frame_support::{
+	assert_noop, assert_ok,
+	sp_runtime::{
+		offchain::storage::StorageValueRef,
+		transaction_validity::{InvalidTransaction, TransactionSource},
+	},
+	traits::Hooks,
+	unsigned::ValidateUnsigned,
+};
+use frame_system::offchain::SignedPayload;
+use sp_core::{
+	offchain::{testing, OffchainWorkerExt, TransactionPoolExt},
+	sr25519, Pair,
+};
+use sp_keystore::{testing::MemoryKeystore, Keystore, KeystoreExt};
+use sp_runtime::{DispatchError, FixedPointNumber, FixedU128, Percent, RuntimeAppPublic};
+
+const BTC: PairId = 0;
+const ETH: PairId = 1;
+const LOCAL_ENDPOINT: &str = "http://localhost:8000/price?fsym={base}&tsyms={quote}";
+
+fn source(endpoints: &[&str], json_path: &str) -> SourceConfig<Test> {
+	SourceConfig {
+		base: b"BTC".to_vec().try_into().unwrap(),
+		quote: b"USD".to_vec().try_into().unwrap(),
+		endpoints: endpoints
+			.iter()
+			.map(|endpoint| endpoint.as_bytes().to_vec().try_into().unwrap())
+			.collect::<Vec<_>>()
+			.try_into()
+			.unwrap(),
+		json_path: json_path.as_bytes().to_vec().try_into().unwrap(),
+	}
+}
+
+fn feed(endpoints: &[&str], json_path: &str) -> FeedInfo<Test> {
+	FeedInfo {
+		source: source(endpoints, json_path),
+		interval: 128,
+		min_sources: 1,
+		max_deviation: Percent::from_percent(5),
+	}
+}
+
+const DAVE: sr25519::Public = sr25519::Public([4u8; 32]);
+
+/// The payload of `key` submitting `price` for `pair`, signed by `signer`.
+fn signed_payload(
+	key: &sr25519::Pair,
+	signer: &sr25519::Pair,
+	pair: PairId,
+	price: FixedU128,
+) -> (PricePayload<sr25519::Public, u64>, sr25519::Signature) {
+	let price_payload = PricePayload { pair, price, block_number: 1, public: key.public() };
+	let signature = signer.sign(&price_payload.encode());
+	(price_payload, signature)
+}
+
+/// An oracle member `key` submitting `price` for `pair` via a signed payload.
+fn signed_submission(key: &sr25519::Pair, pair: PairId, price: FixedU128) -> Call<Test> {
+	let (price_payload, signature) = signed_payload(key, key, pair, price);
+	Call::submit_price_unsigned_with_signed_payload { price_payload, signature }
+}
+
+/// `n` as a price.
+fn price(n: u128) -> FixedU128 {
+	FixedU128::saturating_from_integer(n)
+}
+
+/// The recorded prices of `pair`, oldest first.
+fn prices(pair: PairId) -> Vec<FixedU128> {
+	OCW::prices(pair).to_vec().iter().map(|point| point.price).collect()
+}
+
+/// A price history of `prices` recorded at the given blocks.
+fn history(prices: &[(u128, u64)]) -> PriceHistory<Test> {
+	let mut history = PriceHistory::<Test>::default();
+	for &(n, block_number) in prices {
+		history.push(PricePoint { price: price(n), block_number });
+	}
+	history
+}
+
+/// Finalise the next round of `pair` (in its first block) with `price`.
+fn finalise_round(pair: PairId, price: FixedU128) {
+	System::set_block_number(OCW::next_round_at(pair).max(System::block_number()));
+	assert_ok!(OCW::submit_price(RuntimeOrigin::signed(ALICE), pair, price));
+	assert_ok!(OCW::submit_price(RuntimeOrigin::signed(BOB), pair, price));
+}
+
+const PHRASE: &str = "news slush supreme milk chapter athlete soap sausage put clutch what kitten";
+
+/// Register a keystore holding a `KEY_TYPE` key per derivation of `PHRASE`, returning them.
+fn register_keys(t: &mut sp_io::TestExternalities, derivations: &[&str]) -> Vec<sr25519::Public> {
+	let keystore = MemoryKeystore::new();
+	let keys = derivations
+		.iter()
+		.map(|derivation| {
+			let suri = format!("{}/{}", PHRASE, derivation);
+			keystore.sr25519_generate_new(crypto::Public::ID, Some(&suri)).unwrap()
+		})
+		.collect();
+	t.register_extension(KeystoreExt::new(keystore));
+	keys
+}
+
+fn expect_request(state: &mut testing::OffchainState, uri: &str, response: &[u8]) {
+	state.expect_request(testing::PendingRequest {
+		method: "GET".into(),
+		uri: uri.into(),
+		response: Some(response.to_vec()),
+		sent: true,
+		..Default::default()
+	});
+}
+
+//=====set_feed=====
+
+#[test]
+fn set_feed_works() {
+	new_test_ext().execute_with(|| {
+		let feed = feed(&[LOCAL_ENDPOINT], "{quote}");
+		assert_ok!(OCW::set_feed(RuntimeOrigin::root(), BTC, feed.clone()));
+
+		assert_eq!(OCW::feeds(BTC), Some(feed.clone()));
+		assert_eq!(OCW::feeds(ETH), None);
+		System::assert_last_event(Event::FeedUpdated { pair: BTC, feed }.into());
+	});
+}
+
+#[test]
+fn set_feed_requires_config_origin() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			OCW::set_feed(RuntimeOrigin::signed(ALICE), BTC, feed(&[LOCAL_ENDPOINT], "USD")),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn set_feed_fails_with_invalid_feed() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			OCW::set_feed(RuntimeOrigin::root(), BTC, feed(&[], "USD")),
+			Error::<Test>::InvalidPriceFeed
+		);
+
+		let mut non_utf8 = feed(&[LOCAL_ENDPOINT], "USD");
+		non_utf8.source.base = vec![0xff, 0xfe].try_into().unwrap();
+		assert_noop!(
+			OCW::set_feed(RuntimeOrigin::root(), BTC, non_utf8),
+			Error::<Test>::InvalidPriceFeed
+		);
+
+		for min_sources in [0, 2] {
+			let mut feed = feed(&[LOCAL_ENDPOINT], "USD");
+			feed.min_sources = min_sources;
+			assert_noop!(
+				OCW::set_feed(RuntimeOrigin::root(), BTC, feed),
+				Error::<Test>::InvalidPriceFeed
+			);
+		}
+	});
+}
+
+//=====remove_feed=====
+
+#[test]
+fn remove_feed_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(OCW::set_feed(RuntimeOrigin::root(), BTC, feed(&[LOCAL_ENDPOINT], "USD")));
+		assert_ok!(OCW::submit_price(RuntimeOrigin::signed(ALICE), BTC, price(100)));
+
+		assert_ok!(OCW::remove_feed(RuntimeOrigin::root(), BTC));
+
+		assert_eq!(OCW::feeds(BTC), None);
+		assert!(OCW::submissions(BTC).is_empty());
+		System::assert_last_event(Event::FeedRemoved { pair: BTC }.into());
+	});
+}
+
+#[test]
+fn remove_feed_fails_with_unknown_pair() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(OCW::remove_feed(RuntimeOrigin::root(), ETH), Error::<Test>::UnknownPair);
+	});
+}
+
+//=====members=====
+
+#[test]
+fn add_member_works() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(OCW::threshold(), 2);
+
+		assert_ok!(OCW::add_member(RuntimeOrigin::root(), DAVE));
+
+		assert_eq!(OCW::members().into_inner(), vec![ALICE, BOB, CHARLIE, DAVE]);
+		assert_eq!(OCW::threshold(), 2);
+		System::assert_last_event(Event::MemberAdded { who: DAVE }.into());
+	});
+}
+
+#[test]
+fn add_member_fails() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(OCW::add_member(RuntimeOrigin::signed(ALICE), DAVE), DispatchError::BadOrigin);
+		assert_noop!(OCW::add_member(RuntimeOrigin::root(), BOB), Error::<Test>::AlreadyMember);
+
+		assert_ok!(OCW::add_member(RuntimeOrigin::root(), DAVE));
+		assert_noop!(
+			OCW::add_member(RuntimeOrigin::root(), sr25519::Public([5u8; 32])),
+			Error::<Test>::TooManyMembers
+		);
+	});
+}
+
+#[test]
+fn remove_member_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(OCW::remove_member(RuntimeOrigin::root(), BOB));
+
+		assert_eq!(OCW::members().into_inner(), vec![ALICE, CHARLIE]);
+		assert_eq!(OCW::threshold(), 1);
+		System::assert_last_event(Event::MemberRemoved { who: BOB }.into());
+
+		assert_noop!(OCW::remove_member(RuntimeOrigin::root(), BOB), Error::<Test>::NotMember);
+		assert_noop!(
+			OCW::remove_member(RuntimeOrigin::signed(ALICE), CHARLIE),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+//=====submit_price=====
+
+#[test]
+fn submit_price_finalises_the_round_at_the_threshold() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(OCW::set_feed(RuntimeOrigin::root(), BTC, feed(&[LOCAL_ENDPOINT], "USD")));
+
+		assert_ok!(OCW::submit_price(RuntimeOrigin::signed(ALICE), BTC, price(2_700_000)));
+		System::assert_last_event(
+			Event::PriceSubmitted { pair: BTC, who: ALICE, price: price(2_700_000) }.into(),
+		);
+		assert_eq!(OCW::submissions(BTC).into_inner(), vec![(ALICE, price(2_700_000))]);
+		assert!(prices(BTC).is_empty());
+
+		// 2 of the 3 members submitted
+		assert_ok!(OCW::submit_price(RuntimeOrigin::signed(BOB), BTC, price(2_800_000)));
+		System::assert_last_event(
+			Event::NewPrice { pair: BTC, price: price(2_750_000), who_maybe: None }.into(),
+		);
+		assert_eq!(prices(BTC), vec![price(2_750_000)]);
+		assert!(OCW::submissions(BTC).is_empty());
+		assert_eq!(OCW::next_round_at(BTC), 129);
+	});
+}
+
+#[test]
+fn submit_price_tracks_each_feed_separately() {
+	new_test_ext().execute_with(|| {
+		let mut eth = feed(&[LOCAL_ENDPOINT], "USD");
+		eth.interval = 10;
+		assert_ok!(OCW::set_feed(RuntimeOrigin::root(), BTC, feed(&[LOCAL_ENDPOINT], "USD")));
+		assert_ok!(OCW::set_feed(RuntimeOrigin::root(), ETH, eth));
+
+		for (who, btc, eth) in [(ALICE, 2_700_000, 180_000), (BOB, 2_700_000, 190_000)] {
+			assert_ok!(OCW::submit_price(RuntimeOrigin::signed(who), BTC, price(btc)));
+			assert_ok!(OCW::submit_price(RuntimeOrigin::signed(who), ETH, price(eth)));
+		}
+
+		assert_eq!(prices(BTC), vec![price(2_700_000)]);
+		assert_eq!(prices(ETH), vec![price(185_000)]);
+		assert_eq!(OCW::next_round_at(BTC), 129);
+		assert_eq!(OCW::next_round_at(ETH), 11);
+	});
+}
+
+#[test]
+fn submit_price_fails() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			OCW::submit_price(RuntimeOrigin::signed(ALICE), ETH, price(180_000)),
+			Error::<Test>::UnknownPair
+		);
+
+		assert_ok!(OCW::set_feed(RuntimeOrigin::root(), ETH, feed(&[LOCAL_ENDPOINT], "USD")));
+		assert_noop!(
+			OCW::submit_price(RuntimeOrigin::signed(DAVE), ETH, price(180_000)),
+			Error::<Test>::NotMember
+		);
+
+		assert_ok!(OCW::submit_price(RuntimeOrigin::signed(ALICE), ETH, price(180_000)));
+		assert_noop!(
+			OCW::submit_price(RuntimeOrigin::signed(ALICE), ETH, price(190_000)),
+			Error::<Test>::AlreadySubmitted
+		);
+
+		assert_ok!(OCW::submit_price(RuntimeOrigin::signed(BOB), ETH, price(180_000)));
+		assert_noop!(
+			OCW::submit_price(RuntimeOrigin::signed(CHARLIE), ETH, price(180_000)),
+			Error::<Test>::RoundNotOpen
+		);
+	});
+}
+
+#[test]
+fn submit_price_drops_the_submissions_of_an_expired_round() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(OCW::set_feed(RuntimeOrigin::root(), BTC, feed(&[LOCAL_ENDPOINT], "USD")));
+		assert_ok!(OCW::submit_price(RuntimeOrigin::signed(ALICE), BTC, price(100)));
+
+		// not finalised within the interval (128)
+		System::set_block_number(129);
+		assert_ok!(OCW::submit_price(RuntimeOrigin::signed(BOB), BTC, price(200)));
+		assert!(prices(BTC).is_empty());
+		assert_eq!(OCW::submissions(BTC).into_inner(), vec![(BOB, price(200))]);
+		assert_eq!(OCW::next_round_at(BTC), 128);
+
+		// ALICE can submit again in the new round
+		assert_ok!(OCW::submit_price(RuntimeOrigin::signed(ALICE), BTC, price(300)));
+		assert_eq!(prices(BTC), vec![price(250)]);
+		assert_eq!(OCW::next_round_at(BTC), 257);
+	});
+}
+
+#[test]
+fn submit_price_unsigned_with_signed_payload_counts_for_the_signer() {
+	new_test_ext().execute_with(|| {
+		let key = sr25519::Pair::from_seed(&[7u8; 32]);
+		assert_ok!(OCW::set_feed(RuntimeOrigin::root(), BTC, feed(&[LOCAL_ENDPOINT], "USD")));
+		assert_ok!(OCW::add_member(RuntimeOrigin::root(), key.public()));
+
+		let (price_payload, signature) = signed_payload(&key, &key, BTC, price(2_700_000));
+		assert_ok!(OCW::submit_price_unsigned_with_signed_payload(
+			RuntimeOrigin::none(),
+			price_payload,
+			signature
+		));
+
+		assert_eq!(OCW::submissions(BTC).into_inner(), vec![(key.public(), price(2_700_000))]);
+	});
+}
+
+//=====validate_unsigned=====
+
+#[test]
+fn validate_unsigned_only_accepts_members() {
+	new_test_ext().execute_with(|| {
+		let key = sr25519::Pair::from_seed(&[7u8; 32]);
+		let call = signed_submission(&key, BTC, price(2_700_000));
+		assert_ok!(OCW::set_feed(RuntimeOrigin::root(), BTC, feed(&[LOCAL_ENDPOINT], "USD")));
+
+		assert_eq!(
+			OCW::validate_unsigned(TransactionSource::External, &call),
+			InvalidTransaction::BadSigner.into()
+		);
+
+		assert_ok!(OCW::add_member(RuntimeOrigin::root(), key.public()));
+		assert_ok!(OCW::validate_unsigned(TransactionSource::External, &call));
+	});
+}
+
+#[test]
+fn validate_unsigned_rejects_invalid_submissions() {
+	new_test_ext().execute_with(|| {
+		let key = sr25519::Pair::from_seed(&[7u8; 32]);
+		assert_ok!(OCW::add_member(RuntimeOrigin::root(), key.public()));
+
+		let call = signed_submission(&key, ETH, price(180_000));
+		assert_eq!(
+			OCW::validate_unsigned(TransactionSource::External, &call),
+			InvalidTransaction::Call.into()
+		);
+
+		assert_ok!(OCW::set_feed(RuntimeOrigin::root(), ETH, feed(&[LOCAL_ENDPOINT], "USD")));
+		let other = sr25519::Pair::from_seed(&[8u8; 32]);
+		let (price_payload, signature) = signed_payload(&key, &other, ETH, price(180_000));
+		let forged = Call::submit_price_unsigned_with_signed_payload { price_payload, signature };
+		assert_eq!(
+			OCW::validate_unsigned(TransactionSource::External, &forged),
+			InvalidTransaction::BadProof.into()
+		);
+
+		assert_ok!(OCW::submit_price(RuntimeOrigin::signed(key.public()), ETH, price(180_000)));
+		assert_eq!(
+			OCW::validate_unsigned(TransactionSource::External, &call),
+			InvalidTransaction::Stale.into()
+		);
+	});
+}
+
+//=====fetch_price=====
+
+#[test]
+fn fetch_price_queries_the_configured_source() {
+	let (offchain, state) = testing::TestOffchainExt::new();
+	let mut t = new_test_ext();
+	t.register_extension(OffchainWorkerExt::new(offchain));
+
+	expect_request(
+		&mut state.write(),
+		"http://localhost:8000/price?fsym=BTC&tsyms=USD",
+		br#"{"data": {"USD": 155.23}}"#,
+	);
+
+	t.execute_with(|| {
+		let feed = feed(&[LOCAL_ENDPOINT], "data.{quote}");
+		assert_eq!(
+			OCW::fetch_price(BTC, &feed).unwrap(),
+			FixedU128::saturating_from_rational(15523, 100)
+		);
+	});
+}
+
+#[test]
+fn fetch_price_skips_failed_sources() {
+	let (offchain, state) = testing::TestOffchainExt::new();
+	let mut t = new_test_ext();
+	t.register_extension(OffchainWorkerExt::new(offchain));
+
+	{
+		let mut state = state.write();
+		expect_request(&mut state, "http://localhost:8000/btc", br#"{"error": "unavailable"}"#);
+		expect_request(&mut state, "http://localhost:8001/btc", br#"{"USD": 42}"#);
+	}
+
+	t.execute_with(|| {
+		let feed = feed(&["http://localhost:8000/btc", "http://localhost:8001/btc"], "USD");
+		assert_eq!(OCW::fetch_price(BTC, &feed).unwrap(), price(42));
+	});
+}
+
+#[test]
+fn fetch_price_takes_the_median_of_the_agreeing_sources() {
+	let (offchain, state) = testing::TestOffchainExt::new();
+	let mut t = new_test_ext();
+	t.register_extension(OffchainWorkerExt::new(offchain));
+
+	{
+		let mut state = state.write();
+		expect_request(&mut state, "http://localhost:8000/btc", br#"{"USD": 100}"#);
+		expect_request(&mut state, "http://localhost:8001/btc", br#"{"USD": 150}"#);
+		expect_request(&mut state, "http://localhost:8002/btc", br#"{"USD": 102}"#);
+	}
+
+	t.execute_with(|| {
+		let mut feed = feed(
+			&[
+				"http://localhost:8000/btc",
+				"http://localhost:8001/btc",
+				"http://localhost:8002/btc",
+			],
+			"USD",
+		);
+		feed.min_sources = 2;
+		// 150 is dropped, being more than 5% off the median 102
+		assert_eq!(OCW::fetch_price(BTC, &feed).unwrap(), price(101));
+	});
+}
+
+#[test]
+fn fetch_price_fails_when_too_few_sources_agree() {
+	let (offchain, state) = testing::TestOffchainExt::new();
+	let mut t = new_test_ext();
+	t.register_extension(OffchainWorkerExt::new(offchain));
+
+	{
+		let mut state = state.write();
+		expect_request(&mut state, "http://localhost:8000/btc", br#"{"USD": 100}"#);
+		expect_request(&mut state, "http://localhost:8001/btc", br#"{"USD": 150}"#);
+	}
+
+	t.execute_with(|| {
+		let mut feed = feed(&["http://localhost:8000/btc", "http://localhost:8001/btc"], "USD");
+		feed.min_sources = 2;
+		assert!(OCW::fetch_price(BTC, &feed).is_err());
+	});
+}
+
+#[test]
+fn local_override_takes_precedence_over_the_on_chain_source() {
+	let (offchain, state) = testing::TestOffchainExt::new();
+	let mut t = new_test_ext();
+	t.register_extension(OffchainWorkerExt::new(offchain));
+
+	expect_request(&mut state.write(), "http://127.0.0.1:9933/price", br#"{"USD": 7.5}"#);
+
+	t.execute_with(|| {
+		let feed = feed(&["https://example.com/price"], "USD");
+		let local = source(&["http://127.0.0.1:9933/price"], "USD");
+		StorageValueRef::persistent(&source_override_key(BTC)).set(&local);
+
+		assert_eq!(OCW::effective_source(BTC, &feed), local);
+		assert_eq!(OCW::effective_source(ETH, &feed), feed.source);
+		assert_eq!(
+			OCW::fetch_price(BTC, &feed).unwrap(),
+			FixedU128::saturating_from_rational(75, 10)
+		);
+	});
+}
+
+#[test]
+fn knows_how_to_mock_several_http_calls() {
+	let (offchain, state) = testing::TestOffchainExt::new();
+	let mut t = new_test_ext();
+	t.register_extension(OffchainWorkerExt::new(offchain));
+
+	{
+		let mut state = state.write();
+		for response in [br#"{"USD": 1}"#, br#"{"USD": 2}"#, br#"{"USD": 3}"#] {
+			expect_request(&mut state, "http://localhost:8000/price?fsym=BTC&tsyms=USD", response);
+		}
+	}
+
+	t.execute_with(|| {
+		let feed = feed(&[LOCAL_ENDPOINT], "USD");
+		let price1 = OCW::fetch_price(BTC, &feed).unwrap();
+		let price2 = OCW::fetch_price(BTC, &feed).unwrap();
+		let price3 = OCW::fetch_price(BTC, &feed).unwrap();
+
+		assert_eq!(price1, price(1));
+		assert_eq!(price2, price(2));
+		assert_eq!(price3, price(3));
+	})
+}
+
+//=====offchain_worker=====
+
+fn price_oracle_response(state: &mut testing::OffchainState) {
+	expect_request(state, "http://localhost:8000/price?fsym=BTC&tsyms=USD", br#"{"USD": 155.23}"#);
+}
+
+/// The unsigned price payload `tx` carries, after checking its signature.
+fn verified_payload(tx: Extrinsic) -> PricePayload<sr25519::Public, u64> {
+	assert_eq!(tx.signature, None);
+	match tx.call {
+		RuntimeCall::OCW(Call::submit_price_unsigned_with_signed_payload {
+			price_payload,
+			signature,
+		}) => {
+			assert!(<PricePayload<_, _> as SignedPayload<Test>>::verify::<crypto::TestAuthId>(
+				&price_payload,
+				signature
+			));
+			price_payload
+		},
+		call => panic!("Unexpected call {:?}", call),
+	}
+}
+
+#[test]
+fn should_submit_signed_transaction_on_chain() {
+	let (offchain, offchain_state) = testing::TestOffchainExt::new();
+	let (pool, pool_state) = testing::TestTransactionPoolExt::new();
+	let mut t = new_test_ext();
+	t.register_extension(OffchainWorkerExt::new(offchain));
+	t.register_extension(TransactionPoolExt::new(pool));
+	register_keys(&mut t, &["hunter1"]);
+
+	price_oracle_response(&mut offchain_state.write());
+
+	t.execute_with(|| {
+		assert_ok!(OCW::set_feed(RuntimeOrigin::root(), BTC, feed(&[LOCAL_ENDPOINT], "USD")));
+		// when
+		OCW::fetch_price_and_send_signed(1).unwrap();
+		// then
+		let tx = pool_state.write().transactions.pop().unwrap();
+		assert!(pool_state.read().transactions.is_empty());
+		let tx = Extrinsic::decode(&mut &*tx).unwrap();
+		assert_eq!(tx.signature.unwrap().0, 0);
+		assert_eq!(
+			tx.call,
+			RuntimeCall::OCW(Call::submit_price {
+				pair: BTC,
+				price: FixedU128::saturating_from_rational(15523, 100)
+			})
+		);
+	});
+}
+
+#[test]
+fn should_submit_unsigned_transaction_on_chain_for_any_account() {
+	let (offchain, offchain_state) = testing::TestOffchainExt::new();
+	let (pool, pool_state) = testing::TestTransactionPoolExt::new();
+	let mut t = new_test_ext();
+	t.register_extension(OffchainWorkerExt::new(offchain));
+	t.register_extension(TransactionPoolExt::new(pool));
+	let keys = register_keys(&mut t, &["hunter1", "hunter2"]);
+
+	price_oracle_response(&mut offchain_state.write());
+
+	t.execute_with(|| {
+		assert_ok!(OCW::set_feed(RuntimeOrigin::root(), BTC, feed(&[LOCAL_ENDPOINT], "USD")));
+		// when
+		OCW::fetch_price_and_send_unsigned_for_any_account(1).unwrap();
+		// then
+		let tx = pool_state.write().transactions.pop().unwrap();
+		assert!(pool_state.read().transactions.is_empty());
+		let payload = verified_payload(Extrinsic::decode(&mut &*tx).unwrap());
+		assert_eq!(payload.pair, BTC);
+		assert_eq!(payload.price, FixedU128::saturating_from_rational(15523, 100));
+		assert_eq!(payload.block_number, 1);
+		assert!(keys.contains(&payload.public));
+	});
+}
+
+#[test]
+fn should_submit_unsigned_transaction_on_chain_for_all_accounts() {
+	let (offchain, offchain_state) = testing::TestOffchainExt::new();
+	let (pool, pool_state) = testing::TestTransactionPoolExt::new();
+	let mut t = new_test_ext();
+	t.register_extension(OffchainWorkerExt::new(offchain));
+	t.register_extension(TransactionPoolExt::new(pool));
+	let mut keys = register_keys(&mut t, &["hunter1", "hunter2"]);
+
+	price_oracle_response(&mut offchain_state.write());
+
+	t.execute_with(|| {
+		assert_ok!(OCW::set_feed(RuntimeOrigin::root(), BTC, feed(&[LOCAL_ENDPOINT], "USD")));
+		// when
+		OCW::fetch_price_and_send_unsigned_for_all_accounts(1).unwrap();
+		// then a payload signed by each key
+		let mut signers: Vec<_> = pool_state
+			.read()
+			.transactions
+			.iter()
+			.map(|tx| verified_payload(Extrinsic::decode(&mut &**tx).unwrap()).public)
+			.collect();
+		signers.sort();
+		keys.sort();
+		assert_eq!(signers, keys);
+	});
+}
+
+#[test]
+fn offchain_worker_submits_with_the_configured_strategy() {
+	for (strategy, signed, unsigned) in [
+		(SubmissionStrategy::Signed, 2, 0),
+		(SubmissionStrategy::UnsignedForAny, 0, 1),
+		(SubmissionStrategy::UnsignedForAll, 0, 2),
+	] {
+		let (offchain, offchain_state) = testing::TestOffchainExt::new();
+		let (pool, pool_state) = testing::TestTransactionPoolExt::new();
+		let mut t = new_test_ext();
+		t.register_extension(OffchainWorkerExt::new(offchain));
+		t.register_extension(TransactionPoolExt::new(pool));
+		register_keys(&mut t, &["hunter1", "hunter2"]);
+
+		price_oracle_response(&mut offchain_state.write());
+
+		t.execute_with(|| {
+			Strategy::set(strategy);
+			assert_ok!(OCW::set_feed(RuntimeOrigin::root(), BTC, feed(&[LOCAL_ENDPOINT], "USD")));
+
+			OCW::offchain_worker(1);
+			// nothing sent within the `GracePeriod`
+			OCW::offchain_worker(2);
+
+			let txs: Vec<_> = pool_state
+				.read()
+				.transactions
+				.iter()
+				.map(|tx| Extrinsic::decode(&mut &**tx).unwrap())
+				.collect();
+			assert_eq!(txs.iter().filter(|tx| tx.signature.is_some()).count(), signed);
+			assert_eq!(txs.iter().filter(|tx| tx.signature.is_none()).count(), unsigned);
+		});
+	}
+}
+
+//=====aggregate_prices=====
+
+#[test]
+fn aggregate_prices_works() {
+	let five_percent = Percent::from_percent(5);
+	let test_data = vec![
+		(vec![], 1, None),
+		(vec![("a", 100)], 1, Some(100)),
+		(vec![("a", 100), ("b", 104)], 2, Some(102)),
+		(vec![("a", 100), ("b", 102), ("c", 150)], 2, Some(101)),
+		(vec![("a", 100), ("b", 102), ("c", 150)], 3, None),
+		(vec![("a", 10), ("b", 100), ("c", 1_000)], 1, Some(100)),
+		(vec![("a", u128::MAX), ("b", u128::MAX)], 2, Some(u128::MAX)),
+	];
+
+	for (prices, min_sources, expected) in test_data {
+		let prices =
+			prices.into_iter().map(|(source, inner)| (source, FixedU128::from_inner(inner)));
+		assert_eq!(
+			OCW::aggregate_prices(prices.collect(), min_sources, five_percent),
+			expected.map(FixedU128::from_inner)
+		);
+	}
+}
+
+//=====price_history=====
+
+#[test]
+fn price_history_overwrites_the_oldest_price_when_full() {
+	// `MaxPrices` is 64
+	let history = history(&(1..=70).map(|price| (price, price as u64)).collect::<Vec<_>>());
+
+	assert_eq!(history.points.len(), 64);
+	assert_eq!(history.cursor, 6);
+	assert_eq!(
+		history.to_vec().iter().map(|point| point.price).collect::<Vec<_>>(),
+		(7..=70).map(price).collect::<Vec<_>>()
+	);
+	assert_eq!(history.latest(), Some(&PricePoint { price: price(70), block_number: 70 }));
+	assert_eq!(history.mean(), Some(FixedU128::saturating_from_rational(77, 2)));
+}
+
+#[test]
+fn price_history_averages_the_prices() {
+	let empty = history(&[]);
+	assert_eq!(empty.latest(), None);
+	assert_eq!(empty.mean(), None);
+	assert_eq!(empty.ema(), None);
+	assert_eq!(empty.twap(10, 10), None);
+
+	let three = history(&[(100, 1), (200, 11), (1_000, 21)]);
+	assert_eq!(three.mean(), Some(FixedU128::from_inner(433_333_333_333_333_333_333)));
+	// 20% of each new price
+	assert_eq!(three.ema(), Some(price(296)));
+
+	let history = history(&[(100, 1), (200, 11)]);
+	// 100 for 10 blocks, 200 for 10 blocks
+	assert_eq!(history.twap(21, 20), Some(price(150)));
+	// 100 for 5 blocks, 200 for 10 blocks
+	assert_eq!(history.twap(21, 15), Some(FixedU128::from_inner(166_666_666_666_666_666_666)));
+	assert_eq!(history.twap(21, 5), Some(price(200)));
+	// the window is cut to the oldest price
+	assert_eq!(history.twap(21, 100), Some(price(150)));
+	// no block elapsed since the latest price
+	assert_eq!(history.twap(11, 0), Some(price(200)));
+}
+
+#[test]
+fn price_provider_works() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(OCW::latest_price(BTC), Err(PriceError::UnknownPair));
+
+		assert_ok!(OCW::set_feed(RuntimeOrigin::root(), BTC, feed(&[LOCAL_ENDPOINT], "USD")));
+		assert_eq!(OCW::latest_price(BTC), Err(PriceError::NoPrice));
+		assert_eq!(OCW::mean_price(BTC), Err(PriceError::NoPrice));
+		assert_eq!(OCW::twap(BTC, 10), Err(PriceError::NoPrice));
+		assert_eq!(OCW::ema(BTC), Err(PriceError::NoPrice));
+
+		// at blocks 1 & 129
+		finalise_round(BTC, price(100));
+		finalise_round(BTC, price(105));
+		System::set_block_number(139);
+
+		assert_eq!(OCW::latest_price(BTC), Ok(price(105)));
+		assert_eq!(OCW::mean_price(BTC), Ok(FixedU128::saturating_from_rational(205, 2)));
+		assert_eq!(OCW::twap(BTC, 20), Ok(FixedU128::saturating_from_rational(205, 2)));
+		assert_eq!(OCW::ema(BTC), Ok(price(101)));
+	});
+}
+
+#[test]
+fn price_provider_fails_once_stale() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(OCW::set_feed(RuntimeOrigin::root(), BTC, feed(&[LOCAL_ENDPOINT], "USD")));
+		Timestamp::set_timestamp(42_000);
+		finalise_round(BTC, price(100));
+		assert_eq!(OCW::last_update(BTC), Some(PriceUpdate { block_number: 1, moment: 42_000 }));
+
+		// `MaxStaleness` is 200
+		System::set_block_number(201);
+		assert_eq!(OCW::latest_price(BTC), Ok(price(100)));
+
+		System::set_block_number(202);
+		assert_eq!(OCW::latest_price(BTC), Err(PriceError::Stale));
+		assert_eq!(OCW::mean_price(BTC), Err(PriceError::Stale));
+		assert_eq!(OCW::twap(BTC, 10), Err(PriceError::Stale));
+		assert_eq!(OCW::ema(BTC), Err(PriceError::Stale));
+
+		// fresh again with the next price
+		finalise_round(BTC, price(101));
+		assert_eq!(OCW::last_update(BTC), Some(PriceUpdate { block_number: 202, moment: 42_000 }));
+		assert_eq!(OCW::latest_price(BTC), Ok(price(101)));
+	});
+}
+
+//=====circuit_breaker=====
+
+#[test]
+fn price_moving_beyond_the_max_change_is_held_till_confirmed() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(OCW::set_feed(RuntimeOrigin::root(), BTC, feed(&[LOCAL_ENDPOINT], "USD")));
+		finalise_round(BTC, price(100));
+
+		// `MaxPriceChange` is 10%
+		finalise_round(BTC, price(150));
+		System::assert_last_event(
+			Event::PriceHeld { pair: BTC, price: price(150), previous: price(100) }.into(),
+		);
+		assert_eq!(prices(BTC), vec![price(100)]);
+		assert_eq!(OCW::pending_price(BTC), Some(price(150)));
+		assert_eq!(OCW::last_update(BTC).unwrap().block_number, 1);
+		// the round is finalised all the same
+		assert_eq!(OCW::next_round_at(BTC), 257);
+
+		// confirmed by the next round
+		finalise_round(BTC, price(145));
+		System::assert_last_event(
+			Event::NewPrice { pair: BTC, price: price(145), who_maybe: None }.into(),
+		);
+		assert_eq!(prices(BTC), vec![price(100), price(145)]);
+		assert_eq!(OCW::pending_price(BTC), None);
+		assert_eq!(OCW::last_update(BTC).unwrap().block_number, 257);
+	});
+}
+
+#[test]
+fn held_price_is_dropped_unless_confirmed() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(OCW::set_feed(RuntimeOrigin::root(), BTC, feed(&[LOCAL_ENDPOINT], "USD")));
+		finalise_round(BTC, price(100));
+
+		finalise_round(BTC, price(150));
+		// neither within 10% of 100 nor of 150
+		finalise_round(BTC, price(200));
+		System::assert_last_event(
+			Event::PriceHeld { pair: BTC, price: price(200), previous: price(100) }.into(),
+		);
+		assert_eq!(OCW::pending_price(BTC), Some(price(200)));
+
+		// back within 10% of 100
+		finalise_round(BTC, price(105));
+		assert_eq!(prices(BTC), vec![price(100), price(105)]);
+		assert_eq!(OCW::pending_price(BTC), None);
+	});
+}
+
+//=====parse_price=====
+
+#[test]
+fn parse_price_follows_the_json_path() {
+	let test_data = vec![
+		(r#"{"USD":6536.92}"#, "USD", Some((653692, 100))),
+		(r#"{"USD":6536.924565}"#, "USD", Some((6536924565, 1_000_000))),
+		(r#"{"USD":6536}"#, "USD", Some((6536, 1))),
+		(r#"{"data":{"BTC":{"USD":65.92}}}"#, "data.BTC.USD", Some((6592, 100))),
+		(r#"{"USD":"6432.5"}"#, "USD", Some((64325, 10))),
+		(r#"{"USD2":6536}"#, "USD", None),
+		(r#"{"USD":"price"}"#, "USD", None),
+		(r#"{"USD":true}"#, "USD", None),
+		(r#"{"data":6536}"#, "data.USD", None),
+	];
+
+	for (json, json_path, expected) in test_data {
+		assert_eq!(
+			OCW::parse_price(json, json_path),
+			expected.map(|(n, d)| FixedU128::saturating_from_rational(n, d))
+		);
+	}
+}
+
+#[test]
+fn parse_price_handles_any_precision_and_magnitude() {
+	let test_data = vec![
+		(r#"{"USD":0.000123456}"#, Some(123_456_000_000_000)),
+		(r#"{"USD":6.53692e3}"#, Some(6_536_920_000_000_000_000_000)),
+		(r#"{"USD":1.5E-2}"#, Some(15_000_000_000_000_000)),
+		(r#"{"USD":3e20}"#, Some(300_000_000_000_000_000_000_000_000_000_000_000_000)),
+		(
+			r#"{"USD":"0.1234567890123456789012345678901234567890123"}"#,
+			Some(123_456_789_012_345_678),
+		),
+		(r#"{"USD":"12.5e2"}"#, Some(1_250_000_000_000_000_000_000)),
+		(r#"{"USD":"1.5e-18"}"#, Some(1)),
+		(r#"{"USD":"1e-19"}"#, Some(0)),
+		// negative
+		(r#"{"USD":-1.5}"#, None),
+		(r#"{"USD":"-1.5"}"#, None),
+		// beyond `FixedU128::max_value()`
+		(r#"{"USD":1e21}"#, None),
+		(r#"{"USD":"400000000000000000000"}"#, None),
+		(r#"{"USD":"1e2147483647"}"#, None),
+		// malformed
+		(r#"{"USD":".5"}"#, None),
+		(r#"{"USD":"1.2.3"}"#, None),
+		(r#"{"USD":"1e"}"#, None),
+	];
+
+	for (json, expected) in test_data {
+		assert_eq!(OCW::parse_price(json, "USD"), expected.map(FixedU128::from_inner), "{json}");
+	}
+}
+
+// ===== migrations =====
+
+#[test]
+fn migration_to_v1_removes_the_v0_prices() {
+	use crate::migrations::v1::MigrateToV1;
+	use frame_support::{
+		storage::{storage_prefix, unhashed},
+		traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	};
+
+	new_test_ext().execute_with(|| {
+		let mut history = PriceHistory::<Test>::default();
+		let price = FixedU128::saturating_from_integer(100);
+		history.push(PricePoint { price, block_number: 1 });
+		crate::Prices::<Test>::insert(BTC, history.clone());
+
+		// the v0 values, at the prefix of the v1 `Prices` map
+		let old_prices = storage_prefix(b"OCW", b"Prices");
+		let old_next_unsigned_at = storage_prefix(b"OCW", b"NextUnsignedAt");
+		unhashed::put(&old_prices, &vec![1u32, 2, 3]);
+		unhashed::put(&old_next_unsigned_at, &5u64);
+		StorageVersion::new(0).put::<OCW>();
+
+		MigrateToV1::<Test>::on_runtime_upgrade();
+		assert_eq!(OCW::on_chain_storage_version(), 1);
+		assert!(!unhashed::exists(&old_prices));
+		assert!(!unhashed::exists(&old_next_unsigned_at));
+		assert_eq!(crate::Prices::<Test>::iter().collect::<Vec<_>>(), vec![(BTC, history)]);
+
+		// runs only once
+		unhashed::put(&old_prices, &vec![1u32]);
+		MigrateToV1::<Test>::on_runtime_upgrade();
+		assert!(unhashed::exists(&old_prices));
+	});
+}
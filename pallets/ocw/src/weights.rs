@@ -0,0 +1,189 @@
+//! Weights for pallet_ocw
+//!
+//! NOTE: Not generated by the benchmark CLI yet. The storage accesses follow the worst cases set
+//! up in `src/benchmarking.rs`, but the execution times are placeholders, not measurements.
+//! Regenerate via `./scripts/benchmark.sh ocw` before relying on them.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for pallet_ocw.
+pub trait WeightInfo {
+	fn submit_price() -> Weight;
+	fn submit_price_unsigned_with_signed_payload() -> Weight;
+	fn set_feed() -> Weight;
+	fn remove_feed() -> Weight;
+	fn add_member() -> Weight;
+	fn remove_member() -> Weight;
+}
+
+/// Weights for pallet_ocw using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: OCW Members (r:1 w:0)
+	/// Proof: OCW Members (max_values: Some(1), max_size: Some(513), added: 1008, mode: MaxEncodedLen)
+	/// Storage: OCW Feeds (r:1 w:0)
+	/// Proof: OCW Feeds (max_values: None, max_size: Some(3060), added: 5535, mode: MaxEncodedLen)
+	/// Storage: OCW NextRoundAt (r:1 w:1)
+	/// Proof: OCW NextRoundAt (max_values: None, max_size: Some(16), added: 2491, mode: MaxEncodedLen)
+	/// Storage: OCW Submissions (r:1 w:1)
+	/// Proof: OCW Submissions (max_values: None, max_size: Some(781), added: 3256, mode: MaxEncodedLen)
+	/// Storage: OCW Prices (r:1 w:1)
+	/// Proof: OCW Prices (max_values: None, max_size: Some(1313), added: 3788, mode: MaxEncodedLen)
+	/// Storage: OCW PendingPrices (r:1 w:1)
+	/// Proof: OCW PendingPrices (max_values: None, max_size: Some(28), added: 2503, mode: MaxEncodedLen)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: OCW LastUpdates (r:0 w:1)
+	/// Proof: OCW LastUpdates (max_values: None, max_size: Some(24), added: 2499, mode: MaxEncodedLen)
+	fn submit_price() -> Weight {
+		Weight::from_parts(34_000_000, 19084)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: OCW Members (r:1 w:0)
+	/// Proof: OCW Members (max_values: Some(1), max_size: Some(513), added: 1008, mode: MaxEncodedLen)
+	/// Storage: OCW Feeds (r:1 w:0)
+	/// Proof: OCW Feeds (max_values: None, max_size: Some(3060), added: 5535, mode: MaxEncodedLen)
+	/// Storage: OCW NextRoundAt (r:1 w:1)
+	/// Proof: OCW NextRoundAt (max_values: None, max_size: Some(16), added: 2491, mode: MaxEncodedLen)
+	/// Storage: OCW Submissions (r:1 w:1)
+	/// Proof: OCW Submissions (max_values: None, max_size: Some(781), added: 3256, mode: MaxEncodedLen)
+	/// Storage: OCW Prices (r:1 w:1)
+	/// Proof: OCW Prices (max_values: None, max_size: Some(1313), added: 3788, mode: MaxEncodedLen)
+	/// Storage: OCW PendingPrices (r:1 w:1)
+	/// Proof: OCW PendingPrices (max_values: None, max_size: Some(28), added: 2503, mode: MaxEncodedLen)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: OCW LastUpdates (r:0 w:1)
+	/// Proof: OCW LastUpdates (max_values: None, max_size: Some(24), added: 2499, mode: MaxEncodedLen)
+	fn submit_price_unsigned_with_signed_payload() -> Weight {
+		Weight::from_parts(35_000_000, 19084)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: OCW Feeds (r:0 w:1)
+	/// Proof: OCW Feeds (max_values: None, max_size: Some(3060), added: 5535, mode: MaxEncodedLen)
+	fn set_feed() -> Weight {
+		Weight::from_parts(16_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: OCW Feeds (r:1 w:1)
+	/// Proof: OCW Feeds (max_values: None, max_size: Some(3060), added: 5535, mode: MaxEncodedLen)
+	/// Storage: OCW Prices (r:0 w:1)
+	/// Proof: OCW Prices (max_values: None, max_size: Some(1313), added: 3788, mode: MaxEncodedLen)
+	/// Storage: OCW LastUpdates (r:0 w:1)
+	/// Proof: OCW LastUpdates (max_values: None, max_size: Some(24), added: 2499, mode: MaxEncodedLen)
+	/// Storage: OCW PendingPrices (r:0 w:1)
+	/// Proof: OCW PendingPrices (max_values: None, max_size: Some(28), added: 2503, mode: MaxEncodedLen)
+	/// Storage: OCW NextRoundAt (r:0 w:1)
+	/// Proof: OCW NextRoundAt (max_values: None, max_size: Some(16), added: 2491, mode: MaxEncodedLen)
+	/// Storage: OCW Submissions (r:0 w:1)
+	/// Proof: OCW Submissions (max_values: None, max_size: Some(781), added: 3256, mode: MaxEncodedLen)
+	fn remove_feed() -> Weight {
+		Weight::from_parts(25_000_000, 6525)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	/// Storage: OCW Members (r:1 w:1)
+	/// Proof: OCW Members (max_values: Some(1), max_size: Some(513), added: 1008, mode: MaxEncodedLen)
+	fn add_member() -> Weight {
+		Weight::from_parts(14_000_000, 1998)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: OCW Members (r:1 w:1)
+	/// Proof: OCW Members (max_values: Some(1), max_size: Some(513), added: 1008, mode: MaxEncodedLen)
+	fn remove_member() -> Weight {
+		Weight::from_parts(14_000_000, 1998)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	/// Storage: OCW Members (r:1 w:0)
+	/// Proof: OCW Members (max_values: Some(1), max_size: Some(513), added: 1008, mode: MaxEncodedLen)
+	/// Storage: OCW Feeds (r:1 w:0)
+	/// Proof: OCW Feeds (max_values: None, max_size: Some(3060), added: 5535, mode: MaxEncodedLen)
+	/// Storage: OCW NextRoundAt (r:1 w:1)
+	/// Proof: OCW NextRoundAt (max_values: None, max_size: Some(16), added: 2491, mode: MaxEncodedLen)
+	/// Storage: OCW Submissions (r:1 w:1)
+	/// Proof: OCW Submissions (max_values: None, max_size: Some(781), added: 3256, mode: MaxEncodedLen)
+	/// Storage: OCW Prices (r:1 w:1)
+	/// Proof: OCW Prices (max_values: None, max_size: Some(1313), added: 3788, mode: MaxEncodedLen)
+	/// Storage: OCW PendingPrices (r:1 w:1)
+	/// Proof: OCW PendingPrices (max_values: None, max_size: Some(28), added: 2503, mode: MaxEncodedLen)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: OCW LastUpdates (r:0 w:1)
+	/// Proof: OCW LastUpdates (max_values: None, max_size: Some(24), added: 2499, mode: MaxEncodedLen)
+	fn submit_price() -> Weight {
+		Weight::from_parts(34_000_000, 19084)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: OCW Members (r:1 w:0)
+	/// Proof: OCW Members (max_values: Some(1), max_size: Some(513), added: 1008, mode: MaxEncodedLen)
+	/// Storage: OCW Feeds (r:1 w:0)
+	/// Proof: OCW Feeds (max_values: None, max_size: Some(3060), added: 5535, mode: MaxEncodedLen)
+	/// Storage: OCW NextRoundAt (r:1 w:1)
+	/// Proof: OCW NextRoundAt (max_values: None, max_size: Some(16), added: 2491, mode: MaxEncodedLen)
+	/// Storage: OCW Submissions (r:1 w:1)
+	/// Proof: OCW Submissions (max_values: None, max_size: Some(781), added: 3256, mode: MaxEncodedLen)
+	/// Storage: OCW Prices (r:1 w:1)
+	/// Proof: OCW Prices (max_values: None, max_size: Some(1313), added: 3788, mode: MaxEncodedLen)
+	/// Storage: OCW PendingPrices (r:1 w:1)
+	/// Proof: OCW PendingPrices (max_values: None, max_size: Some(28), added: 2503, mode: MaxEncodedLen)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: OCW LastUpdates (r:0 w:1)
+	/// Proof: OCW LastUpdates (max_values: None, max_size: Some(24), added: 2499, mode: MaxEncodedLen)
+	fn submit_price_unsigned_with_signed_payload() -> Weight {
+		Weight::from_parts(35_000_000, 19084)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: OCW Feeds (r:0 w:1)
+	/// Proof: OCW Feeds (max_values: None, max_size: Some(3060), added: 5535, mode: MaxEncodedLen)
+	fn set_feed() -> Weight {
+		Weight::from_parts(16_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: OCW Feeds (r:1 w:1)
+	/// Proof: OCW Feeds (max_values: None, max_size: Some(3060), added: 5535, mode: MaxEncodedLen)
+	/// Storage: OCW Prices (r:0 w:1)
+	/// Proof: OCW Prices (max_values: None, max_size: Some(1313), added: 3788, mode: MaxEncodedLen)
+	/// Storage: OCW LastUpdates (r:0 w:1)
+	/// Proof: OCW LastUpdates (max_values: None, max_size: Some(24), added: 2499, mode: MaxEncodedLen)
+	/// Storage: OCW PendingPrices (r:0 w:1)
+	/// Proof: OCW PendingPrices (max_values: None, max_size: Some(28), added: 2503, mode: MaxEncodedLen)
+	/// Storage: OCW NextRoundAt (r:0 w:1)
+	/// Proof: OCW NextRoundAt (max_values: None, max_size: Some(16), added: 2491, mode: MaxEncodedLen)
+	/// Storage: OCW Submissions (r:0 w:1)
+	/// Proof: OCW Submissions (max_values: None, max_size: Some(781), added: 3256, mode: MaxEncodedLen)
+	fn remove_feed() -> Weight {
+		Weight::from_parts(25_000_000, 6525)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	/// Storage: OCW Members (r:1 w:1)
+	/// Proof: OCW Members (max_values: Some(1), max_size: Some(513), added: 1008, mode: MaxEncodedLen)
+	fn add_member() -> Weight {
+		Weight::from_parts(14_000_000, 1998)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: OCW Members (r:1 w:1)
+	/// Proof: OCW Members (max_values: Some(1), max_size: Some(513), added: 1008, mode: MaxEncodedLen)
+	fn remove_member() -> Weight {
+		Weight::from_parts(14_000_000, 1998)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+}
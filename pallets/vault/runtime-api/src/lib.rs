@@ -0,0 +1,43 @@
+//! Runtime API definition for the Vault pallet.
+//!
+//! The ERC-4626 style conversions between the tokens (assets) & the shares of a vault (the queries for
+//! an unknown vault return zero) & the balance history of an account.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+pub use pallet_vault::DiffBalances;
+
+sp_api::decl_runtime_apis! {
+	pub trait VaultApi<AccountId, Balance, VaultId, BlockNumber, Moment>
+	where
+		AccountId: Codec,
+		Balance: Codec,
+		VaultId: Codec,
+		BlockNumber: Codec,
+		Moment: Codec,
+	{
+		/// Total tokens (deposits & accrued interest) held by the vault.
+		fn total_assets(vault_id: VaultId) -> Balance;
+
+		/// Shares held by the account in the vault.
+		fn shares_of(vault_id: VaultId, who: AccountId) -> Balance;
+
+		/// Shares worth the `assets` as per the share price at the last accrual.
+		fn convert_to_shares(vault_id: VaultId, assets: Balance) -> Balance;
+
+		/// Assets worth the `shares` as per the share price at the last accrual.
+		fn convert_to_assets(vault_id: VaultId, shares: Balance) -> Balance;
+
+		/// Shares minted by depositing the `assets` in the current block.
+		fn preview_deposit(vault_id: VaultId, assets: Balance) -> Balance;
+
+		/// Assets paid by withdrawing (redeeming) the `shares` in the current block.
+		fn preview_redeem(vault_id: VaultId, shares: Balance) -> Balance;
+
+		/// Balance snapshots of the account, oldest first.
+		fn balance_history(who: AccountId) -> Vec<DiffBalances<Balance, BlockNumber, Moment>>;
+	}
+}
@@ -5,30 +5,200 @@ use super::*;
 #[allow(unused)]
 use crate::Pallet as Vault;
 use frame_benchmarking::v2::*;
+use frame_support::{
+	assert_ok,
+	sp_runtime::{Perbill, Saturating},
+	traits::{Currency, EnsureOrigin, Get},
+};
 use frame_system::RawOrigin;
 
+const SEED: u32 = 0;
+
+type BalanceOf<T> =
+	<<T as Config>::MyCurrency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// Tokens deposited to the vaults.
+fn amount<T: Config>() -> BalanceOf<T> {
+	T::MyCurrency::minimum_balance().saturating_mul(1_000u32.into())
+}
+
+/// Account with enough balance to fund a reward pool & deposit a few times.
+fn funded_account<T: Config>(name: &'static str, index: u32) -> T::AccountId {
+	let account: T::AccountId = account(name, index, SEED);
+	T::MyCurrency::make_free_balance_be(&account, amount::<T>().saturating_mul(1_000u32.into()));
+	account
+}
+
+fn advance_blocks<T: Config>(n: T::BlockNumber) {
+	frame_system::Pallet::<T>::set_block_number(
+		frame_system::Pallet::<T>::block_number().saturating_add(n),
+	);
+}
+
+/// Vault with an APY & a funded reward pool, where `depositor` has deposited. A year passes by,
+/// so that the interest is accrued by the next call.
+fn setup_vault<T: Config>(depositor: &T::AccountId) -> Result<VaultId, BenchmarkError> {
+	let origin = T::VaultOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+	let vault_id = NextVaultId::<T>::get();
+	assert_ok!(Vault::<T>::create_vault(
+		origin,
+		Perbill::from_percent(10),
+		T::MyCurrency::minimum_balance(),
+		amount::<T>().saturating_mul(10u32.into()),
+		1u32.into()
+	));
+
+	let funder = funded_account::<T>("funder", 0);
+	assert_ok!(Vault::<T>::fund_reward_pool(
+		RawOrigin::Signed(funder).into(),
+		vault_id,
+		amount::<T>()
+	));
+	assert_ok!(Vault::<T>::deposit(
+		RawOrigin::Signed(depositor.clone()).into(),
+		vault_id,
+		amount::<T>()
+	));
+
+	advance_blocks::<T>(T::BlocksPerYear::get());
+	Ok(vault_id)
+}
+
 #[benchmarks]
 mod benchmarks {
 	use super::*;
 
 	#[benchmark]
-	fn do_something() {
-		// let value = 100u32.into();
-		// let caller: T::AccountId = whitelisted_caller();
-		// #[extrinsic_call]
-		// do_something(RawOrigin::Signed(caller), value);
+	fn set_balance() {
+		let caller = funded_account::<T>("caller", 0);
+
+		#[extrinsic_call]
+		set_balance(RawOrigin::Signed(caller.clone()));
+
+		assert_eq!(Vault::<T>::balance_history(&caller).len(), 1);
+	}
+
+	// the ring buffer is full, so the oldest snapshot is overwritten
+	#[benchmark]
+	fn update_balance() {
+		let caller = funded_account::<T>("caller", 0);
+		assert_ok!(Vault::<T>::set_balance(RawOrigin::Signed(caller.clone()).into()));
+		for _ in 1..T::MaxBalanceSnapshots::get() {
+			assert_ok!(Vault::<T>::update_balance(RawOrigin::Signed(caller.clone()).into()));
+		}
+
+		#[extrinsic_call]
+		update_balance(RawOrigin::Signed(caller.clone()));
+
+		assert_eq!(
+			Vault::<T>::balance_history(&caller).len() as u32,
+			T::MaxBalanceSnapshots::get()
+		);
+	}
+
+	#[benchmark]
+	fn create_vault() -> Result<(), BenchmarkError> {
+		let origin =
+			T::VaultOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
 
-		// assert_eq!(Something::<T>::get(), Some(value));
+		#[block]
+		{
+			assert_ok!(Vault::<T>::create_vault(
+				origin,
+				Perbill::from_percent(10),
+				T::MyCurrency::minimum_balance(),
+				amount::<T>(),
+				1u32.into()
+			));
+		}
+
+		assert!(Vaults::<T>::contains_key(0));
+		Ok(())
+	}
+
+	// the interest pending for a year is accrued before minting
+	#[benchmark]
+	fn deposit() -> Result<(), BenchmarkError> {
+		let vault_id = setup_vault::<T>(&funded_account::<T>("depositor", 0))?;
+		let caller = funded_account::<T>("caller", 0);
+
+		#[extrinsic_call]
+		deposit(RawOrigin::Signed(caller.clone()), vault_id, amount::<T>());
+
+		assert!(!Shares::<T>::get(vault_id, &caller).is_zero());
+		Ok(())
+	}
+
+	// the caller has already unstaked some shares & the pending interest is accrued
+	#[benchmark]
+	fn unstake() -> Result<(), BenchmarkError> {
+		let caller = funded_account::<T>("caller", 0);
+		let vault_id = setup_vault::<T>(&caller)?;
+		let shares = Shares::<T>::get(vault_id, &caller) / 4u32.into();
+		assert_ok!(Vault::<T>::unstake(RawOrigin::Signed(caller.clone()).into(), vault_id, shares));
+		advance_blocks::<T>(T::BlocksPerYear::get());
+
+		#[extrinsic_call]
+		unstake(RawOrigin::Signed(caller.clone()), vault_id, shares);
+
+		assert_eq!(
+			Unstaking::<T>::get(vault_id, &caller).unwrap().shares,
+			shares.saturating_mul(2u32.into())
+		);
+		Ok(())
 	}
 
+	// all the shares are withdrawn after accruing the pending interest
 	#[benchmark]
-	fn cause_error() {
-		// Something::<T>::put(100u32);
-		// let caller: T::AccountId = whitelisted_caller();
-		// #[extrinsic_call]
-		// cause_error(RawOrigin::Signed(caller));
+	fn withdraw() -> Result<(), BenchmarkError> {
+		let caller = funded_account::<T>("caller", 0);
+		let vault_id = setup_vault::<T>(&caller)?;
+		let shares = Shares::<T>::get(vault_id, &caller);
+		assert_ok!(Vault::<T>::unstake(RawOrigin::Signed(caller.clone()).into(), vault_id, shares));
+		advance_blocks::<T>(T::BlocksPerYear::get());
+
+		#[extrinsic_call]
+		withdraw(RawOrigin::Signed(caller.clone()), vault_id);
+
+		assert!(Unstaking::<T>::get(vault_id, &caller).is_none());
+		assert!(Shares::<T>::get(vault_id, &caller).is_zero());
+		Ok(())
+	}
+
+	// the interest pending for a year is accrued before setting the APY
+	#[benchmark]
+	fn set_apy() -> Result<(), BenchmarkError> {
+		let vault_id = setup_vault::<T>(&funded_account::<T>("depositor", 0))?;
+		let origin =
+			T::VaultOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[block]
+		{
+			assert_ok!(Vault::<T>::set_apy(origin, vault_id, Perbill::from_percent(20)));
+		}
+
+		assert_eq!(Vaults::<T>::get(vault_id).unwrap().apy, Perbill::from_percent(20));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn fund_reward_pool() -> Result<(), BenchmarkError> {
+		let origin =
+			T::VaultOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		assert_ok!(Vault::<T>::create_vault(
+			origin,
+			Perbill::from_percent(10),
+			T::MyCurrency::minimum_balance(),
+			amount::<T>(),
+			1u32.into()
+		));
+		let caller = funded_account::<T>("caller", 0);
+
+		#[extrinsic_call]
+		fund_reward_pool(RawOrigin::Signed(caller), 0, amount::<T>());
 
-		// assert_eq!(Something::<T>::get(), Some(101u32));
+		assert_eq!(T::MyCurrency::free_balance(&Vault::<T>::reward_pool(0)), amount::<T>());
+		Ok(())
 	}
 
 	impl_benchmark_test_suite!(Vault, crate::mock::new_test_ext(), crate::mock::Test);
@@ -7,22 +7,48 @@
 //!
 //! The Vault pallet provides functionality for handling staking of tokens by users.
 //!
-//! When a user stakes tokens, they are added to the total staked tokens of the vault & individual vault.
-//! And when they unstake tokens, they are removed from the total staked tokens of the vault & individual vault.
+//! There can be multiple vaults (identified by a `VaultId`), each created by the `VaultOrigin` (say root) with its own
+//! APY, min/max stake, lock period & reward pool. A user can stake in several vaults at the same time.
 //!
-//! The accrued interest is calculated till the unstake timestamp.
+//! Each vault follows the ERC-4626 (tokenized vault) accounting. When a user stakes tokens, they are transferred to
+//! the vault account & the user is minted vault shares at the current share price i.e. total assets / total shares.
+//! And when they withdraw, the shares are burnt & the user is paid their value in tokens.
+//!
+//! The total assets of a vault earn interest every block at its APY, which is transferred from its reward pool (a
+//! pallet owned account funded via `fund_reward_pool`) to the vault account whenever the vault is touched. So, the
+//! yield is distributed pro-rata to the share holders (via the share price) without any per-user update. The
+//! interest is capped by the tokens available in the reward pool.
+//!
+//! The shares have to be unstaked first & can be withdrawn after the lock period of the vault. The unstaked shares
+//! keep earning interest till withdrawn.
+//!
+//! The conversions between tokens & shares (`convert_to_shares`, `convert_to_assets`) & the previews of
+//! `deposit` & `withdraw` (`preview_deposit`, `preview_redeem`) are exposed via the `VaultApi` runtime API
+//! (`pallet-vault-runtime-api`).
+//!
+//! ### Balance History
+//!
+//! Apart from the vaults, a user can record snapshots of its free, reserved & total balance (along with the block
+//! number & the `pallet_timestamp` moment) via `set_balance` & `update_balance`. The last `MaxBalanceSnapshots`
+//! snapshots per account are kept in a ring buffer i.e. the oldest one is overwritten once full. The history is
+//! exposed (oldest first) via the `VaultApi` runtime API for charting the balances over time.
 //!
 //! ### Terminology
 //!
-//! - **Vault**: where users stake their tokens.
+//! - **Vault**: where users stake their tokens. Identified by a `VaultId`.
 //! - **Stake**: lock tokens by users.
 //! - **Unstake**: unlock tokens unlocked by users.
 //! - **Total Staked Tokens**: total tokens staked by all users.
 //! - **Individual Staked Tokens**: tokens staked by a user.
 //! - **Accrued Interest**: interest earned by a user.
 //! - **Claimable Amount**: amount of tokens a user can claim including accrued interest.
+//! - **Shares**: claim of a user on the total assets of the vault.
+//! - **Share Price**: tokens per share i.e. total assets / total shares.
 //! - **Stake Timestamp**: timestamp when a user stakes its tokens.
 //! - **Unstake Timestamp**: timestamp when a user unstakes its tokens.
+//! - **Lock Period**: blocks after the unstake timestamp till which the unstaked shares can't be withdrawn.
+//! - **Min/Max Stake**: bounds on the tokens (worth of shares) a user can have staked in a vault.
+//! - **Reward Pool**: account of a vault from which the accrued interest is paid.
 //!
 //! ## Interface
 //!
@@ -35,26 +61,31 @@
 //!
 //! Actions:
 //!
-//! - `deposit`: Deposit tokens to its vault.
-//! - `unstake`: Unstake tokens from its vault.
-//! - `withdraw`: Withdraw tokens from its vault.
+//! - `deposit`: Deposit tokens to a vault.
+//! - `unstake`: Unstake shares from a vault.
+//! - `withdraw`: Withdraw (burn) the unstaked shares from a vault.
+//! - `fund_reward_pool`: Transfer tokens to the reward pool of a vault.
+//! - `set_balance`: Record the first snapshot of its balances.
+//! - `update_balance`: Record a snapshot of its balances.
 //!
-//! #### Root
-//! - `set_apy`: Set APY for the vault.
+//! #### Vault Origin (Root)
+//! - `create_vault`: Create a vault with its APY, min/max stake & lock period.
+//! - `set_apy`: Set APY for a vault.
 //!
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
 pub use pallet::*;
 
-// #[cfg(test)]
-// mod mock;
+#[cfg(test)]
+mod mock;
 
-// #[cfg(test)]
-// mod tests;
+#[cfg(test)]
+mod tests;
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
+pub mod migrations;
 pub mod weights;
 pub use weights::*;
 
@@ -62,19 +93,34 @@ pub use weights::*;
 pub mod pallet {
 	use super::*;
 	use frame_support::{
+		inherent::Vec,
 		log,
 		pallet_prelude::*,
-		traits::{Currency, ReservableCurrency},
-		Blake2_128Concat,
+		sp_runtime::{
+			helpers_128bit::multiply_by_rational_with_rounding,
+			traits::{AccountIdConversion, SaturatedConversion, Saturating, Zero},
+			FixedPointNumber, FixedU128, Perbill, Rounding,
+		},
+		traits::{Currency, ExistenceRequirement, ReservableCurrency, Time},
+		Blake2_128Concat, PalletId,
 	};
 	use frame_system::pallet_prelude::*;
 	use scale_info::TypeInfo;
 
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	type AccountOf<T> = <T as frame_system::Config>::AccountId; // optional
-	type BalanceOf<T> = <<T as Config>::MyCurrency as Currency<AccountOf<T>>>::Balance;
+	pub(crate) type BalanceOf<T> = <<T as Config>::MyCurrency as Currency<AccountOf<T>>>::Balance;
+
+	type MomentOf<T> = <<T as Config>::TimeProvider as Time>::Moment;
+
+	/// Identifier of a vault.
+	pub type VaultId = u32;
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
 	#[pallet::config]
@@ -87,16 +133,108 @@ pub mod pallet {
 		/// But, we need to use `reserved_balance` function which is not available in `Currency` trait.
 		/// That's why `ReservableCurrency` trait is used.
 		type MyCurrency: ReservableCurrency<Self::AccountId>;
+
+		/// The vault's pallet id, used for deriving the vault & reward pool accounts.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// Origin allowed to create vaults & set their APY.
+		type VaultOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Time provider (`pallet_timestamp`) for timestamping the balance snapshots.
+		type TimeProvider: Time;
+
+		/// Max. balance snapshots kept per account.
+		#[pallet::constant]
+		type MaxBalanceSnapshots: Get<u32>;
+
+		/// Blocks per year, used for converting the APY into interest per block.
+		#[pallet::constant]
+		type BlocksPerYear: Get<Self::BlockNumber>;
 	}
 
+	/// Snapshot of the balances of an account.
 	#[derive(
 		Clone, Encode, Decode, Eq, PartialEq, TypeInfo, RuntimeDebug, Default, MaxEncodedLen,
 	)]
+	pub struct DiffBalances<Balance, BlockNumber, Moment> {
+		pub free_balance: Balance,
+		pub reserved_balance: Balance,
+		pub total_balance: Balance,
+		/// Block at which the snapshot was taken.
+		pub block_number: BlockNumber,
+		/// `pallet_timestamp` moment at which the snapshot was taken.
+		pub moment: Moment,
+	}
+
+	pub type DiffBalancesOf<T> =
+		DiffBalances<BalanceOf<T>, <T as frame_system::Config>::BlockNumber, MomentOf<T>>;
+
+	/// Ring buffer of the balance snapshots of an account.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, TypeInfo, RuntimeDebug, MaxEncodedLen)]
 	#[scale_info(skip_type_params(T))]
-	pub struct DiffBalances<T: Config> {
-		free_balance: BalanceOf<T>,
-		reserved_balance: BalanceOf<T>,
-		total_balance: BalanceOf<T>,
+	pub struct BalanceHistory<T: Config> {
+		/// The snapshots, in the order of recording till full.
+		pub snapshots: BoundedVec<DiffBalancesOf<T>, T::MaxBalanceSnapshots>,
+		/// Index of the oldest snapshot (to be overwritten next) once full.
+		pub cursor: u32,
+	}
+
+	impl<T: Config> BalanceHistory<T> {
+		/// Record the snapshot, overwriting the oldest one if full.
+		pub fn push(&mut self, snapshot: DiffBalancesOf<T>) {
+			if let Err(snapshot) = self.snapshots.try_push(snapshot) {
+				let len = self.snapshots.len() as u32;
+				if let Some(oldest) = self.snapshots.get_mut(self.cursor as usize) {
+					*oldest = snapshot;
+					self.cursor = (self.cursor + 1) % len;
+				}
+			}
+		}
+
+		/// The latest snapshot.
+		pub fn latest(&self) -> Option<&DiffBalancesOf<T>> {
+			match self.cursor {
+				0 => self.snapshots.last(),
+				cursor => self.snapshots.get(cursor as usize - 1),
+			}
+		}
+
+		/// The snapshots, oldest first.
+		pub fn to_vec(&self) -> Vec<DiffBalancesOf<T>> {
+			let (newer, older) = self.snapshots.split_at(self.cursor as usize);
+			older.iter().chain(newer.iter()).cloned().collect()
+		}
+	}
+
+	/// Parameters & accounting of a vault.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, TypeInfo, RuntimeDebug, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	pub struct VaultInfo<T: Config> {
+		/// APY of the vault.
+		pub apy: Perbill,
+		/// Min. tokens (worth of shares) a user can have staked.
+		pub min_stake: BalanceOf<T>,
+		/// Max. tokens (worth of shares) a user can have staked.
+		pub max_stake: BalanceOf<T>,
+		/// Blocks after unstaking till which the unstaked shares can't be withdrawn.
+		pub lock_period: T::BlockNumber,
+		/// Total tokens (deposits & accrued interest) held by the vault.
+		pub total_assets: BalanceOf<T>,
+		/// Total shares issued by the vault.
+		pub total_shares: BalanceOf<T>,
+		/// Block at which the interest was last accrued to the vault.
+		pub last_accrual: T::BlockNumber,
+	}
+
+	/// Shares unstaked by a user.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, TypeInfo, RuntimeDebug, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	pub struct UnstakeRequest<T: Config> {
+		/// Unstaked shares (still earning interest).
+		pub shares: BalanceOf<T>,
+		/// Block from which the unstaked shares can be withdrawn.
+		pub unlock_at: T::BlockNumber,
 	}
 
 	// The pallet's runtime storage items.
@@ -107,7 +245,42 @@ pub mod pallet {
 	// https://docs.substrate.io/main-docs/build/runtime-storage/#declaring-storage-items
 	// can also use `AccountOf<T>` instead of `T::AccountId` here.
 	pub type SomeBalance<T: Config> =
-		StorageMap<_, Blake2_128Concat, T::AccountId, DiffBalances<T>>;
+		StorageMap<_, Blake2_128Concat, T::AccountId, BalanceHistory<T>>;
+
+	/// Id of the next vault to be created.
+	#[pallet::storage]
+	#[pallet::getter(fn next_vault_id)]
+	pub type NextVaultId<T: Config> = StorageValue<_, VaultId, ValueQuery>;
+
+	/// The vaults.
+	#[pallet::storage]
+	#[pallet::getter(fn vaults)]
+	pub type Vaults<T: Config> = StorageMap<_, Blake2_128Concat, VaultId, VaultInfo<T>>;
+
+	/// Shares of the users (including the unstaked ones) per vault.
+	#[pallet::storage]
+	#[pallet::getter(fn shares)]
+	pub type Shares<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		VaultId,
+		Blake2_128Concat,
+		T::AccountId,
+		BalanceOf<T>,
+		ValueQuery,
+	>;
+
+	/// Shares unstaked by the users per vault.
+	#[pallet::storage]
+	#[pallet::getter(fn unstaking)]
+	pub type Unstaking<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		VaultId,
+		Blake2_128Concat,
+		T::AccountId,
+		UnstakeRequest<T>,
+	>;
 
 	// Pallets use events to inform users when important changes are made.
 	// https://docs.substrate.io/main-docs/build/events-errors/
@@ -128,6 +301,45 @@ pub mod pallet {
 			new_total_balance: BalanceOf<T>,
 			current_block: T::BlockNumber,
 		},
+
+		/// Vault created.
+		VaultCreated {
+			vault_id: VaultId,
+			apy: Perbill,
+			min_stake: BalanceOf<T>,
+			max_stake: BalanceOf<T>,
+			lock_period: T::BlockNumber,
+		},
+
+		/// Tokens deposited to a vault & shares minted.
+		Deposited {
+			vault_id: VaultId,
+			who: T::AccountId,
+			amount: BalanceOf<T>,
+			shares: BalanceOf<T>,
+		},
+
+		/// Shares unstaked from a vault.
+		Unstaked {
+			vault_id: VaultId,
+			who: T::AccountId,
+			shares: BalanceOf<T>,
+			unlock_at: T::BlockNumber,
+		},
+
+		/// Unstaked shares burnt & tokens withdrawn from a vault.
+		Withdrawn {
+			vault_id: VaultId,
+			who: T::AccountId,
+			shares: BalanceOf<T>,
+			amount: BalanceOf<T>,
+		},
+
+		/// APY of a vault set.
+		ApySet { vault_id: VaultId, apy: Perbill },
+
+		/// Reward pool of a vault funded.
+		RewardPoolFunded { vault_id: VaultId, who: T::AccountId, amount: BalanceOf<T> },
 	}
 
 	// Errors inform users that something went wrong.
@@ -137,17 +349,35 @@ pub mod pallet {
 		BalancesNotSet,
 		/// Insufficient reserves.
 		InsufficientReserves,
-		/// Old Total balance is greater.
-		OldTotalBalanceIsGreater,
+		/// Zero amount.
+		ZeroAmount,
+		/// Deposit too small to mint any share.
+		ZeroShares,
+		/// Insufficient (staked) shares.
+		InsufficientShares,
+		/// Nothing to withdraw.
+		NothingToWithdraw,
+		/// Lock period not over.
+		LockPeriodNotOver,
+		/// Vault not found.
+		VaultNotFound,
+		/// Min. stake is greater than the max. stake.
+		InvalidStakeLimits,
+		/// Stake below the min. stake of the vault.
+		BelowMinStake,
+		/// Stake above the max. stake of the vault.
+		AboveMaxStake,
+		/// No more vaults can be created.
+		TooManyVaults,
 	}
 
 	// All these functions mentioned here are callable by external user.
 	// And each function cost some weight.
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		/// Set total balance
+		/// Set total balance i.e. record the first snapshot of the balances.
 		#[pallet::call_index(0)]
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
+		#[pallet::weight(T::WeightInfo::set_balance())]
 		pub fn set_balance(origin: OriginFor<T>) -> DispatchResult {
 			// Check that the extrinsic was signed and get the signer.
 			// This function will return an error if the extrinsic is not signed.
@@ -155,17 +385,17 @@ pub mod pallet {
 			let who = ensure_signed(origin)?;
 
 			// get the diff balances of the caller. [Total = free + reserved]
-			let free_balance = T::MyCurrency::free_balance(&who);
-			let reserved_balance = T::MyCurrency::reserved_balance(&who);
-			let total_balance = T::MyCurrency::total_balance(&who);
-
-			let diff_balances = DiffBalances { free_balance, reserved_balance, total_balance };
+			let diff_balances = Self::snapshot(&who);
+			let total_balance = diff_balances.total_balance;
 
 			// ensure the balance is not set
-			ensure!(<SomeBalance<T>>::get(&who) == None, Error::<T>::BalancesNotSet);
+			ensure!(!<SomeBalance<T>>::contains_key(&who), Error::<T>::BalancesNotSet);
+
+			let mut history = BalanceHistory { snapshots: Default::default(), cursor: 0 };
+			history.push(diff_balances);
 
 			// Update storage.
-			<SomeBalance<T>>::insert(&who, diff_balances);
+			<SomeBalance<T>>::insert(&who, history);
 
 			// Emit an event.
 			Self::deposit_event(Event::BalanceSet {
@@ -178,9 +408,11 @@ pub mod pallet {
 			Ok(())
 		}
 
-		/// Update balance if it is greater than the old balance.
+		/// Update balance i.e. record a snapshot of the balances (increased or decreased).
+		///
+		/// The oldest snapshot is overwritten once `MaxBalanceSnapshots` are recorded.
 		#[pallet::call_index(1)]
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1).ref_time())]
+		#[pallet::weight(T::WeightInfo::update_balance())]
 		pub fn update_balance(origin: OriginFor<T>) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
@@ -197,30 +429,22 @@ pub mod pallet {
 			match <SomeBalance<T>>::get(&who) {
 				// Return an error if the value has not been set.
 				None => return Err(Error::<T>::BalancesNotSet.into()),
-				Some(old_diff_balances) => {
-					ensure!(
-						current_tot_balance > old_diff_balances.total_balance,
-						Error::<T>::OldTotalBalanceIsGreater
-					);
+				Some(mut history) => {
+					let old_total_balance =
+						history.latest().map_or(Zero::zero(), |old| old.total_balance);
 
 					// get the diff balances of the caller. [Total = free + reserved]
-					let new_free_balance = T::MyCurrency::free_balance(&who);
-					let new_reserved_balance = T::MyCurrency::reserved_balance(&who);
-					let new_total_balance = T::MyCurrency::total_balance(&who);
-
-					let new_diff_balances = DiffBalances {
-						free_balance: new_free_balance,
-						reserved_balance: new_reserved_balance,
-						total_balance: new_total_balance,
-					};
+					let new_diff_balances = Self::snapshot(&who);
+					let new_total_balance = new_diff_balances.total_balance;
+					history.push(new_diff_balances);
 
 					// update the storage
-					<SomeBalance<T>>::insert(&who, new_diff_balances);
+					<SomeBalance<T>>::insert(&who, history);
 
 					// Emit an event.
 					Self::deposit_event(Event::BalanceUpdated {
 						who,
-						old_total_balance: old_diff_balances.total_balance,
+						old_total_balance,
 						new_total_balance,
 						current_block: <frame_system::Pallet<T>>::block_number(),
 					});
@@ -229,5 +453,362 @@ pub mod pallet {
 				},
 			}
 		}
+
+		/// Deposit (stake) tokens to a vault.
+		///
+		/// The tokens are transferred to the vault account & the caller is minted shares at the
+		/// current share price. The caller's stake (worth of shares) has to stay within the min. &
+		/// max. stake of the vault.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::deposit())]
+		pub fn deposit(
+			origin: OriginFor<T>,
+			vault_id: VaultId,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+
+			let mut info = Vaults::<T>::get(vault_id).ok_or(Error::<T>::VaultNotFound)?;
+			Self::accrue_interest(vault_id, &mut info);
+
+			// check the stake limits
+			let staked = Self::convert(
+				Self::staked_shares(vault_id, &who),
+				info.total_assets,
+				info.total_shares,
+			)
+			.saturating_add(amount);
+			ensure!(staked >= info.min_stake, Error::<T>::BelowMinStake);
+			ensure!(staked <= info.max_stake, Error::<T>::AboveMaxStake);
+
+			let shares = Self::convert(amount, info.total_shares, info.total_assets);
+			ensure!(!shares.is_zero(), Error::<T>::ZeroShares);
+
+			T::MyCurrency::transfer(
+				&who,
+				&Self::account_id(vault_id),
+				amount,
+				ExistenceRequirement::KeepAlive,
+			)?;
+
+			// mint the shares
+			Shares::<T>::mutate(vault_id, &who, |s| *s = s.saturating_add(shares));
+			info.total_shares = info.total_shares.saturating_add(shares);
+			info.total_assets = info.total_assets.saturating_add(amount);
+			Vaults::<T>::insert(vault_id, info);
+
+			// Emit an event.
+			Self::deposit_event(Event::Deposited { vault_id, who, amount, shares });
+
+			Ok(())
+		}
+
+		/// Unstake shares from a vault.
+		///
+		/// The unstaked shares can be withdrawn after the lock period of the vault. Unstaking again
+		/// (before withdrawing) restarts the lock period for all the unstaked shares. The remaining
+		/// stake (if any), valued after accruing the pending interest, has to stay above the min.
+		/// stake of the vault.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::unstake())]
+		pub fn unstake(
+			origin: OriginFor<T>,
+			vault_id: VaultId,
+			shares: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(!shares.is_zero(), Error::<T>::ZeroAmount);
+
+			let mut info = Vaults::<T>::get(vault_id).ok_or(Error::<T>::VaultNotFound)?;
+			Self::accrue_interest(vault_id, &mut info);
+
+			let staked_shares = Self::staked_shares(vault_id, &who);
+			ensure!(staked_shares >= shares, Error::<T>::InsufficientShares);
+
+			// check the remaining stake
+			let remaining =
+				Self::convert(staked_shares - shares, info.total_assets, info.total_shares);
+			ensure!(remaining.is_zero() || remaining >= info.min_stake, Error::<T>::BelowMinStake);
+
+			let unstaked = Unstaking::<T>::get(vault_id, &who).map_or(Zero::zero(), |r| r.shares);
+			let unlock_at =
+				<frame_system::Pallet<T>>::block_number().saturating_add(info.lock_period);
+			Unstaking::<T>::insert(
+				vault_id,
+				&who,
+				UnstakeRequest { shares: unstaked.saturating_add(shares), unlock_at },
+			);
+			Vaults::<T>::insert(vault_id, info);
+
+			// Emit an event.
+			Self::deposit_event(Event::Unstaked { vault_id, who, shares, unlock_at });
+
+			Ok(())
+		}
+
+		/// Withdraw the unstaked shares from a vault (after its lock period).
+		///
+		/// The shares are burnt & the caller is paid their value (as per the current share
+		/// price) from the vault account.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::withdraw())]
+		pub fn withdraw(origin: OriginFor<T>, vault_id: VaultId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut info = Vaults::<T>::get(vault_id).ok_or(Error::<T>::VaultNotFound)?;
+			let request =
+				Unstaking::<T>::get(vault_id, &who).ok_or(Error::<T>::NothingToWithdraw)?;
+			ensure!(
+				<frame_system::Pallet<T>>::block_number() >= request.unlock_at,
+				Error::<T>::LockPeriodNotOver
+			);
+
+			Self::accrue_interest(vault_id, &mut info);
+			let shares = request.shares;
+			let amount = Self::convert(shares, info.total_assets, info.total_shares);
+
+			T::MyCurrency::transfer(
+				&Self::account_id(vault_id),
+				&who,
+				amount,
+				ExistenceRequirement::AllowDeath,
+			)?;
+
+			// burn the shares
+			Unstaking::<T>::remove(vault_id, &who);
+			Shares::<T>::mutate_exists(vault_id, &who, |s| {
+				let remaining = s.unwrap_or_default().saturating_sub(shares);
+				*s = if remaining.is_zero() { None } else { Some(remaining) };
+			});
+			info.total_shares = info.total_shares.saturating_sub(shares);
+			info.total_assets = info.total_assets.saturating_sub(amount);
+			Vaults::<T>::insert(vault_id, info);
+
+			// Emit an event.
+			Self::deposit_event(Event::Withdrawn { vault_id, who, shares, amount });
+
+			Ok(())
+		}
+
+		/// Set APY for a vault.
+		///
+		/// The interest accrued till now is as per the old APY.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::set_apy())]
+		pub fn set_apy(origin: OriginFor<T>, vault_id: VaultId, apy: Perbill) -> DispatchResult {
+			T::VaultOrigin::ensure_origin(origin)?;
+
+			let mut info = Vaults::<T>::get(vault_id).ok_or(Error::<T>::VaultNotFound)?;
+			Self::accrue_interest(vault_id, &mut info);
+			info.apy = apy;
+			Vaults::<T>::insert(vault_id, info);
+
+			// Emit an event.
+			Self::deposit_event(Event::ApySet { vault_id, apy });
+
+			Ok(())
+		}
+
+		/// Transfer tokens to the reward pool of a vault.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::fund_reward_pool())]
+		pub fn fund_reward_pool(
+			origin: OriginFor<T>,
+			vault_id: VaultId,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+			ensure!(Vaults::<T>::contains_key(vault_id), Error::<T>::VaultNotFound);
+
+			T::MyCurrency::transfer(
+				&who,
+				&Self::reward_pool(vault_id),
+				amount,
+				ExistenceRequirement::KeepAlive,
+			)?;
+
+			// Emit an event.
+			Self::deposit_event(Event::RewardPoolFunded { vault_id, who, amount });
+
+			Ok(())
+		}
+
+		/// Create a vault with its APY, min/max stake (per user) & lock period.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::create_vault())]
+		pub fn create_vault(
+			origin: OriginFor<T>,
+			apy: Perbill,
+			min_stake: BalanceOf<T>,
+			max_stake: BalanceOf<T>,
+			lock_period: T::BlockNumber,
+		) -> DispatchResult {
+			T::VaultOrigin::ensure_origin(origin)?;
+
+			ensure!(min_stake <= max_stake, Error::<T>::InvalidStakeLimits);
+
+			let vault_id = NextVaultId::<T>::get();
+			NextVaultId::<T>::put(vault_id.checked_add(1).ok_or(Error::<T>::TooManyVaults)?);
+
+			Vaults::<T>::insert(
+				vault_id,
+				VaultInfo {
+					apy,
+					min_stake,
+					max_stake,
+					lock_period,
+					total_assets: Zero::zero(),
+					total_shares: Zero::zero(),
+					last_accrual: <frame_system::Pallet<T>>::block_number(),
+				},
+			);
+
+			// Emit an event.
+			Self::deposit_event(Event::VaultCreated {
+				vault_id,
+				apy,
+				min_stake,
+				max_stake,
+				lock_period,
+			});
+
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Snapshot of the current balances of the account.
+		fn snapshot(who: &T::AccountId) -> DiffBalancesOf<T> {
+			DiffBalances {
+				free_balance: T::MyCurrency::free_balance(who),
+				reserved_balance: T::MyCurrency::reserved_balance(who),
+				total_balance: T::MyCurrency::total_balance(who),
+				block_number: <frame_system::Pallet<T>>::block_number(),
+				moment: T::TimeProvider::now(),
+			}
+		}
+
+		/// Balance snapshots of the account, oldest first.
+		pub fn balance_history(who: &T::AccountId) -> Vec<DiffBalancesOf<T>> {
+			SomeBalance::<T>::get(who).map_or_else(Vec::new, |history| history.to_vec())
+		}
+
+		/// Account holding the total assets of the vault.
+		pub fn account_id(vault_id: VaultId) -> T::AccountId {
+			T::PalletId::get().into_sub_account_truncating((b"assets", vault_id))
+		}
+
+		/// Account of the reward pool of the vault.
+		pub fn reward_pool(vault_id: VaultId) -> T::AccountId {
+			T::PalletId::get().into_sub_account_truncating((b"reward", vault_id))
+		}
+
+		/// Shares of the user in the vault, excluding the unstaked ones.
+		fn staked_shares(vault_id: VaultId, who: &T::AccountId) -> BalanceOf<T> {
+			let unstaked = Unstaking::<T>::get(vault_id, who).map_or(Zero::zero(), |r| r.shares);
+			Shares::<T>::get(vault_id, who).saturating_sub(unstaked)
+		}
+
+		/// Interest accrued to the vault since its last accrual (capped by its reward pool).
+		fn pending_interest(vault_id: VaultId, info: &VaultInfo<T>) -> BalanceOf<T> {
+			let now = <frame_system::Pallet<T>>::block_number();
+			let elapsed = now.saturating_sub(info.last_accrual).saturated_into::<u128>();
+			let blocks_per_year = T::BlocksPerYear::get().saturated_into::<u128>();
+			let apy =
+				FixedU128::saturating_from_rational(info.apy.deconstruct(), Perbill::ACCURACY);
+
+			let interest: BalanceOf<T> = apy
+				.saturating_mul(FixedU128::saturating_from_rational(
+					elapsed,
+					blocks_per_year.max(1),
+				))
+				.saturating_mul_int(info.total_assets.saturated_into::<u128>())
+				.saturated_into();
+			let available = T::MyCurrency::free_balance(&Self::reward_pool(vault_id))
+				.saturating_sub(T::MyCurrency::minimum_balance());
+
+			interest.min(available)
+		}
+
+		/// Transfer the interest accrued since the last accrual from the reward pool to the vault.
+		///
+		/// NOTE: `info` has to be written back to `Vaults` by the caller.
+		fn accrue_interest(vault_id: VaultId, info: &mut VaultInfo<T>) {
+			let interest = Self::pending_interest(vault_id, info);
+			if !interest.is_zero()
+				&& T::MyCurrency::transfer(
+					&Self::reward_pool(vault_id),
+					&Self::account_id(vault_id),
+					interest,
+					ExistenceRequirement::KeepAlive,
+				)
+				.is_ok()
+			{
+				info.total_assets = info.total_assets.saturating_add(interest);
+			}
+			info.last_accrual = <frame_system::Pallet<T>>::block_number();
+		}
+
+		/// `amount * (numerator + 1) / (denominator + 1)`, rounded down.
+		///
+		/// The offset of 1 (virtual share & asset) keeps the share price well defined for an
+		/// empty vault & makes inflating the share price via donations unprofitable.
+		fn convert(
+			amount: BalanceOf<T>,
+			numerator: BalanceOf<T>,
+			denominator: BalanceOf<T>,
+		) -> BalanceOf<T> {
+			multiply_by_rational_with_rounding(
+				amount.saturated_into::<u128>(),
+				numerator.saturated_into::<u128>().saturating_add(1),
+				denominator.saturated_into::<u128>().saturating_add(1),
+				Rounding::Down,
+			)
+			.unwrap_or(u128::MAX)
+			.saturated_into()
+		}
+
+		/// Total tokens (deposits & accrued interest) held by the vault (zero if not found).
+		pub fn total_assets(vault_id: VaultId) -> BalanceOf<T> {
+			Vaults::<T>::get(vault_id).map_or(Zero::zero(), |info| info.total_assets)
+		}
+
+		/// Shares worth the `assets` as per the share price of the vault at its last accrual.
+		pub fn convert_to_shares(vault_id: VaultId, assets: BalanceOf<T>) -> BalanceOf<T> {
+			Vaults::<T>::get(vault_id).map_or(Zero::zero(), |info| {
+				Self::convert(assets, info.total_shares, info.total_assets)
+			})
+		}
+
+		/// Assets worth the `shares` as per the share price of the vault at its last accrual.
+		pub fn convert_to_assets(vault_id: VaultId, shares: BalanceOf<T>) -> BalanceOf<T> {
+			Vaults::<T>::get(vault_id).map_or(Zero::zero(), |info| {
+				Self::convert(shares, info.total_assets, info.total_shares)
+			})
+		}
+
+		/// Shares minted by depositing the `assets` to the vault in the current block.
+		pub fn preview_deposit(vault_id: VaultId, assets: BalanceOf<T>) -> BalanceOf<T> {
+			Vaults::<T>::get(vault_id).map_or(Zero::zero(), |info| {
+				let total_assets =
+					info.total_assets.saturating_add(Self::pending_interest(vault_id, &info));
+				Self::convert(assets, info.total_shares, total_assets)
+			})
+		}
+
+		/// Assets paid by withdrawing (redeeming) the `shares` from the vault in the current
+		/// block.
+		pub fn preview_redeem(vault_id: VaultId, shares: BalanceOf<T>) -> BalanceOf<T> {
+			Vaults::<T>::get(vault_id).map_or(Zero::zero(), |info| {
+				let total_assets =
+					info.total_assets.saturating_add(Self::pending_interest(vault_id, &info));
+				Self::convert(shares, total_assets, info.total_shares)
+			})
+		}
 	}
 }
@@ -0,0 +1,80 @@
+//! Storage migrations of the vault pallet.
+
+use super::*;
+
+/// Storage version 1: `SomeBalance` holds a `BalanceHistory` instead of a single `DiffBalances`.
+pub mod v1 {
+	use super::*;
+	use codec::Decode;
+	use core::marker::PhantomData;
+	#[cfg(feature = "try-runtime")]
+	use frame_support::{ensure, inherent::Vec};
+	use frame_support::{
+		log,
+		sp_runtime::traits::Zero,
+		traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+		weights::Weight,
+	};
+
+	/// The balances of an account as stored in `SomeBalance` before v1.
+	#[derive(Decode)]
+	struct OldDiffBalances<Balance> {
+		free_balance: Balance,
+		reserved_balance: Balance,
+		total_balance: Balance,
+	}
+
+	/// Convert the balances of each account into a history of a single snapshot.
+	///
+	/// The block & moment of the old balances aren't known, so the snapshot is stamped with zero.
+	pub struct MigrateToBalanceHistory<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToBalanceHistory<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() >= 1 {
+				log::info!(target: "pallet_vault", "Skipping the v1 migration");
+				return T::DbWeight::get().reads(1);
+			}
+
+			let mut count: u64 = 0;
+			SomeBalance::<T>::translate::<OldDiffBalances<BalanceOf<T>>, _>(|_, old| {
+				count += 1;
+				let mut history = BalanceHistory { snapshots: Default::default(), cursor: 0 };
+				history.push(DiffBalances {
+					free_balance: old.free_balance,
+					reserved_balance: old.reserved_balance,
+					total_balance: old.total_balance,
+					block_number: Zero::zero(),
+					moment: Zero::zero(),
+				});
+				Some(history)
+			});
+
+			StorageVersion::new(1).put::<Pallet<T>>();
+			log::info!(target: "pallet_vault", "Migrated {} balances to v1", count);
+
+			// the version & the entries
+			T::DbWeight::get().reads_writes(count.saturating_add(1), count.saturating_add(1))
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+			use codec::Encode;
+
+			Ok((SomeBalance::<T>::iter_keys().count() as u32).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+			let count =
+				u32::decode(&mut &state[..]).map_err(|_| "Undecodable pre-upgrade state")?;
+			ensure!(Pallet::<T>::on_chain_storage_version() >= 1, "Not migrated to v1");
+			// the histories decode & none is lost
+			ensure!(
+				SomeBalance::<T>::iter_values().count() as u32 == count,
+				"Balances lost in the v1 migration"
+			);
+			Ok(())
+		}
+	}
+}
@@ -1,2 +1,589 @@
-use crate::{mock::*, Error, Event};
-use frame_support::{assert_noop, assert_ok};
+use crate::{mock::*, DiffBalances, Error, Event, VaultId};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{Currency, ReservableCurrency},
+};
+use sp_runtime::{DispatchError::BadOrigin, Perbill};
+
+const UNIT: Balance = 1e10 as Balance;
+const MIN_STAKE: Balance = 10 * UNIT;
+const MAX_STAKE: Balance = 5_000 * UNIT;
+const LOCK_PERIOD: u64 = 14_400; // 1 day in blocks
+const ONE_YEAR: u64 = 5_256_000;
+
+// ===== helpers =====
+
+fn create_vault(apy: Perbill) -> VaultId {
+	let vault_id = Vault::next_vault_id();
+	assert_ok!(Vault::create_vault(RuntimeOrigin::root(), apy, MIN_STAKE, MAX_STAKE, LOCK_PERIOD));
+	vault_id
+}
+
+fn fund(vault_id: VaultId, amount: Balance) {
+	assert_ok!(Vault::fund_reward_pool(RuntimeOrigin::signed(TREASURY), vault_id, amount));
+}
+
+fn deposit(who: u64, vault_id: VaultId, amount: Balance) {
+	assert_ok!(Vault::deposit(RuntimeOrigin::signed(who), vault_id, amount));
+}
+
+/// `amount * (numerator + 1) / (denominator + 1)` as per the vault.
+fn convert(amount: Balance, numerator: Balance, denominator: Balance) -> Balance {
+	amount * (numerator + 1) / (denominator + 1)
+}
+
+fn snapshot(
+	free: Balance,
+	reserved: Balance,
+	block_number: u64,
+	moment: u64,
+) -> DiffBalances<Balance, u64, u64> {
+	DiffBalances {
+		free_balance: free,
+		reserved_balance: reserved,
+		total_balance: free + reserved,
+		block_number,
+		moment,
+	}
+}
+
+// ===== balance snapshots =====
+
+#[test]
+fn set_balance_works() {
+	new_test_ext().execute_with(|| {
+		Timestamp::set_timestamp(6_000);
+
+		assert_ok!(Vault::set_balance(RuntimeOrigin::signed(ALICE)));
+
+		assert_eq!(Vault::balance_history(&ALICE), vec![snapshot(10_000 * UNIT, 0, 1, 6_000)]);
+		System::assert_last_event(
+			Event::BalanceSet { who: ALICE, total_balance: 10_000 * UNIT, current_block: 1 }.into(),
+		);
+	});
+}
+
+#[test]
+fn set_balance_fails_if_already_set() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Vault::set_balance(RuntimeOrigin::signed(ALICE)));
+
+		assert_noop!(
+			Vault::set_balance(RuntimeOrigin::signed(ALICE)),
+			Error::<Test>::BalancesNotSet
+		);
+	});
+}
+
+#[test]
+fn update_balance_fails_if_not_set() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Vault::update_balance(RuntimeOrigin::signed(ALICE)),
+			Error::<Test>::BalancesNotSet
+		);
+	});
+}
+
+#[test]
+fn update_balance_tracks_reserves() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Vault::set_balance(RuntimeOrigin::signed(ALICE)));
+		assert_ok!(Balances::reserve(&ALICE, 1_000 * UNIT));
+
+		System::set_block_number(2);
+		Timestamp::set_timestamp(6_000);
+		assert_ok!(Vault::update_balance(RuntimeOrigin::signed(ALICE)));
+
+		// free moved to reserved, total unchanged
+		assert_eq!(
+			Vault::balance_history(&ALICE),
+			vec![snapshot(10_000 * UNIT, 0, 1, 0), snapshot(9_000 * UNIT, 1_000 * UNIT, 2, 6_000)]
+		);
+		System::assert_last_event(
+			Event::BalanceUpdated {
+				who: ALICE,
+				old_total_balance: 10_000 * UNIT,
+				new_total_balance: 10_000 * UNIT,
+				current_block: 2,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn update_balance_records_decrease() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Vault::set_balance(RuntimeOrigin::signed(ALICE)));
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(ALICE), BOB, 4_000 * UNIT));
+
+		assert_ok!(Vault::update_balance(RuntimeOrigin::signed(ALICE)));
+
+		assert_eq!(Vault::balance_history(&ALICE)[1].total_balance, 6_000 * UNIT);
+		System::assert_last_event(
+			Event::BalanceUpdated {
+				who: ALICE,
+				old_total_balance: 10_000 * UNIT,
+				new_total_balance: 6_000 * UNIT,
+				current_block: 1,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn update_balance_counts_reserved_balance_above_existential_deposit() {
+	new_test_ext().execute_with(|| {
+		let dave = 4;
+		Balances::make_free_balance_be(&dave, 600);
+		assert_ok!(Vault::set_balance(RuntimeOrigin::signed(dave)));
+
+		// only the existential deposit is left free
+		assert_ok!(Balances::reserve(&dave, 500));
+		assert_ok!(Vault::update_balance(RuntimeOrigin::signed(dave)));
+
+		assert_eq!(Vault::balance_history(&dave)[1], snapshot(EXISTENTIAL_DEPOSIT, 500, 1, 0));
+	});
+}
+
+#[test]
+fn update_balance_fails_with_insufficient_reserves() {
+	new_test_ext().execute_with(|| {
+		// total balance equal to the existential deposit
+		let dave = 4;
+		Balances::make_free_balance_be(&dave, EXISTENTIAL_DEPOSIT);
+		assert_ok!(Vault::set_balance(RuntimeOrigin::signed(dave)));
+
+		assert_noop!(
+			Vault::update_balance(RuntimeOrigin::signed(dave)),
+			Error::<Test>::InsufficientReserves
+		);
+
+		// no balance at all
+		let eve = 5;
+		assert_ok!(Vault::set_balance(RuntimeOrigin::signed(eve)));
+		assert_eq!(Vault::balance_history(&eve), vec![snapshot(0, 0, 1, 0)]);
+
+		assert_noop!(
+			Vault::update_balance(RuntimeOrigin::signed(eve)),
+			Error::<Test>::InsufficientReserves
+		);
+	});
+}
+
+#[test]
+fn balance_history_overwrites_oldest_snapshot() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Vault::set_balance(RuntimeOrigin::signed(ALICE)));
+		for block in 2..=4 {
+			System::set_block_number(block);
+			assert_ok!(Vault::update_balance(RuntimeOrigin::signed(ALICE)));
+		}
+
+		// `MaxBalanceSnapshots` = 3, oldest first
+		let blocks: Vec<u64> =
+			Vault::balance_history(&ALICE).iter().map(|s| s.block_number).collect();
+		assert_eq!(blocks, vec![2, 3, 4]);
+		assert_eq!(Vault::get_balance(ALICE).unwrap().cursor, 1);
+		assert_eq!(Vault::get_balance(ALICE).unwrap().latest().unwrap().block_number, 4);
+	});
+}
+
+// ===== vaults =====
+
+#[test]
+fn create_vault_works() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(create_vault(Perbill::from_percent(10)), 0);
+		System::assert_last_event(
+			Event::VaultCreated {
+				vault_id: 0,
+				apy: Perbill::from_percent(10),
+				min_stake: MIN_STAKE,
+				max_stake: MAX_STAKE,
+				lock_period: LOCK_PERIOD,
+			}
+			.into(),
+		);
+
+		assert_eq!(create_vault(Perbill::from_percent(5)), 1);
+		assert_eq!(Vault::vaults(1).unwrap().apy, Perbill::from_percent(5));
+		assert_eq!(Vault::next_vault_id(), 2);
+	});
+}
+
+#[test]
+fn create_vault_fails() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Vault::create_vault(
+				RuntimeOrigin::signed(ALICE),
+				Perbill::from_percent(10),
+				MIN_STAKE,
+				MAX_STAKE,
+				LOCK_PERIOD
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			Vault::create_vault(
+				RuntimeOrigin::root(),
+				Perbill::from_percent(10),
+				MAX_STAKE + 1,
+				MAX_STAKE,
+				LOCK_PERIOD
+			),
+			Error::<Test>::InvalidStakeLimits
+		);
+	});
+}
+
+#[test]
+fn deposit_mints_shares() {
+	new_test_ext().execute_with(|| {
+		let vault_id = create_vault(Perbill::from_percent(10));
+
+		deposit(ALICE, vault_id, 1_000 * UNIT);
+
+		// 1:1 for an empty vault
+		assert_eq!(Vault::shares(vault_id, ALICE), 1_000 * UNIT);
+		assert_eq!(Vault::total_assets(vault_id), 1_000 * UNIT);
+		assert_eq!(Balances::free_balance(Vault::account_id(vault_id)), 1_000 * UNIT);
+		assert_eq!(Balances::free_balance(ALICE), 9_000 * UNIT);
+		System::assert_last_event(
+			Event::Deposited { vault_id, who: ALICE, amount: 1_000 * UNIT, shares: 1_000 * UNIT }
+				.into(),
+		);
+	});
+}
+
+#[test]
+fn deposit_fails() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Vault::deposit(RuntimeOrigin::signed(ALICE), 0, 100 * UNIT),
+			Error::<Test>::VaultNotFound
+		);
+
+		let vault_id = create_vault(Perbill::from_percent(10));
+		assert_noop!(
+			Vault::deposit(RuntimeOrigin::signed(ALICE), vault_id, 0),
+			Error::<Test>::ZeroAmount
+		);
+		assert_noop!(
+			Vault::deposit(RuntimeOrigin::signed(ALICE), vault_id, MIN_STAKE - 1),
+			Error::<Test>::BelowMinStake
+		);
+		assert_noop!(
+			Vault::deposit(RuntimeOrigin::signed(BOB), vault_id, MAX_STAKE + 1),
+			Error::<Test>::AboveMaxStake
+		);
+
+		// the max. stake is per user, including the previous deposits
+		deposit(BOB, vault_id, 3_000 * UNIT);
+		assert_noop!(
+			Vault::deposit(RuntimeOrigin::signed(BOB), vault_id, 3_000 * UNIT),
+			Error::<Test>::AboveMaxStake
+		);
+	});
+}
+
+#[test]
+fn interest_accrues_to_share_price() {
+	new_test_ext().execute_with(|| {
+		let vault_id = create_vault(Perbill::from_percent(10));
+		fund(vault_id, 1_000 * UNIT);
+		deposit(ALICE, vault_id, 1_000 * UNIT);
+
+		System::set_block_number(1 + ONE_YEAR / 2);
+
+		// 5% for half a year, not accrued yet
+		let total_assets = 1_050 * UNIT;
+		assert_eq!(Vault::convert_to_assets(vault_id, 1_000 * UNIT), 1_000 * UNIT);
+		assert_eq!(
+			Vault::preview_redeem(vault_id, 1_000 * UNIT),
+			convert(1_000 * UNIT, total_assets, 1_000 * UNIT)
+		);
+		let bob_shares = convert(total_assets, 1_000 * UNIT, total_assets);
+		assert_eq!(Vault::preview_deposit(vault_id, total_assets), bob_shares);
+
+		// the deposit accrues the interest first, so Bob gets fewer shares for the same price
+		deposit(BOB, vault_id, total_assets);
+		assert_eq!(Vault::shares(vault_id, BOB), bob_shares);
+		assert_eq!(Vault::total_assets(vault_id), 2 * total_assets);
+		assert_eq!(Balances::free_balance(Vault::reward_pool(vault_id)), 950 * UNIT);
+		assert_eq!(
+			Vault::convert_to_assets(vault_id, 1_000 * UNIT),
+			convert(1_000 * UNIT, 2 * total_assets, 1_000 * UNIT + bob_shares)
+		);
+	});
+}
+
+#[test]
+fn interest_is_capped_by_reward_pool() {
+	new_test_ext().execute_with(|| {
+		let vault_id = create_vault(Perbill::from_percent(10));
+		fund(vault_id, 10 * UNIT);
+		deposit(ALICE, vault_id, 1_000 * UNIT);
+
+		System::set_block_number(1 + ONE_YEAR / 2);
+		deposit(BOB, vault_id, 100 * UNIT);
+
+		// the reward pool is kept alive
+		assert_eq!(Vault::total_assets(vault_id), 1_110 * UNIT - EXISTENTIAL_DEPOSIT);
+		assert_eq!(Balances::free_balance(Vault::reward_pool(vault_id)), EXISTENTIAL_DEPOSIT);
+	});
+}
+
+#[test]
+fn donations_do_not_change_share_price() {
+	new_test_ext().execute_with(|| {
+		let vault_id = create_vault(Perbill::zero());
+		deposit(ALICE, vault_id, 100 * UNIT);
+
+		assert_ok!(Balances::transfer(
+			RuntimeOrigin::signed(BOB),
+			Vault::account_id(vault_id),
+			1_000 * UNIT
+		));
+
+		assert_eq!(Vault::convert_to_assets(vault_id, 100 * UNIT), 100 * UNIT);
+		deposit(CHARLIE, vault_id, 100 * UNIT);
+		assert_eq!(Vault::shares(vault_id, CHARLIE), 100 * UNIT);
+	});
+}
+
+#[test]
+fn unstake_and_withdraw_works() {
+	new_test_ext().execute_with(|| {
+		let vault_id = create_vault(Perbill::zero());
+		deposit(ALICE, vault_id, 100 * UNIT);
+
+		assert_ok!(Vault::unstake(RuntimeOrigin::signed(ALICE), vault_id, 40 * UNIT));
+		System::assert_last_event(
+			Event::Unstaked { vault_id, who: ALICE, shares: 40 * UNIT, unlock_at: 1 + LOCK_PERIOD }
+				.into(),
+		);
+
+		assert_noop!(
+			Vault::withdraw(RuntimeOrigin::signed(ALICE), vault_id),
+			Error::<Test>::LockPeriodNotOver
+		);
+
+		System::set_block_number(1 + LOCK_PERIOD);
+		assert_ok!(Vault::withdraw(RuntimeOrigin::signed(ALICE), vault_id));
+		System::assert_last_event(
+			Event::Withdrawn { vault_id, who: ALICE, shares: 40 * UNIT, amount: 40 * UNIT }.into(),
+		);
+
+		assert_eq!(Vault::shares(vault_id, ALICE), 60 * UNIT);
+		assert_eq!(Vault::unstaking(vault_id, ALICE), None);
+		assert_eq!(Vault::total_assets(vault_id), 60 * UNIT);
+		assert_eq!(Balances::free_balance(ALICE), 9_940 * UNIT);
+
+		assert_noop!(
+			Vault::withdraw(RuntimeOrigin::signed(ALICE), vault_id),
+			Error::<Test>::NothingToWithdraw
+		);
+	});
+}
+
+#[test]
+fn unstake_fails() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Vault::unstake(RuntimeOrigin::signed(ALICE), 0, 10 * UNIT),
+			Error::<Test>::VaultNotFound
+		);
+
+		let vault_id = create_vault(Perbill::zero());
+		deposit(ALICE, vault_id, 100 * UNIT);
+
+		assert_noop!(
+			Vault::unstake(RuntimeOrigin::signed(ALICE), vault_id, 0),
+			Error::<Test>::ZeroAmount
+		);
+		assert_noop!(
+			Vault::unstake(RuntimeOrigin::signed(ALICE), vault_id, 101 * UNIT),
+			Error::<Test>::InsufficientShares
+		);
+		// remaining stake below the min. stake
+		assert_noop!(
+			Vault::unstake(RuntimeOrigin::signed(ALICE), vault_id, 95 * UNIT),
+			Error::<Test>::BelowMinStake
+		);
+
+		// the unstaked shares can't be unstaked again
+		assert_ok!(Vault::unstake(RuntimeOrigin::signed(ALICE), vault_id, 60 * UNIT));
+		assert_noop!(
+			Vault::unstake(RuntimeOrigin::signed(ALICE), vault_id, 50 * UNIT),
+			Error::<Test>::InsufficientShares
+		);
+
+		// unstaking everything is fine
+		assert_ok!(Vault::unstake(RuntimeOrigin::signed(ALICE), vault_id, 40 * UNIT));
+		assert_eq!(Vault::unstaking(vault_id, ALICE).unwrap().shares, 100 * UNIT);
+	});
+}
+
+#[test]
+fn unstake_checks_min_stake_after_accruing_interest() {
+	new_test_ext().execute_with(|| {
+		let vault_id = create_vault(Perbill::from_percent(20));
+		fund(vault_id, 1_000 * UNIT);
+		deposit(ALICE, vault_id, 100 * UNIT);
+
+		System::set_block_number(1 + ONE_YEAR);
+
+		// the remaining 9 shares are worth 9 units at the stale share price, ~10.8 after accruing
+		assert_ok!(Vault::unstake(RuntimeOrigin::signed(ALICE), vault_id, 91 * UNIT));
+		assert_eq!(Balances::free_balance(Vault::reward_pool(vault_id)), 980 * UNIT);
+	});
+}
+
+#[test]
+fn set_apy_accrues_at_old_apy() {
+	new_test_ext().execute_with(|| {
+		let vault_id = create_vault(Perbill::from_percent(10));
+		fund(vault_id, 1_000 * UNIT);
+		deposit(ALICE, vault_id, 1_000 * UNIT);
+
+		System::set_block_number(1 + ONE_YEAR / 2);
+		assert_ok!(Vault::set_apy(RuntimeOrigin::root(), vault_id, Perbill::from_percent(20)));
+		System::assert_last_event(
+			Event::ApySet { vault_id, apy: Perbill::from_percent(20) }.into(),
+		);
+		assert_eq!(Vault::total_assets(vault_id), 1_050 * UNIT);
+
+		// 10% on 1_050 for the next half a year
+		System::set_block_number(1 + ONE_YEAR);
+		assert_eq!(
+			Vault::preview_redeem(vault_id, 1_000 * UNIT),
+			convert(1_000 * UNIT, 1_155 * UNIT, 1_000 * UNIT)
+		);
+	});
+}
+
+#[test]
+fn set_apy_fails() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Vault::set_apy(RuntimeOrigin::root(), 0, Perbill::from_percent(20)),
+			Error::<Test>::VaultNotFound
+		);
+
+		let vault_id = create_vault(Perbill::from_percent(10));
+		assert_noop!(
+			Vault::set_apy(RuntimeOrigin::signed(ALICE), vault_id, Perbill::from_percent(20)),
+			BadOrigin
+		);
+	});
+}
+
+#[test]
+fn fund_reward_pool_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Vault::fund_reward_pool(RuntimeOrigin::signed(TREASURY), 0, 100 * UNIT),
+			Error::<Test>::VaultNotFound
+		);
+
+		let vault_id = create_vault(Perbill::from_percent(10));
+		assert_noop!(
+			Vault::fund_reward_pool(RuntimeOrigin::signed(TREASURY), vault_id, 0),
+			Error::<Test>::ZeroAmount
+		);
+
+		fund(vault_id, 100 * UNIT);
+		assert_eq!(Balances::free_balance(Vault::reward_pool(vault_id)), 100 * UNIT);
+		System::assert_last_event(
+			Event::RewardPoolFunded { vault_id, who: TREASURY, amount: 100 * UNIT }.into(),
+		);
+	});
+}
+
+#[test]
+fn positions_in_multiple_vaults_are_independent() {
+	new_test_ext().execute_with(|| {
+		let first = create_vault(Perbill::from_percent(10));
+		let second = create_vault(Perbill::from_percent(20));
+		fund(first, 1_000 * UNIT);
+		fund(second, 1_000 * UNIT);
+
+		deposit(ALICE, first, 1_000 * UNIT);
+		deposit(ALICE, second, 1_000 * UNIT);
+		assert_ne!(Vault::account_id(first), Vault::account_id(second));
+		assert_ne!(Vault::reward_pool(first), Vault::reward_pool(second));
+
+		System::set_block_number(1 + ONE_YEAR / 2);
+		assert_eq!(
+			Vault::preview_redeem(first, 1_000 * UNIT),
+			convert(1_000 * UNIT, 1_050 * UNIT, 1_000 * UNIT)
+		);
+		assert_eq!(
+			Vault::preview_redeem(second, 1_000 * UNIT),
+			convert(1_000 * UNIT, 1_100 * UNIT, 1_000 * UNIT)
+		);
+
+		// unstaking from one vault doesn't affect the other
+		assert_ok!(Vault::unstake(RuntimeOrigin::signed(ALICE), first, 1_000 * UNIT));
+		assert_eq!(Vault::unstaking(second, ALICE), None);
+		assert_ok!(Vault::unstake(RuntimeOrigin::signed(ALICE), second, 500 * UNIT));
+	});
+}
+
+// ===== migrations =====
+
+#[test]
+fn migration_to_v1_converts_balances_to_history() {
+	use crate::migrations::v1::MigrateToBalanceHistory;
+	use frame_support::{
+		storage::unhashed,
+		traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	};
+
+	new_test_ext().execute_with(|| {
+		// the v0 balances i.e. (free, reserved, total)
+		let key = crate::SomeBalance::<Test>::hashed_key_for(ALICE);
+		unhashed::put(&key, &(9_000 * UNIT, 1_000 * UNIT, 10_000 * UNIT));
+		StorageVersion::new(0).put::<Vault>();
+
+		MigrateToBalanceHistory::<Test>::on_runtime_upgrade();
+		assert_eq!(Vault::on_chain_storage_version(), 1);
+		assert_eq!(
+			Vault::balance_history(&ALICE),
+			vec![snapshot(9_000 * UNIT, 1_000 * UNIT, 0, 0)]
+		);
+		assert_eq!(Vault::balance_history(&BOB), vec![]);
+
+		// runs once
+		MigrateToBalanceHistory::<Test>::on_runtime_upgrade();
+		assert_eq!(Vault::get_balance(&ALICE).unwrap().snapshots.len(), 1);
+	});
+}
+
+#[cfg(feature = "try-runtime")]
+#[test]
+fn migration_to_v1_passes_try_runtime_checks() {
+	use crate::migrations::v1::MigrateToBalanceHistory;
+	use frame_support::{
+		storage::unhashed,
+		traits::{OnRuntimeUpgrade, StorageVersion},
+	};
+
+	new_test_ext().execute_with(|| {
+		// the v0 balances i.e. (free, reserved, total)
+		for (who, free) in [(ALICE, 9_000 * UNIT), (BOB, 5_000 * UNIT)] {
+			let key = crate::SomeBalance::<Test>::hashed_key_for(who);
+			unhashed::put(&key, &(free, 1_000 * UNIT, free + 1_000 * UNIT));
+		}
+		StorageVersion::new(0).put::<Vault>();
+
+		let state = MigrateToBalanceHistory::<Test>::pre_upgrade().unwrap();
+		MigrateToBalanceHistory::<Test>::on_runtime_upgrade();
+		assert_ok!(MigrateToBalanceHistory::<Test>::post_upgrade(state));
+		assert_eq!(Vault::balance_history(&BOB), vec![snapshot(5_000 * UNIT, 1_000 * UNIT, 0, 0)]);
+	});
+}
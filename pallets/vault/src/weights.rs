@@ -1,30 +1,8 @@
-
-//! Autogenerated weights for pallet_vault
+//! Weights for pallet_vault
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
-//! DATE: 2023-04-06, STEPS: `50`, REPEAT: `20`, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! WORST CASE MAP SIZE: `1000000`
-//! HOSTNAME: `Alexs-MacBook-Pro-2.local`, CPU: `<UNKNOWN>`
-//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
-
-// Executed Command:
-// ../../target/release/node-template
-// benchmark
-// pallet
-// --chain
-// dev
-// --pallet
-// pallet_vault
-// --extrinsic
-// *
-// --steps=50
-// --repeat=20
-// --execution=wasm
-// --wasm-execution=compiled
-// --output
-// pallets/vault/src/weights.rs
-// --vault
-// ../../.maintain/frame-weight-vault.hbs
+//! NOTE: Not generated by the benchmark CLI yet. The storage accesses follow the worst cases set
+//! up in `src/benchmarking.rs`, but the execution times are placeholders, not measurements.
+//! Regenerate via `./scripts/benchmark.sh vault` before relying on them.
 
 #![cfg_attr(rustfmt, rustfmt_skip)]
 #![allow(unused_parens)]
@@ -37,55 +15,195 @@ use core::marker::PhantomData;
 pub trait WeightInfo {
 	fn set_balance() -> Weight;
 	fn update_balance() -> Weight;
+	fn deposit() -> Weight;
+	fn unstake() -> Weight;
+	fn withdraw() -> Weight;
+	fn set_apy() -> Weight;
+	fn fund_reward_pool() -> Weight;
+	fn create_vault() -> Weight;
 }
 
 /// Weights for pallet_vault using the Substrate node and recommended hardware.
 pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
-	/// Storage: Vault Something (r:0 w:1)
-	/// Proof: Vault Something (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Vault SomeBalance (r:1 w:1)
+	/// Proof: Vault SomeBalance (max_values: None, max_size: Some(6054), added: 8529, mode: MaxEncodedLen)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:0)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
 	fn set_balance() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `0`
-		//  Estimated: `0`
-		// Minimum execution time: 8_000_000 picoseconds.
-		Weight::from_parts(9_000_000, 0)
+		Weight::from_parts(15_000_000, 11635)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
-	/// Storage: Vault Something (r:1 w:1)
-	/// Proof: Vault Something (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Vault SomeBalance (r:1 w:1)
+	/// Proof: Vault SomeBalance (max_values: None, max_size: Some(6054), added: 8529, mode: MaxEncodedLen)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:0)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
 	fn update_balance() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `32`
-		//  Estimated: `1489`
-		// Minimum execution time: 6_000_000 picoseconds.
-		Weight::from_parts(6_000_000, 1489)
-			.saturating_add(T::DbWeight::get().reads(1_u64))
+		Weight::from_parts(27_000_000, 11635)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: Vault Vaults (r:1 w:1)
+	/// Proof: Vault Vaults (max_values: None, max_size: Some(96), added: 2571, mode: MaxEncodedLen)
+	/// Storage: Vault Unstaking (r:1 w:0)
+	/// Proof: Vault Unstaking (max_values: None, max_size: Some(88), added: 2563, mode: MaxEncodedLen)
+	/// Storage: Vault Shares (r:1 w:1)
+	/// Proof: Vault Shares (max_values: None, max_size: Some(84), added: 2559, mode: MaxEncodedLen)
+	/// Storage: System Account (r:3 w:3)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn deposit() -> Weight {
+		Weight::from_parts(56_000_000, 8799)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: Vault Vaults (r:1 w:1)
+	/// Proof: Vault Vaults (max_values: None, max_size: Some(96), added: 2571, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Vault Shares (r:1 w:0)
+	/// Proof: Vault Shares (max_values: None, max_size: Some(84), added: 2559, mode: MaxEncodedLen)
+	/// Storage: Vault Unstaking (r:1 w:1)
+	/// Proof: Vault Unstaking (max_values: None, max_size: Some(88), added: 2563, mode: MaxEncodedLen)
+	fn unstake() -> Weight {
+		Weight::from_parts(42_000_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: Vault Vaults (r:1 w:1)
+	/// Proof: Vault Vaults (max_values: None, max_size: Some(96), added: 2571, mode: MaxEncodedLen)
+	/// Storage: Vault Unstaking (r:1 w:1)
+	/// Proof: Vault Unstaking (max_values: None, max_size: Some(88), added: 2563, mode: MaxEncodedLen)
+	/// Storage: System Account (r:3 w:3)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Vault Shares (r:1 w:1)
+	/// Proof: Vault Shares (max_values: None, max_size: Some(84), added: 2559, mode: MaxEncodedLen)
+	fn withdraw() -> Weight {
+		Weight::from_parts(60_000_000, 8799)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	/// Storage: Vault Vaults (r:1 w:1)
+	/// Proof: Vault Vaults (max_values: None, max_size: Some(96), added: 2571, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn set_apy() -> Weight {
+		Weight::from_parts(38_000_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: Vault Vaults (r:1 w:0)
+	/// Proof: Vault Vaults (max_values: None, max_size: Some(96), added: 2571, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn fund_reward_pool() -> Weight {
+		Weight::from_parts(34_000_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: Vault NextVaultId (r:1 w:1)
+	/// Proof: Vault NextVaultId (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Vault Vaults (r:0 w:1)
+	/// Proof: Vault Vaults (max_values: None, max_size: Some(96), added: 2571, mode: MaxEncodedLen)
+	fn create_vault() -> Weight {
+		Weight::from_parts(11_000_000, 1489)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
-	/// Storage: Vault Something (r:0 w:1)
-	/// Proof: Vault Something (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Vault SomeBalance (r:1 w:1)
+	/// Proof: Vault SomeBalance (max_values: None, max_size: Some(6054), added: 8529, mode: MaxEncodedLen)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:0)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
 	fn set_balance() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `0`
-		//  Estimated: `0`
-		// Minimum execution time: 8_000_000 picoseconds.
-		Weight::from_parts(9_000_000, 0)
+		Weight::from_parts(15_000_000, 11635)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
-	/// Storage: Vault Something (r:1 w:1)
-	/// Proof: Vault Something (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Vault SomeBalance (r:1 w:1)
+	/// Proof: Vault SomeBalance (max_values: None, max_size: Some(6054), added: 8529, mode: MaxEncodedLen)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:0)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
 	fn update_balance() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `32`
-		//  Estimated: `1489`
-		// Minimum execution time: 6_000_000 picoseconds.
-		Weight::from_parts(6_000_000, 1489)
-			.saturating_add(RocksDbWeight::get().reads(1_u64))
+		Weight::from_parts(27_000_000, 11635)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// Storage: Vault Vaults (r:1 w:1)
+	/// Proof: Vault Vaults (max_values: None, max_size: Some(96), added: 2571, mode: MaxEncodedLen)
+	/// Storage: Vault Unstaking (r:1 w:0)
+	/// Proof: Vault Unstaking (max_values: None, max_size: Some(88), added: 2563, mode: MaxEncodedLen)
+	/// Storage: Vault Shares (r:1 w:1)
+	/// Proof: Vault Shares (max_values: None, max_size: Some(84), added: 2559, mode: MaxEncodedLen)
+	/// Storage: System Account (r:3 w:3)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn deposit() -> Weight {
+		Weight::from_parts(56_000_000, 8799)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: Vault Vaults (r:1 w:1)
+	/// Proof: Vault Vaults (max_values: None, max_size: Some(96), added: 2571, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Vault Shares (r:1 w:0)
+	/// Proof: Vault Shares (max_values: None, max_size: Some(84), added: 2559, mode: MaxEncodedLen)
+	/// Storage: Vault Unstaking (r:1 w:1)
+	/// Proof: Vault Unstaking (max_values: None, max_size: Some(88), added: 2563, mode: MaxEncodedLen)
+	fn unstake() -> Weight {
+		Weight::from_parts(42_000_000, 6196)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: Vault Vaults (r:1 w:1)
+	/// Proof: Vault Vaults (max_values: None, max_size: Some(96), added: 2571, mode: MaxEncodedLen)
+	/// Storage: Vault Unstaking (r:1 w:1)
+	/// Proof: Vault Unstaking (max_values: None, max_size: Some(88), added: 2563, mode: MaxEncodedLen)
+	/// Storage: System Account (r:3 w:3)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Vault Shares (r:1 w:1)
+	/// Proof: Vault Shares (max_values: None, max_size: Some(84), added: 2559, mode: MaxEncodedLen)
+	fn withdraw() -> Weight {
+		Weight::from_parts(60_000_000, 8799)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	/// Storage: Vault Vaults (r:1 w:1)
+	/// Proof: Vault Vaults (max_values: None, max_size: Some(96), added: 2571, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn set_apy() -> Weight {
+		Weight::from_parts(38_000_000, 6196)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: Vault Vaults (r:1 w:0)
+	/// Proof: Vault Vaults (max_values: None, max_size: Some(96), added: 2571, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn fund_reward_pool() -> Weight {
+		Weight::from_parts(34_000_000, 6196)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: Vault NextVaultId (r:1 w:1)
+	/// Proof: Vault NextVaultId (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Vault Vaults (r:0 w:1)
+	/// Proof: Vault Vaults (max_values: None, max_size: Some(96), added: 2571, mode: MaxEncodedLen)
+	fn create_vault() -> Weight {
+		Weight::from_parts(11_000_000, 1489)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
 }
@@ -13,8 +13,8 @@ use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
 	traits::{
-		AccountIdLookup, BlakeTwo256, Block as BlockT, Extrinsic, IdentifyAccount, NumberFor, One,
-		Verify,
+		AccountIdLookup, BlakeTwo256, Block as BlockT, ConvertInto, Extrinsic, IdentifyAccount,
+		NumberFor, One, OpaqueKeys, Verify,
 	},
 	transaction_validity::{TransactionSource, TransactionValidity},
 	ApplyExtrinsicResult, MultiSignature,
@@ -36,7 +36,7 @@ pub use frame_support::{
 		},
 		IdentityFee, Weight,
 	},
-	StorageValue,
+	PalletId, StorageValue,
 };
 pub use frame_system::Call as SystemCall;
 pub use pallet_balances::Call as BalancesCall;
@@ -44,7 +44,7 @@ pub use pallet_timestamp::Call as TimestampCall;
 use pallet_transaction_payment::{ConstFeeMultiplier, CurrencyAdapter, Multiplier};
 #[cfg(any(feature = "std", test))]
 pub use sp_runtime::BuildStorage;
-pub use sp_runtime::{Perbill, Permill};
+pub use sp_runtime::{Perbill, Percent, Permill};
 
 /// Import the template pallet.
 pub use pallet_template;
@@ -95,6 +95,9 @@ pub type Index = u32;
 /// A hash of some data used by the chain.
 pub type Hash = sp_core::H256;
 
+/// A timestamp: milliseconds since the unix epoch.
+pub type Moment = u64;
+
 /// Opaque types. These are used by the CLI to instantiate machinery that don't need to know
 /// the specifics of the runtime. They can then be made to be agnostic over specific formats
 /// of data like extrinsics, allowing for them to continue syncing the network through upgrades
@@ -131,10 +134,12 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 100,
+	// NOTE: Bumped for the storage migrations (`Migrations`) to run on the upgrade.
+	spec_version: 101,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	// NOTE: Bumped for the changed call indices & arguments (e.g. of `EOSIOSystem` & `OCW`).
+	transaction_version: 2,
 	state_version: 1,
 };
 
@@ -250,7 +255,7 @@ impl pallet_grandpa::Config for Runtime {
 
 impl pallet_timestamp::Config for Runtime {
 	/// A timestamp: milliseconds since the unix epoch.
-	type Moment = u64;
+	type Moment = Moment;
 	type OnTimestampSet = Aura;
 	type MinimumPeriod = ConstU64<{ SLOT_DURATION / 2 }>;
 	type WeightInfo = ();
@@ -313,11 +318,27 @@ impl pallet_counter::Config for Runtime {
 	type WeightInfo = pallet_counter::weights::SubstrateWeight<Runtime>;
 }
 
+parameter_types! {
+	pub const VaultPalletId: PalletId = PalletId(*b"py/vault");
+	pub const MaxBalanceSnapshots: u32 = 100;
+}
+
 /// Configure the pallet-vault in pallets/vault.
 impl pallet_vault::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = pallet_vault::weights::SubstrateWeight<Runtime>;
 	type MyCurrency = Balances;
+	type PalletId = VaultPalletId;
+	type VaultOrigin = frame_system::EnsureRoot<AccountId>;
+	type TimeProvider = Timestamp;
+	type MaxBalanceSnapshots = MaxBalanceSnapshots;
+	type BlocksPerYear = BlocksPerYear;
+}
+
+parameter_types! {
+	pub const MaxNamedLocks: u32 = 10;	// per account, within the 50 locks of `Balances`
+	pub const MaxExpiriesPerBlock: u32 = 20;	// rest are swept in `on_idle`
+	pub const MinVestedTransfer: Balance = 100 * EXISTENTIAL_DEPOSIT;
 }
 
 /// Configure the pallet-lockable-currency in pallets/lockable_currency.
@@ -325,6 +346,9 @@ impl pallet_lockable_currency::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = pallet_lockable_currency::weights::SubstrateWeight<Runtime>;
 	type StakeCurrency = Balances;
+	type MaxLocks = MaxNamedLocks;
+	type MaxExpiriesPerBlock = MaxExpiriesPerBlock;
+	type MinVestedTransfer = MinVestedTransfer;
 }
 
 parameter_types! {
@@ -369,29 +393,73 @@ impl pallet_arithmetic::Config for Runtime {
 parameter_types! {
 	pub const MinStakeAmount: Balance = 50 * 1e10 as Balance;	// 50 EOS
 	pub const MaxVotesPerAccount: u32 = 30;	// 30 validators
-	pub const ActiveValidatorsCount: u8 = 21;	// 21 validators
-	pub const StandbyValidatorsCount: u16 = 50;	// 50 validators
+	pub const ActiveValidatorsCount: u32 = 21;	// 21 validators
+	pub const StandbyValidatorsCount: u32 = 50;	// 50 validators
+	pub const MaxProducers: u32 = 1_000;	// registered producers
 	pub const RankingDuration: u32 = 14_400;	// in blocks
 	pub const HeartbeatDuration: u32 = 10;		// in blocks
-	pub const MaxProducerInfoUrlLen: u16 = 256;	// string can't be 512 bytes with utf-16 encoding
+	pub const MaxProducerInfoUrlLen: u32 = 512;	// in bytes (UTF-8)
+	pub const ProducerBond: Balance = 100 * 1e10 as Balance;	// 100 EOS
+	pub const UnbondingDuration: u32 = 3 * DAYS;	// in blocks
+	pub const MaxUnbondingChunks: u32 = 32;
+	pub const BlocksPerWeek: u32 = 7 * DAYS;	// in blocks
+	pub const BlocksPerYear: u32 = 365 * DAYS;	// in blocks
+	pub const ProducersInflationRate: Perbill = Perbill::from_percent(1);	// 1% p.a.
+	pub const PerBlockRewardShare: Perbill = Perbill::from_percent(25);	// 25%
+	pub const ClaimInterval: u32 = DAYS;	// in blocks
+	pub const MinPerVotePay: Balance = 100 * 1e10 as Balance;	// 100 EOS
 }
 
 /// Configure the pallet-eosio-system in pallets/eosio_system.
 impl pallet_eosio_system::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
+	type AuthorityId = pallet_eosio_system::crypto::HeartbeatAuthId;
+	type UnsignedPriority = UnsignedPriority;
 	type WeightInfo = pallet_eosio_system::weights::SubstrateWeight<Runtime>;
 	type MyCurrency = Balances;
 	type MinStakeAmount = MinStakeAmount;
 	type MaxVotesPerAccount = MaxVotesPerAccount;
 	type ActiveValidatorsCount = ActiveValidatorsCount;
 	type StandbyValidatorsCount = StandbyValidatorsCount;
+	type MaxProducers = MaxProducers;
 	type RankingDuration = RankingDuration;
 	type HeartbeatDuration = HeartbeatDuration;
 	type MaxProducerInfoUrlLen = MaxProducerInfoUrlLen;
+	type ProducerBond = ProducerBond;
+	type UnbondingDuration = UnbondingDuration;
+	type MaxUnbondingChunks = MaxUnbondingChunks;
+	type BlocksPerWeek = BlocksPerWeek;
+	type FindAuthor = pallet_session::FindAccountFromAuthorIndex<Self, Aura>;
+	type HasSessionKeys = pallet_eosio_system::HasSessionKeys<Runtime>;
+	type BlocksPerYear = BlocksPerYear;
+	type ProducersInflationRate = ProducersInflationRate;
+	type PerBlockRewardShare = PerBlockRewardShare;
+	type ClaimInterval = ClaimInterval;
+	type MinPerVotePay = MinPerVotePay;
+}
+
+/// Configure the pallet-session to rotate the Aura & GRANDPA authorities as per the producers
+/// elected by pallet-eosio-system.
+impl pallet_session::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type ValidatorId = <Self as frame_system::Config>::AccountId;
+	type ValidatorIdOf = ConvertInto;
+	type ShouldEndSession = pallet_session::PeriodicSessions<RankingDuration, ConstU32<0>>;
+	type NextSessionRotation = pallet_session::PeriodicSessions<RankingDuration, ConstU32<0>>;
+	type SessionManager = EOSIOSystem;
+	type SessionHandler = <opaque::SessionKeys as OpaqueKeys>::KeyTypeIdProviders;
+	type Keys = opaque::SessionKeys;
+	type WeightInfo = pallet_session::weights::SubstrateWeight<Runtime>;
 }
 
 parameter_types! {
 	pub const UnsignedPriority: u64 = 1 << 20; // 2**20
+	pub const OracleThreshold: Percent = Percent::from_percent(50);	// of the oracle members
+	pub const PriceEmaSmoothing: Percent = Percent::from_percent(20);	// weight of a new price
+	pub const MaxPriceStaleness: BlockNumber = HOURS;
+	pub const MaxPriceChange: Percent = Percent::from_percent(10);	// per round, unless confirmed
+	pub const OcwSubmissionStrategy: pallet_ocw::SubmissionStrategy =
+		pallet_ocw::SubmissionStrategy::UnsignedForAll;	// by each local oracle key, without fees
 }
 
 /// Configure the pallet-ocw in pallets/ocw.
@@ -401,7 +469,17 @@ impl pallet_ocw::Config for Runtime {
 	type GracePeriod = ConstU32<5>;
 	type MaxPrices = ConstU32<64>;
 	type UnsignedPriority = UnsignedPriority;
-	type UnsignedInterval = ConstU32<128>;
+	type SubmissionStrategy = OcwSubmissionStrategy;
+	type ConfigOrigin = frame_system::EnsureRoot<AccountId>;
+	type MaxEndpoints = ConstU32<8>;
+	type MaxSourceLen = ConstU32<256>;
+	type MaxMembers = ConstU32<16>;
+	type Threshold = OracleThreshold;
+	type EmaSmoothing = PriceEmaSmoothing;
+	type TimeProvider = Timestamp;
+	type MaxStaleness = MaxPriceStaleness;
+	type MaxPriceChange = MaxPriceChange;
+	type WeightInfo = pallet_ocw::weights::SubstrateWeight<Runtime>;
 }
 
 use codec::Encode;
@@ -470,24 +548,32 @@ construct_runtime!(
 		NodeBlock = opaque::Block,
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
-		System: frame_system,
-		Timestamp: pallet_timestamp,
-		Aura: pallet_aura,
-		Grandpa: pallet_grandpa,
-		Balances: pallet_balances,
-		TransactionPayment: pallet_transaction_payment,
-		Sudo: pallet_sudo,
+		// NOTE: The pallets are ordered by their hooks & genesis, while the explicit indices keep
+		// the ones they were deployed with.
+		System: frame_system = 0,
+		Timestamp: pallet_timestamp = 1,
+		// `Balances` has to come before `Session` as the session keys' owners have to exist at
+		// genesis.
+		Balances: pallet_balances = 4,
+		// `EOSIOSystem` has to come before `Session`, so that the producers are ranked (in
+		// `on_initialize`) before the session rotates in the same block & not one rotation later.
+		EOSIOSystem: pallet_eosio_system = 15,
+		// `Session` has to come before `Aura` & `Grandpa` as it sets their authorities.
+		Session: pallet_session = 17,
+		Aura: pallet_aura = 2,
+		Grandpa: pallet_grandpa = 3,
+		TransactionPayment: pallet_transaction_payment = 5,
+		Sudo: pallet_sudo = 6,
 		// Include the custom logic from the pallet-template in the runtime.
-		TemplateModule: pallet_template,
-		Hello: pallet_hello,
-		Counter: pallet_counter,
-		Vault: pallet_vault,
-		Voting: pallet_voting,
-		LockableCurrency: pallet_lockable_currency,
-		Bank: pallet_bank,
-		Arithmetic: pallet_arithmetic,
-		EOSIOSystem: pallet_eosio_system,
-		OCW: pallet_ocw,
+		TemplateModule: pallet_template = 7,
+		Hello: pallet_hello = 8,
+		Counter: pallet_counter = 9,
+		Vault: pallet_vault = 10,
+		Voting: pallet_voting = 11,
+		LockableCurrency: pallet_lockable_currency = 12,
+		Bank: pallet_bank = 13,
+		Arithmetic: pallet_arithmetic = 14,
+		OCW: pallet_ocw = 16,
 	}
 );
 
@@ -514,6 +600,13 @@ pub type UncheckedExtrinsic =
 	generic::UncheckedExtrinsic<Address, RuntimeCall, Signature, SignedExtra>;
 /// The payload being signed in transactions.
 pub type SignedPayload = generic::SignedPayload<RuntimeCall, SignedExtra>;
+/// Storage migrations run on the runtime upgrade, ahead of the pallets' `on_runtime_upgrade`.
+pub type Migrations = (
+	pallet_vault::migrations::v1::MigrateToBalanceHistory<Runtime>,
+	pallet_eosio_system::migrations::v1::MigrateToV1<Runtime>,
+	pallet_ocw::migrations::v1::MigrateToV1<Runtime>,
+);
+
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
 	Runtime,
@@ -521,6 +614,7 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	Migrations,
 >;
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -715,6 +809,65 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_vault_runtime_api::VaultApi<Block, AccountId, Balance, pallet_vault::VaultId, BlockNumber, Moment>
+		for Runtime
+	{
+		fn total_assets(vault_id: pallet_vault::VaultId) -> Balance {
+			Vault::total_assets(vault_id)
+		}
+		fn shares_of(vault_id: pallet_vault::VaultId, who: AccountId) -> Balance {
+			Vault::shares(vault_id, who)
+		}
+		fn convert_to_shares(vault_id: pallet_vault::VaultId, assets: Balance) -> Balance {
+			Vault::convert_to_shares(vault_id, assets)
+		}
+		fn convert_to_assets(vault_id: pallet_vault::VaultId, shares: Balance) -> Balance {
+			Vault::convert_to_assets(vault_id, shares)
+		}
+		fn preview_deposit(vault_id: pallet_vault::VaultId, assets: Balance) -> Balance {
+			Vault::preview_deposit(vault_id, assets)
+		}
+		fn preview_redeem(vault_id: pallet_vault::VaultId, shares: Balance) -> Balance {
+			Vault::preview_redeem(vault_id, shares)
+		}
+		fn balance_history(
+			who: AccountId,
+		) -> Vec<pallet_vault_runtime_api::DiffBalances<Balance, BlockNumber, Moment>> {
+			Vault::balance_history(&who)
+		}
+	}
+
+	impl pallet_lockable_currency_runtime_api::LockableCurrencyApi<Block, AccountId, Balance, BlockNumber>
+		for Runtime
+	{
+		fn locks(
+			who: AccountId,
+		) -> Vec<pallet_lockable_currency_runtime_api::LockInfo<Balance, BlockNumber>> {
+			LockableCurrency::locks(who).into_inner()
+		}
+	}
+
+	impl pallet_eosio_system_runtime_api::EosioSystemApi<Block, AccountId, Balance, BlockNumber> for Runtime {
+		fn get_producers(
+			limit: u32,
+		) -> Vec<pallet_eosio_system_runtime_api::ProducerDetails<AccountId, BlockNumber>> {
+			EOSIOSystem::get_producers(limit)
+		}
+		fn get_voter(
+			account: AccountId,
+		) -> Option<pallet_eosio_system_runtime_api::VoterDetails<AccountId, Balance, BlockNumber>> {
+			EOSIOSystem::get_voter(account)
+		}
+		fn get_producer_schedule() -> pallet_eosio_system_runtime_api::ProducerSchedule<AccountId> {
+			EOSIOSystem::get_producer_schedule()
+		}
+		fn get_unclaimed_rewards(
+			producer: AccountId,
+		) -> Option<pallet_eosio_system_runtime_api::RewardsEstimate<Balance, BlockNumber>> {
+			EOSIOSystem::get_unclaimed_rewards(producer)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (
@@ -0,0 +1,30 @@
+#!/usr/bin/env bash
+# This script is meant to be run on Unix/Linux based systems
+#
+# Regenerates `pallets/<pallet>/src/weights.rs` of the given pallets (by directory name, e.g.
+# `vault`) on the current machine, or of all the benchmarked pallets if none is given.
+set -e
+
+cd "$(dirname "$0")/.."
+
+PALLETS=("$@")
+if [ ${#PALLETS[@]} -eq 0 ]; then
+   PALLETS=(eosio_system vault lockable-currency ocw)
+fi
+
+echo "*** Building the node with the runtime benchmarks"
+cargo build --release -p node-template --features runtime-benchmarks
+
+for pallet in "${PALLETS[@]}"; do
+   echo "*** Benchmarking pallet_${pallet//-/_}"
+   ./target/release/node-template benchmark pallet \
+      --chain dev \
+      --pallet "pallet_${pallet//-/_}" \
+      --extrinsic '*' \
+      --steps 50 \
+      --repeat 20 \
+      --execution wasm \
+      --wasm-execution compiled \
+      --output "pallets/$pallet/src/weights.rs" \
+      --template .maintain/frame-weight-template.hbs
+done